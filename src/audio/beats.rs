@@ -0,0 +1,163 @@
+//! Beat rate measurement from the amplitude envelope.
+//!
+//! Two strings a few tenths of a hertz apart are hard to tell apart by
+//! pitch, but their sum swells and fades at the difference frequency.
+//! Counting those fades over a window gives the beat rate directly.
+
+/// Default envelope block length in seconds.
+pub const BLOCK_SECS: f32 = 0.01;
+/// Smallest envelope swing, relative to its peak, that counts as beating.
+const MIN_MODULATION: f32 = 0.2;
+/// Envelope level (fraction of its range) a fade must drop below.
+const LOW_THRESHOLD: f32 = 0.3;
+/// Envelope level (fraction of its range) the swell must return above.
+const HIGH_THRESHOLD: f32 = 0.7;
+
+/// Measures beats per second between two sounding strings.
+pub struct BeatCounter {
+    sample_rate: u32,
+    block_secs: f32,
+}
+
+impl BeatCounter {
+    /// Create a beat counter.
+    pub fn new(sample_rate: u32) -> Self {
+        Self {
+            sample_rate,
+            block_secs: BLOCK_SECS,
+        }
+    }
+
+    /// Set the envelope block length in seconds.
+    ///
+    /// Blocks should span at least one period of the lowest sounding note,
+    /// or the envelope ripples with the waveform itself.
+    pub fn with_block_secs(mut self, secs: f32) -> Self {
+        self.block_secs = secs;
+        self
+    }
+
+    /// Amplitude envelope as RMS per block.
+    pub fn envelope(&self, samples: &[f32]) -> Vec<f32> {
+        let block = self.block_len();
+        samples
+            .chunks_exact(block)
+            .map(|chunk| (chunk.iter().map(|s| s * s).sum::<f32>() / block as f32).sqrt())
+            .collect()
+    }
+
+    /// Envelope swing relative to its peak (0 = steady, 1 = fades to silence).
+    pub fn modulation_depth(&self, samples: &[f32]) -> f32 {
+        let envelope = self.envelope(samples);
+        let max = envelope.iter().copied().fold(0.0_f32, f32::max);
+        let min = envelope.iter().copied().fold(f32::MAX, f32::min);
+        if max <= 0.0 {
+            0.0
+        } else {
+            (max - min) / max
+        }
+    }
+
+    /// Measure the beat rate in Hz.
+    ///
+    /// Returns `None` when the envelope is flat or fewer than two fades
+    /// fit in the window.
+    pub fn beats_per_second(&self, samples: &[f32]) -> Option<f32> {
+        let envelope = self.envelope(samples);
+
+        let max = envelope.iter().copied().fold(0.0_f32, f32::max);
+        let min = envelope.iter().copied().fold(f32::MAX, f32::min);
+        let range = max - min;
+        if max <= 0.0 || range / max < MIN_MODULATION {
+            return None;
+        }
+
+        let low = min + LOW_THRESHOLD * range;
+        let high = min + HIGH_THRESHOLD * range;
+
+        // Block index of the deepest point of each fade
+        let mut minima = Vec::new();
+        let mut in_fade: Option<(usize, f32)> = None;
+        for (i, &level) in envelope.iter().enumerate() {
+            match in_fade {
+                Some((_, deepest)) if level < deepest => in_fade = Some((i, level)),
+                Some((at, _)) if level > high => {
+                    minima.push(at);
+                    in_fade = None;
+                }
+                None if level < low => in_fade = Some((i, level)),
+                _ => {}
+            }
+        }
+
+        if minima.len() < 2 {
+            return None;
+        }
+
+        let span_blocks = (minima[minima.len() - 1] - minima[0]) as f32;
+        let span_secs = span_blocks * self.block_len() as f32 / self.sample_rate as f32;
+        Some((minima.len() - 1) as f32 / span_secs)
+    }
+
+    fn block_len(&self) -> usize {
+        ((self.sample_rate as f32 * self.block_secs) as usize).max(1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::audio::traits::{AudioSource, TestAudioSource};
+
+    const SAMPLE_RATE: u32 = 44100;
+
+    fn two_tones(a: f32, b: f32, secs: f32) -> Vec<f32> {
+        let mut first = TestAudioSource::sine(a, secs, SAMPLE_RATE);
+        let mut second = TestAudioSource::sine(b, secs, SAMPLE_RATE);
+        let len = (SAMPLE_RATE as f32 * secs) as usize;
+
+        let mut x = vec![0.0; len];
+        let mut y = vec![0.0; len];
+        first.read_samples(&mut x);
+        second.read_samples(&mut y);
+        x.iter().zip(&y).map(|(a, b)| 0.5 * (a + b)).collect()
+    }
+
+    #[test]
+    fn test_three_hz_beat() {
+        let samples = two_tones(440.0, 443.0, 2.0);
+        let bps = BeatCounter::new(SAMPLE_RATE)
+            .beats_per_second(&samples)
+            .expect("Should find beats");
+        assert!((bps - 3.0).abs() < 0.1, "got {bps}");
+    }
+
+    #[test]
+    fn test_slow_beat_in_bass() {
+        let samples = two_tones(110.0, 111.0, 3.0);
+        let bps = BeatCounter::new(SAMPLE_RATE)
+            .beats_per_second(&samples)
+            .expect("Should find beats");
+        assert!((bps - 1.0).abs() < 0.1, "got {bps}");
+    }
+
+    #[test]
+    fn test_modulation_depth() {
+        let counter = BeatCounter::new(SAMPLE_RATE);
+        let steady = TestAudioSource::sine(440.0, 1.0, SAMPLE_RATE);
+        assert!(counter.modulation_depth(steady.samples()) < 0.05);
+
+        // Equal-amplitude beating fades all the way out
+        let beating = two_tones(440.0, 443.0, 1.0);
+        assert!(counter.modulation_depth(&beating) > 0.9);
+    }
+
+    #[test]
+    fn test_unison_has_no_beats() {
+        let samples = two_tones(440.0, 440.0, 2.0);
+        assert_eq!(
+            BeatCounter::new(SAMPLE_RATE).beats_per_second(&samples),
+            None
+        );
+    }
+}
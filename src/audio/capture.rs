@@ -1,8 +1,16 @@
 //! Microphone input capture using cpal.
 
+use super::filter::PreFilter;
+use super::gate;
+use super::reference::RegisterGain;
 use super::traits::AudioSource;
 use cpal::traits::{DeviceTrait, HostTrait, StreamTrait};
+use std::ops::RangeInclusive;
 use std::sync::{Arc, Mutex};
+use std::time::Instant;
+
+/// Device sample rates the window and frequency math can handle.
+pub const SUPPORTED_SAMPLE_RATES: RangeInclusive<u32> = 8000..=192_000;
 
 /// Error type for audio capture.
 #[derive(Debug, thiserror::Error)]
@@ -15,6 +23,74 @@ pub enum CaptureError {
     BuildStreamError(#[from] cpal::BuildStreamError),
     #[error("Failed to play stream: {0}")]
     PlayStreamError(#[from] cpal::PlayStreamError),
+    #[error("Failed to pause stream: {0}")]
+    PauseStreamError(#[from] cpal::PauseStreamError),
+    #[error("Unsupported device sample rate: {0} Hz (expected 8000-192000 Hz)")]
+    UnsupportedSampleRate(u32),
+    #[error("No output device named \"{name}\" (available: {})", available.join(", "))]
+    OutputDeviceNotFound {
+        name: String,
+        available: Vec<String>,
+    },
+}
+
+/// Check that a device's sample rate is one pianito can work with.
+pub fn validate_sample_rate(sample_rate: u32) -> Result<u32, CaptureError> {
+    if SUPPORTED_SAMPLE_RATES.contains(&sample_rate) {
+        Ok(sample_rate)
+    } else {
+        Err(CaptureError::UnsupportedSampleRate(sample_rate))
+    }
+}
+
+/// A stream that tracks whether it is running, so pause/resume are idempotent.
+struct StreamHandle<S: StreamTrait> {
+    stream: S,
+    paused: bool,
+}
+
+impl<S: StreamTrait> StreamHandle<S> {
+    /// Start playing `stream` and wrap it.
+    fn start(stream: S) -> Result<Self, CaptureError> {
+        stream.play()?;
+        Ok(Self {
+            stream,
+            paused: false,
+        })
+    }
+
+    /// Stop the stream callback, releasing the device.
+    fn pause(&mut self) -> Result<(), CaptureError> {
+        if !self.paused {
+            self.stream.pause()?;
+            self.paused = true;
+        }
+        Ok(())
+    }
+
+    /// Restart a paused stream.
+    fn resume(&mut self) -> Result<(), CaptureError> {
+        if self.paused {
+            self.stream.play()?;
+            self.paused = false;
+        }
+        Ok(())
+    }
+
+    fn is_paused(&self) -> bool {
+        self.paused
+    }
+}
+
+/// Samples kept for pitch detection (~0.5 second at 44.1kHz).
+const MAX_BUFFER_SAMPLES: usize = 22050;
+
+/// Most recent samples checked for clipping (~0.1 second at 44.1kHz).
+const CLIP_WINDOW_SAMPLES: usize = 4410;
+
+/// Age in milliseconds of audio whose newest sample arrived at `newest`.
+pub fn latency_ms(newest: Instant, now: Instant) -> f32 {
+    now.saturating_duration_since(newest).as_secs_f32() * 1000.0
 }
 
 /// Shared buffer for audio samples.
@@ -22,11 +98,158 @@ struct SharedBuffer {
     samples: Vec<f32>,
     /// Flag to indicate new samples are available.
     new_data: bool,
+    /// Samples pushed since the last read.
+    unread: usize,
+    /// How many samples at the end of the last read were new to it.
+    fresh: usize,
+    /// When the newest sample arrived.
+    newest_at: Option<Instant>,
+    /// Filters applied to samples as they arrive.
+    filter: PreFilter,
+}
+
+impl SharedBuffer {
+    fn with_capacity(capacity: usize) -> Self {
+        Self {
+            samples: Vec::with_capacity(capacity),
+            new_data: false,
+            unread: 0,
+            fresh: 0,
+            newest_at: None,
+            filter: PreFilter::new(),
+        }
+    }
+
+    /// Append mono samples, keeping only the most recent window.
+    fn push(&mut self, mono: impl IntoIterator<Item = f32>) {
+        self.push_at(mono, Instant::now());
+    }
+
+    /// Append mono samples that arrived at `captured_at`.
+    fn push_at(&mut self, mono: impl IntoIterator<Item = f32>, captured_at: Instant) {
+        let filter = &mut self.filter;
+        let before = self.samples.len();
+        self.samples
+            .extend(mono.into_iter().map(|x| filter.process(x)));
+        self.unread += self.samples.len() - before;
+        self.newest_at = Some(captured_at);
+
+        if self.samples.len() > MAX_BUFFER_SAMPLES {
+            let excess = self.samples.len() - MAX_BUFFER_SAMPLES;
+            self.samples.drain(0..excess);
+        }
+
+        self.new_data = true;
+    }
+
+    /// Copy the most recent samples into `buffer` if new data arrived.
+    fn read_into(&mut self, buffer: &mut [f32]) -> usize {
+        // Only return samples if we have new data
+        if !self.new_data {
+            self.fresh = 0;
+            return 0;
+        }
+
+        // Copy the most recent samples (sliding window)
+        let available = self.samples.len();
+        let to_read = buffer.len().min(available);
+
+        if to_read > 0 {
+            let start = available - to_read;
+            buffer[..to_read].copy_from_slice(&self.samples[start..]);
+        }
+
+        self.fresh = self.unread.min(to_read);
+        self.unread = 0;
+        self.new_data = false;
+        to_read
+    }
+
+    /// Copy every buffered sample, without marking them as read.
+    fn snapshot(&self) -> Vec<f32> {
+        self.samples.clone()
+    }
+
+    /// Check if the most recent samples are clipping.
+    fn is_clipping(&self) -> bool {
+        let start = self.samples.len().saturating_sub(CLIP_WINDOW_SAMPLES);
+        gate::is_clipping(&self.samples[start..])
+    }
+
+    /// Drop all buffered samples.
+    fn flush(&mut self) {
+        self.samples.clear();
+        self.new_data = false;
+        self.unread = 0;
+        self.fresh = 0;
+        self.newest_at = None;
+    }
+}
+
+/// Description of an input device's default stream.
+#[derive(Debug, Clone, PartialEq)]
+pub struct InputInfo {
+    /// Device name.
+    pub name: String,
+    /// Sample rate in Hz.
+    pub sample_rate: u32,
+    /// Number of channels.
+    pub channels: u16,
+    /// Sample format, e.g. "f32".
+    pub sample_format: String,
+}
+
+/// Names of all input devices on the default host.
+pub fn input_device_names() -> Vec<String> {
+    cpal::default_host()
+        .input_devices()
+        .map(|devices| devices.filter_map(|d| d.name().ok()).collect())
+        .unwrap_or_default()
+}
+
+/// Pick the device called `name` from `(name, device)` pairs.
+fn find_device<D>(devices: Vec<(String, D)>, name: &str) -> Result<D, CaptureError> {
+    let available: Vec<String> = devices.iter().map(|(n, _)| n.clone()).collect();
+    devices
+        .into_iter()
+        .find(|(n, _)| n == name)
+        .map(|(_, device)| device)
+        .ok_or_else(|| CaptureError::OutputDeviceNotFound {
+            name: name.to_string(),
+            available,
+        })
+}
+
+/// Output devices on the default host with their names.
+fn named_output_devices() -> Vec<(String, cpal::Device)> {
+    cpal::default_host()
+        .output_devices()
+        .map(|devices| {
+            devices
+                .filter_map(|d| d.name().ok().map(|name| (name, d)))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Describe the default input device and its default stream config.
+pub fn default_input_info() -> Result<InputInfo, CaptureError> {
+    let device = cpal::default_host()
+        .default_input_device()
+        .ok_or(CaptureError::NoInputDevice)?;
+    let config = device.default_input_config()?;
+
+    Ok(InputInfo {
+        name: device.name().unwrap_or_else(|_| "(unnamed)".to_string()),
+        sample_rate: config.sample_rate().0,
+        channels: config.channels(),
+        sample_format: config.sample_format().to_string(),
+    })
 }
 
 /// Microphone capture using the system's default input device.
 pub struct MicCapture {
-    _stream: cpal::Stream,
+    stream: StreamHandle<cpal::Stream>,
     buffer: Arc<Mutex<SharedBuffer>>,
     sample_rate: u32,
 }
@@ -41,12 +264,11 @@ impl MicCapture {
             .ok_or(CaptureError::NoInputDevice)?;
 
         let config = device.default_input_config()?;
-        let sample_rate = config.sample_rate().0;
+        let sample_rate = validate_sample_rate(config.sample_rate().0)?;
 
-        let buffer = Arc::new(Mutex::new(SharedBuffer {
-            samples: Vec::with_capacity(sample_rate as usize), // 1 second buffer
-            new_data: false,
-        }));
+        let buffer = Arc::new(Mutex::new(SharedBuffer::with_capacity(
+            sample_rate as usize, // 1 second buffer
+        )));
 
         let buffer_clone = Arc::clone(&buffer);
 
@@ -64,15 +286,78 @@ impl MicCapture {
             }
         };
 
-        stream.play()?;
-
         Ok(Self {
-            _stream: stream,
+            stream: StreamHandle::start(stream)?,
             buffer,
             sample_rate,
         })
     }
 
+    /// Stop capturing and release the input device.
+    pub fn pause(&mut self) -> Result<(), CaptureError> {
+        self.stream.pause()
+    }
+
+    /// Resume capturing. Audio buffered before the pause is discarded.
+    pub fn resume(&mut self) -> Result<(), CaptureError> {
+        if self.stream.is_paused() {
+            self.buffer.lock().unwrap().flush();
+        }
+        self.stream.resume()
+    }
+
+    /// Check if capture is paused.
+    pub fn is_paused(&self) -> bool {
+        self.stream.is_paused()
+    }
+
+    /// Copy the current sample window, oldest first, for re-running detection.
+    ///
+    /// Unlike `read_samples`, this leaves the buffer untouched, so the normal
+    /// read loop still sees the same audio.
+    pub fn snapshot(&self) -> Vec<f32> {
+        self.buffer.lock().unwrap().snapshot()
+    }
+
+    /// Check if the input is clipping, meaning the gain is set too high.
+    pub fn is_clipping(&self) -> bool {
+        self.buffer.lock().unwrap().is_clipping()
+    }
+
+    /// High-pass the input at `cutoff_hz` before detection, e.g. 20 Hz to
+    /// drop rumble below A0.
+    pub fn with_highpass(self, cutoff_hz: f32) -> Self {
+        self.add_filter(|filter, rate| filter.with_highpass(cutoff_hz, rate))
+    }
+
+    /// Notch out mains hum at `mains_hz` (50 or 60) before detection.
+    pub fn with_hum_notch(self, mains_hz: f32) -> Self {
+        self.add_filter(|filter, rate| filter.with_hum_notch(mains_hz, rate))
+    }
+
+    fn add_filter(self, add: impl FnOnce(PreFilter, u32) -> PreFilter) -> Self {
+        {
+            let mut buffer = self.buffer.lock().unwrap();
+            let filter = std::mem::take(&mut buffer.filter);
+            buffer.filter = add(filter, self.sample_rate);
+        }
+        self
+    }
+
+    /// How many samples at the end of the latest read arrived since the
+    /// read before it.
+    ///
+    /// Reads return a sliding window, so consecutive reads overlap; only
+    /// this tail is audio the caller hasn't seen yet.
+    pub fn fresh_samples(&self) -> usize {
+        self.buffer.lock().unwrap().fresh
+    }
+
+    /// When the newest buffered sample arrived, for measuring latency.
+    pub fn newest_sample_at(&self) -> Option<Instant> {
+        self.buffer.lock().unwrap().newest_at
+    }
+
     fn build_stream_f32(
         device: &cpal::Device,
         config: &cpal::StreamConfig,
@@ -83,22 +368,11 @@ impl MicCapture {
         device.build_input_stream(
             config,
             move |data: &[f32], _: &cpal::InputCallbackInfo| {
-                let mut buf = buffer.lock().unwrap();
-
                 // Convert to mono and append to buffer
-                for frame in data.chunks(channels) {
-                    let mono: f32 = frame.iter().sum::<f32>() / channels as f32;
-                    buf.samples.push(mono);
-                }
-
-                // Keep buffer at reasonable size (~0.5 second for pitch detection)
-                let max_samples = 22050;
-                if buf.samples.len() > max_samples {
-                    let excess = buf.samples.len() - max_samples;
-                    buf.samples.drain(0..excess);
-                }
-
-                buf.new_data = true;
+                let mono = data
+                    .chunks(channels)
+                    .map(|frame| frame.iter().sum::<f32>() / channels as f32);
+                buffer.lock().unwrap().push(mono);
             },
             |err| {
                 eprintln!("Audio capture error: {}", err);
@@ -117,23 +391,11 @@ impl MicCapture {
         device.build_input_stream(
             config,
             move |data: &[i16], _: &cpal::InputCallbackInfo| {
-                let mut buf = buffer.lock().unwrap();
-
                 // Convert to mono f32 and append to buffer
-                for frame in data.chunks(channels) {
-                    let mono: f32 =
-                        frame.iter().map(|&s| s as f32 / 32768.0).sum::<f32>() / channels as f32;
-                    buf.samples.push(mono);
-                }
-
-                // Keep buffer at reasonable size (~0.5 second for pitch detection)
-                let max_samples = 22050;
-                if buf.samples.len() > max_samples {
-                    let excess = buf.samples.len() - max_samples;
-                    buf.samples.drain(0..excess);
-                }
-
-                buf.new_data = true;
+                let mono = data.chunks(channels).map(|frame| {
+                    frame.iter().map(|&s| s as f32 / 32768.0).sum::<f32>() / channels as f32
+                });
+                buffer.lock().unwrap().push(mono);
             },
             |err| {
                 eprintln!("Audio capture error: {}", err);
@@ -145,50 +407,54 @@ impl MicCapture {
 
 impl AudioSource for MicCapture {
     fn read_samples(&mut self, buffer: &mut [f32]) -> usize {
-        let mut buf = self.buffer.lock().unwrap();
-
-        // Only return samples if we have new data
-        if !buf.new_data {
-            return 0;
-        }
-
-        // Copy the most recent samples (sliding window)
-        let available = buf.samples.len();
-        let to_read = buffer.len().min(available);
-
-        if to_read > 0 {
-            // Copy from the end of the buffer (most recent samples)
-            let start = available - to_read;
-            buffer[..to_read].copy_from_slice(&buf.samples[start..]);
-        }
-
-        buf.new_data = false;
-        to_read
+        self.buffer.lock().unwrap().read_into(buffer)
     }
 
     fn sample_rate(&self) -> u32 {
         self.sample_rate
     }
+
+    fn flush(&mut self) {
+        self.buffer.lock().unwrap().flush();
+    }
 }
 
 /// Audio output sink using cpal.
 pub struct AudioOutput {
-    _stream: cpal::Stream,
+    stream: StreamHandle<cpal::Stream>,
     buffer: Arc<Mutex<Vec<f32>>>,
     sample_rate: u32,
+    gain: RegisterGain,
 }
 
 impl AudioOutput {
     /// Create a new audio output instance.
     pub fn new() -> Result<Self, CaptureError> {
-        let host = cpal::default_host();
-
-        let device = host
+        let device = cpal::default_host()
             .default_output_device()
             .ok_or(CaptureError::NoInputDevice)?;
+        Self::open(&device)
+    }
+
+    /// Create an output on the device called `name`, e.g. an audio interface
+    /// rather than the laptop speakers.
+    pub fn with_device(name: &str) -> Result<Self, CaptureError> {
+        let device = find_device(named_output_devices(), name)?;
+        Self::open(&device)
+    }
+
+    /// Names of all output devices.
+    pub fn list_devices() -> Vec<String> {
+        named_output_devices()
+            .into_iter()
+            .map(|(name, _)| name)
+            .collect()
+    }
 
+    /// Start an output stream on `device`.
+    fn open(device: &cpal::Device) -> Result<Self, CaptureError> {
         let config = device.default_output_config()?;
-        let sample_rate = config.sample_rate().0;
+        let sample_rate = validate_sample_rate(config.sample_rate().0)?;
 
         let buffer: Arc<Mutex<Vec<f32>>> = Arc::new(Mutex::new(Vec::new()));
         let buffer_clone = Arc::clone(&buffer);
@@ -214,15 +480,47 @@ impl AudioOutput {
             None,
         )?;
 
-        stream.play()?;
-
         Ok(Self {
-            _stream: stream,
+            stream: StreamHandle::start(stream)?,
             buffer,
             sample_rate,
+            gain: RegisterGain::default(),
         })
     }
 
+    /// Apply per-register gain to played tones.
+    pub fn with_gain(mut self, gain: RegisterGain) -> Self {
+        self.gain = gain;
+        self
+    }
+
+    /// Stop the output callback and release the device. Queued samples are dropped.
+    pub fn pause(&mut self) -> Result<(), CaptureError> {
+        self.stream.pause()?;
+        self.clear();
+        Ok(())
+    }
+
+    /// Resume playback.
+    pub fn resume(&mut self) -> Result<(), CaptureError> {
+        self.stream.resume()
+    }
+
+    /// Check if output is paused.
+    pub fn is_paused(&self) -> bool {
+        self.stream.is_paused()
+    }
+
+    /// Seconds of audio still queued for playback.
+    pub fn queued_secs(&self) -> f32 {
+        self.buffer.lock().unwrap().len() as f32 / self.sample_rate as f32
+    }
+
+    /// Drop any queued samples.
+    pub fn clear(&self) {
+        self.buffer.lock().unwrap().clear();
+    }
+
     /// Queue samples for playback.
     pub fn queue(&self, samples: &[f32]) {
         let mut buf = self.buffer.lock().unwrap();
@@ -239,9 +537,11 @@ impl AudioOutput {
         let num_samples = (self.sample_rate as f32 * duration) as usize;
         let mut samples = Vec::with_capacity(num_samples);
 
+        let amplitude = (0.3 * self.gain.gain_for(frequency)).min(1.0);
+
         for i in 0..num_samples {
             let t = i as f32 / self.sample_rate as f32;
-            let sample = 0.3 * (2.0 * std::f32::consts::PI * frequency * t).sin();
+            let sample = amplitude * (2.0 * std::f32::consts::PI * frequency * t).sin();
             samples.push(sample);
         }
 
@@ -249,3 +549,241 @@ impl AudioOutput {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::audio::PitchDetector;
+    use std::cell::Cell;
+    use std::time::Duration;
+
+    /// Stream stub that records whether it is playing.
+    #[derive(Default)]
+    struct MockStream {
+        playing: Cell<bool>,
+        plays: Cell<u32>,
+        pauses: Cell<u32>,
+    }
+
+    impl StreamTrait for MockStream {
+        fn play(&self) -> Result<(), cpal::PlayStreamError> {
+            self.playing.set(true);
+            self.plays.set(self.plays.get() + 1);
+            Ok(())
+        }
+
+        fn pause(&self) -> Result<(), cpal::PauseStreamError> {
+            self.playing.set(false);
+            self.pauses.set(self.pauses.get() + 1);
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_stream_pause_then_resume() {
+        let mut handle = StreamHandle::start(MockStream::default()).unwrap();
+        assert!(handle.stream.playing.get());
+
+        handle.pause().unwrap();
+        assert!(handle.is_paused());
+        assert!(!handle.stream.playing.get());
+
+        handle.resume().unwrap();
+        assert!(!handle.is_paused());
+        assert!(handle.stream.playing.get());
+    }
+
+    #[test]
+    fn test_stream_pause_resume_idempotent() {
+        let mut handle = StreamHandle::start(MockStream::default()).unwrap();
+
+        handle.resume().unwrap();
+        handle.pause().unwrap();
+        handle.pause().unwrap();
+        handle.resume().unwrap();
+
+        assert_eq!(handle.stream.plays.get(), 2);
+        assert_eq!(handle.stream.pauses.get(), 1);
+    }
+
+    #[test]
+    fn test_find_device_by_name() {
+        let devices = vec![
+            ("Built-in Output".to_string(), 1),
+            ("Studio Monitors".to_string(), 2),
+        ];
+        assert_eq!(find_device(devices.clone(), "Studio Monitors").unwrap(), 2);
+
+        let err = find_device(devices, "Headphones").unwrap_err();
+        match &err {
+            CaptureError::OutputDeviceNotFound { name, available } => {
+                assert_eq!(name, "Headphones");
+                assert_eq!(available, &["Built-in Output", "Studio Monitors"]);
+            }
+            other => panic!("unexpected error: {other}"),
+        }
+        assert_eq!(
+            err.to_string(),
+            "No output device named \"Headphones\" (available: Built-in Output, Studio Monitors)"
+        );
+    }
+
+    #[test]
+    fn test_validate_sample_rate() {
+        assert_eq!(validate_sample_rate(44100).unwrap(), 44100);
+        assert_eq!(validate_sample_rate(8000).unwrap(), 8000);
+        assert_eq!(validate_sample_rate(192_000).unwrap(), 192_000);
+
+        for rate in [0, 4000, 1_000_000, u32::MAX] {
+            let err = validate_sample_rate(rate).unwrap_err();
+            assert!(matches!(err, CaptureError::UnsupportedSampleRate(r) if r == rate));
+        }
+        assert!(validate_sample_rate(0)
+            .unwrap_err()
+            .to_string()
+            .contains("0 Hz"));
+    }
+
+    #[test]
+    fn test_shared_buffer_reads_latest_window() {
+        let mut shared = SharedBuffer::with_capacity(16);
+        shared.push([0.1, 0.2, 0.3, 0.4]);
+
+        let mut out = [0.0; 2];
+        assert_eq!(shared.read_into(&mut out), 2);
+        assert_eq!(out, [0.3, 0.4]);
+
+        // No new data since last read
+        assert_eq!(shared.read_into(&mut out), 0);
+    }
+
+    #[test]
+    fn test_overlapping_reads_count_only_fresh_samples() {
+        let mut shared = SharedBuffer::with_capacity(16);
+        let mut out = [0.0; 4];
+        shared.push([0.1, 0.2, 0.3, 0.4]);
+        assert_eq!(shared.read_into(&mut out), 4);
+        assert_eq!(shared.fresh, 4);
+
+        // The next window repeats two samples the last read already returned
+        shared.push([0.5, 0.6]);
+        assert_eq!(shared.read_into(&mut out), 4);
+        assert_eq!(out, [0.3, 0.4, 0.5, 0.6]);
+        assert_eq!(shared.fresh, 2);
+
+        assert_eq!(shared.read_into(&mut out), 0);
+        assert_eq!(shared.fresh, 0);
+    }
+
+    #[test]
+    fn test_snapshot_matches_read() {
+        let mut shared = SharedBuffer::with_capacity(16);
+        shared.push((0..1000).map(|i| i as f32 / 1000.0));
+
+        let snapshot = shared.snapshot();
+        let mut out = vec![0.0; snapshot.len()];
+        assert_eq!(shared.read_into(&mut out), snapshot.len());
+        assert_eq!(out, snapshot);
+
+        // A shorter read returns the snapshot's most recent samples
+        shared.push([1.0]);
+        let snapshot = shared.snapshot();
+        let mut tail = [0.0; 64];
+        assert_eq!(shared.read_into(&mut tail), 64);
+        assert_eq!(tail[..], snapshot[snapshot.len() - 64..]);
+    }
+
+    #[test]
+    fn test_shared_buffer_caps_size() {
+        let mut shared = SharedBuffer::with_capacity(16);
+        shared.push(vec![0.5; MAX_BUFFER_SAMPLES + 100]);
+        assert_eq!(shared.samples.len(), MAX_BUFFER_SAMPLES);
+    }
+
+    #[test]
+    fn test_reads_warm_up_after_flush() {
+        let detector = PitchDetector::new(44100);
+        let mut shared = SharedBuffer::with_capacity(16);
+        shared.push(vec![0.5; 8000]);
+        shared.flush();
+
+        // Callback-sized chunks trickle in after the flush
+        let mut out = vec![0.0; 4410];
+        let mut warm_reads = 0;
+        loop {
+            shared.push(vec![0.5; 512]);
+            let read = shared.read_into(&mut out);
+            if !detector.is_warming_up(read) {
+                break;
+            }
+            warm_reads += 1;
+        }
+
+        assert_eq!(warm_reads, detector.min_samples() / 512);
+    }
+
+    #[test]
+    fn test_shared_buffer_detects_clipping() {
+        let mut shared = SharedBuffer::with_capacity(16);
+        assert!(!shared.is_clipping());
+
+        let quiet: Vec<f32> = (0..CLIP_WINDOW_SAMPLES)
+            .map(|i| 0.1 * (2.0 * std::f32::consts::PI * 440.0 * i as f32 / 44100.0).sin())
+            .collect();
+        shared.push(quiet.iter().copied());
+        assert!(!shared.is_clipping());
+
+        let clipped = (0..CLIP_WINDOW_SAMPLES).map(|i| if i % 2 == 0 { 1.0 } else { -1.0 });
+        shared.push(clipped);
+        assert!(shared.is_clipping());
+
+        // Only the recent window counts once the level comes back down
+        shared.push(quiet);
+        assert!(!shared.is_clipping());
+    }
+
+    #[test]
+    fn test_filter_applies_as_samples_arrive() {
+        let mut shared = SharedBuffer::with_capacity(16);
+        shared.filter = PreFilter::new().with_hum_notch(60.0, 44100);
+        let hum = (0..MAX_BUFFER_SAMPLES)
+            .map(|i| 0.5 * (std::f32::consts::TAU * 60.0 * i as f32 / 44100.0).sin());
+        shared.push(hum);
+
+        let tail = &shared.snapshot()[MAX_BUFFER_SAMPLES / 2..];
+        assert!(gate::rms(tail) < 0.05);
+    }
+
+    #[test]
+    fn test_latency_from_newest_sample() {
+        let start = Instant::now();
+        let mut shared = SharedBuffer::with_capacity(16);
+        shared.push_at([0.1, 0.2], start);
+        shared.push_at([0.3], start + Duration::from_millis(20));
+
+        let newest = shared.newest_at.unwrap();
+        let now = start + Duration::from_millis(65);
+        assert!((latency_ms(newest, now) - 45.0).abs() < 1e-3);
+
+        // A clock reading older than the audio is never negative
+        assert_eq!(latency_ms(newest, start), 0.0);
+
+        shared.flush();
+        assert_eq!(shared.newest_at, None);
+    }
+
+    #[test]
+    fn test_flush_discards_stale_samples() {
+        let mut shared = SharedBuffer::with_capacity(16);
+        shared.push([0.1, 0.2, 0.3]);
+        shared.flush();
+
+        let mut out = [0.0; 4];
+        assert_eq!(shared.read_into(&mut out), 0);
+
+        // Only fresh audio is returned once it arrives
+        shared.push([0.9]);
+        assert_eq!(shared.read_into(&mut out), 1);
+        assert_eq!(out[0], 0.9);
+    }
+}
@@ -0,0 +1,100 @@
+//! Downsampling before detection, for CPU-constrained devices.
+//!
+//! YIN's cost grows with the number of samples it compares, so detecting on
+//! audio reduced by an integer factor is much cheaper. Mid and high notes
+//! still have plenty of samples per period at the lower rate; the bass does
+//! not gain enough to be worth the risk, so it always runs at full rate.
+
+/// Lowest target that may be decimated (about G3).
+pub const DECIMATE_MIN_HZ: f32 = 200.0;
+/// Samples per period the decimated rate must still provide; fewer lets
+/// YIN's interpolation drift by more than a cent.
+pub const MIN_SAMPLES_PER_PERIOD: f32 = 32.0;
+
+/// Reduce `samples` by `factor`, averaging each group to filter out
+/// content the lower rate can't represent.
+pub fn decimate(samples: &[f32], factor: usize) -> Vec<f32> {
+    if factor <= 1 {
+        return samples.to_vec();
+    }
+    samples
+        .chunks_exact(factor)
+        .map(|group| group.iter().sum::<f32>() / factor as f32)
+        .collect()
+}
+
+/// Decimation factor to use for a note at `target_hz`: `factor` when the
+/// reduced rate still resolves the note exactly, otherwise 1 (full rate).
+pub fn factor_for(target_hz: f32, sample_rate: u32, factor: usize) -> usize {
+    let usable = factor > 1
+        && target_hz >= DECIMATE_MIN_HZ
+        // An inexact rate would shift every reading
+        && sample_rate.is_multiple_of(factor as u32)
+        && sample_rate as f32 / factor as f32 >= MIN_SAMPLES_PER_PERIOD * target_hz;
+    if usable {
+        factor
+    } else {
+        1
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::audio::{PitchDetector, TestAudioSource};
+    use crate::tuning::Temperament;
+
+    const SAMPLE_RATE: u32 = 44100;
+
+    fn tone(freq: f32, secs: f32) -> Vec<f32> {
+        TestAudioSource::sine(freq, secs, SAMPLE_RATE)
+            .samples()
+            .to_vec()
+    }
+
+    fn decimated_cents(freq: f32, factor: usize) -> f32 {
+        let samples = decimate(&tone(freq, 0.1), factor);
+        let detector = PitchDetector::new(SAMPLE_RATE / factor as u32);
+        let result = detector.detect(&samples).expect("Should detect the tone");
+        Temperament::new().cents_from_target(result.frequency, freq)
+    }
+
+    #[test]
+    fn test_decimated_a4_within_a_cent() {
+        let factor = factor_for(440.0, SAMPLE_RATE, 2);
+        assert_eq!(factor, 2);
+        assert_eq!(decimate(&tone(440.0, 0.1), factor).len(), 4410 / 2);
+
+        let cents = decimated_cents(440.0, factor);
+        assert!(cents.abs() < 1.0, "off by {cents} cents");
+    }
+
+    #[test]
+    fn test_chosen_factor_stays_within_a_cent() {
+        for freq in [200.0, 261.6, 349.2, 440.0, 523.3, 880.0, 1760.0] {
+            for requested in [2, 3, 4] {
+                // Compared with full rate, since the treble is off a little anyway
+                let factor = factor_for(freq, SAMPLE_RATE, requested);
+                let drift = decimated_cents(freq, factor) - decimated_cents(freq, 1);
+                assert!(drift.abs() < 1.0, "{freq} Hz x{factor}: {drift} cents");
+            }
+        }
+    }
+
+    #[test]
+    fn test_bass_runs_at_full_rate() {
+        assert_eq!(factor_for(55.0, SAMPLE_RATE, 4), 1);
+        assert_eq!(factor_for(130.8, SAMPLE_RATE, 2), 1);
+    }
+
+    #[test]
+    fn test_factor_limited_by_rate() {
+        // A4 needs more samples per period than a quarter rate gives
+        assert_eq!(factor_for(440.0, SAMPLE_RATE, 4), 1);
+        assert_eq!(factor_for(4186.0, SAMPLE_RATE, 2), 1);
+        // 44100 Hz doesn't divide by 8
+        assert_eq!(factor_for(440.0, SAMPLE_RATE, 8), 1);
+        assert_eq!(factor_for(440.0, SAMPLE_RATE, 1), 1);
+        assert_eq!(decimate(&[1.0, 3.0, 5.0, 7.0, 9.0], 2), [2.0, 6.0]);
+    }
+}
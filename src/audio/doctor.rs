@@ -0,0 +1,269 @@
+//! Detection self-test report for `pianito doctor`.
+//!
+//! The hardware side lives in the CLI; this module only gathers what was
+//! measured and turns it into a report a user can paste into a bug report.
+
+use std::fmt;
+
+use super::capture::InputInfo;
+use super::gate::{rms, DEFAULT_OPEN_RMS};
+use super::pitch::PitchResult;
+
+/// Sample formats `MicCapture` can read.
+const SUPPORTED_FORMATS: [&str; 2] = ["f32", "i16"];
+
+/// Level and pitch statistics from a test capture.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CaptureStats {
+    /// Seconds captured.
+    pub seconds: f32,
+    /// Blocks analysed.
+    pub blocks: usize,
+    /// Blocks with a detected pitch.
+    pub pitched_blocks: usize,
+    /// Mean RMS level over all blocks.
+    pub rms: f32,
+    /// Loudest block's RMS level.
+    pub peak_rms: f32,
+    /// Frequency of the most confident detection.
+    pub detected: Option<f32>,
+    /// Confidence of that detection.
+    confidence: f32,
+}
+
+impl CaptureStats {
+    /// Add one analysed block.
+    pub fn record(&mut self, samples: &[f32], pitch: Option<&PitchResult>) {
+        let level = rms(samples);
+        self.rms = (self.rms * self.blocks as f32 + level) / (self.blocks + 1) as f32;
+        self.peak_rms = self.peak_rms.max(level);
+        self.blocks += 1;
+
+        if let Some(pitch) = pitch {
+            self.pitched_blocks += 1;
+            if self.detected.is_none() || pitch.confidence > self.confidence {
+                self.detected = Some(pitch.frequency);
+                self.confidence = pitch.confidence;
+            }
+        }
+    }
+
+    /// Cents between the detected pitch and a known `reference` tone.
+    pub fn cents_from(&self, reference: f32) -> Option<f32> {
+        let detected = self.detected?;
+        (detected > 0.0 && reference > 0.0).then(|| 1200.0 * (detected / reference).log2())
+    }
+}
+
+/// Everything `pianito doctor` found out.
+#[derive(Debug, Clone, Default)]
+pub struct DoctorReport {
+    /// Names of all input devices.
+    pub devices: Vec<String>,
+    /// The default input device, if it could be queried.
+    pub input: Option<InputInfo>,
+    /// Capture results, if capture ran.
+    pub stats: Option<CaptureStats>,
+    /// Why querying or capturing failed.
+    pub error: Option<String>,
+}
+
+impl DoctorReport {
+    /// One-line verdict pointing at the most likely problem.
+    pub fn diagnosis(&self) -> String {
+        if let Some(error) = &self.error {
+            return format!("Could not open the microphone: {error}");
+        }
+        let Some(input) = &self.input else {
+            return "No default input device; connect or enable a microphone.".to_string();
+        };
+        if !SUPPORTED_FORMATS.contains(&input.sample_format.as_str()) {
+            return format!(
+                "The default input uses {} samples, which pianito cannot read.",
+                input.sample_format
+            );
+        }
+        let Some(stats) = &self.stats else {
+            return "No audio was captured.".to_string();
+        };
+        if stats.blocks == 0 {
+            "No audio arrived from the microphone; check app permissions.".to_string()
+        } else if stats.peak_rms < DEFAULT_OPEN_RMS {
+            "Input is too quiet to detect; check the microphone is unmuted and close to the piano."
+                .to_string()
+        } else if stats.detected.is_none() {
+            "Sound was heard but no pitch; play a single sustained note.".to_string()
+        } else {
+            "Detection works.".to_string()
+        }
+    }
+}
+
+/// Level in dBFS, floored so silence prints as a number.
+fn dbfs(level: f32) -> f32 {
+    20.0 * level.max(1e-6).log10()
+}
+
+impl fmt::Display for DoctorReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Input devices:")?;
+        if self.devices.is_empty() {
+            writeln!(f, "  (none)")?;
+        }
+        for name in &self.devices {
+            let marker = match &self.input {
+                Some(input) if input.name == *name => "*",
+                _ => " ",
+            };
+            writeln!(f, "{marker} {name}")?;
+        }
+
+        if let Some(input) = &self.input {
+            writeln!(f)?;
+            writeln!(f, "Default input: {}", input.name)?;
+            writeln!(f, "  Sample rate: {} Hz", input.sample_rate)?;
+            writeln!(f, "  Channels:    {}", input.channels)?;
+            writeln!(f, "  Format:      {}", input.sample_format)?;
+        }
+
+        if let Some(stats) = &self.stats {
+            writeln!(f)?;
+            writeln!(f, "Captured {:.1} s", stats.seconds)?;
+            writeln!(
+                f,
+                "  Level: {:.4} RMS ({:.1} dBFS), peak {:.4} ({:.1} dBFS)",
+                stats.rms,
+                dbfs(stats.rms),
+                stats.peak_rms,
+                dbfs(stats.peak_rms)
+            )?;
+            match stats.detected {
+                Some(freq) => writeln!(
+                    f,
+                    "  Pitch: {:.1} Hz, detected in {} of {} blocks",
+                    freq, stats.pitched_blocks, stats.blocks
+                )?,
+                None => writeln!(f, "  Pitch: none detected in {} blocks", stats.blocks)?,
+            }
+        }
+
+        writeln!(f)?;
+        write!(f, "{}", self.diagnosis())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::audio::{PitchDetector, ReferenceTone};
+
+    fn input(format: &str) -> InputInfo {
+        InputInfo {
+            name: "USB Mic".to_string(),
+            sample_rate: 48000,
+            channels: 1,
+            sample_format: format.to_string(),
+        }
+    }
+
+    fn pitch(frequency: f32, confidence: f32) -> PitchResult {
+        PitchResult {
+            frequency,
+            confidence,
+            partials: Vec::new(),
+        }
+    }
+
+    fn stats(rms: f32, detected: Option<f32>) -> CaptureStats {
+        CaptureStats {
+            seconds: 2.0,
+            blocks: 40,
+            pitched_blocks: if detected.is_some() { 12 } else { 0 },
+            rms,
+            peak_rms: rms * 2.0,
+            detected,
+            confidence: 0.9,
+        }
+    }
+
+    fn report(stats: Option<CaptureStats>) -> DoctorReport {
+        DoctorReport {
+            devices: vec!["Built-in".to_string(), "USB Mic".to_string()],
+            input: Some(input("f32")),
+            stats,
+            error: None,
+        }
+    }
+
+    #[test]
+    fn test_reference_tone_loops_back_near_zero_cents() {
+        let sample_rate = 44100;
+        let detector = PitchDetector::new(sample_rate);
+        let tone = ReferenceTone::new(sample_rate).generate(440.0, 1.0);
+
+        let mut stats = CaptureStats::default();
+        for block in tone.chunks(sample_rate as usize / 10) {
+            stats.record(block, detector.detect(block).as_ref());
+        }
+
+        let cents = stats.cents_from(440.0).expect("Should hear the tone");
+        assert!(cents.abs() < 0.5, "got {cents} cents");
+        assert_eq!(CaptureStats::default().cents_from(440.0), None);
+    }
+
+    #[test]
+    fn test_stats_keep_most_confident_pitch() {
+        let mut stats = CaptureStats::default();
+        stats.record(&[0.5, -0.5], Some(&pitch(220.0, 0.6)));
+        stats.record(&[0.0, 0.0], None);
+        stats.record(&[0.1, -0.1], Some(&pitch(440.0, 0.9)));
+        stats.record(&[0.1, -0.1], Some(&pitch(880.0, 0.7)));
+
+        assert_eq!(stats.blocks, 4);
+        assert_eq!(stats.pitched_blocks, 3);
+        assert_eq!(stats.detected, Some(440.0));
+        assert!((stats.peak_rms - 0.5).abs() < 1e-6);
+        assert!((stats.rms - 0.175).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_report_lists_devices_and_format() {
+        let text = report(Some(stats(0.05, Some(440.0)))).to_string();
+
+        assert!(text.contains("  Built-in\n"));
+        assert!(text.contains("* USB Mic\n"), "default is marked");
+        assert!(text.contains("Sample rate: 48000 Hz"));
+        assert!(text.contains("Format:      f32"));
+        assert!(text.contains("Captured 2.0 s"));
+        assert!(text.contains("0.0500 RMS (-26.0 dBFS)"));
+        assert!(text.contains("Pitch: 440.0 Hz, detected in 12 of 40 blocks"));
+        assert!(text.ends_with("Detection works."));
+    }
+
+    #[test]
+    fn test_diagnosis_points_at_likely_problem() {
+        assert!(report(Some(stats(0.001, None)))
+            .diagnosis()
+            .contains("too quiet"));
+        assert!(report(Some(stats(0.05, None)))
+            .diagnosis()
+            .contains("no pitch"));
+        assert!(report(Some(CaptureStats::default()))
+            .diagnosis()
+            .contains("permissions"));
+
+        let mut unsupported = report(None);
+        unsupported.input = Some(input("u8"));
+        assert!(unsupported.diagnosis().contains("u8 samples"));
+
+        let none = DoctorReport::default();
+        assert!(none.to_string().contains("(none)"));
+        assert!(none.diagnosis().contains("No default input"));
+
+        let failed = DoctorReport {
+            error: Some("device busy".to_string()),
+            ..report(None)
+        };
+        assert!(failed.diagnosis().contains("device busy"));
+    }
+}
@@ -0,0 +1,233 @@
+//! Pre-detection filters for room rumble and mains hum.
+//!
+//! Mains hum at 50 or 60 Hz sits right among the lowest octaves (A1 is
+//! 55 Hz), where it can swamp the string's fundamental. A narrow notch on
+//! the mains frequency and a gentle high-pass below A0 clean up the input
+//! before it reaches the detector.
+
+use std::f32::consts::{FRAC_1_SQRT_2, TAU};
+
+/// Quality factor of the hum notch; about 2 Hz wide at 60 Hz.
+pub const HUM_NOTCH_Q: f32 = 30.0;
+/// Highest high-pass cutoff, in Hz; any higher would cut into A0.
+pub const MAX_HIGHPASS_HZ: f32 = 27.5;
+/// Mains frequencies the hum notch may sit on, in Hz.
+pub const HUM_NOTCH_RANGE: std::ops::RangeInclusive<f32> = 40.0..=70.0;
+
+/// Check if `cutoff_hz` is a high-pass cutoff that keeps A0.
+pub fn is_valid_highpass(cutoff_hz: f32) -> bool {
+    cutoff_hz > 0.0 && cutoff_hz <= MAX_HIGHPASS_HZ
+}
+
+/// Check if `mains_hz` is a mains frequency the notch can take.
+pub fn is_valid_hum_notch(mains_hz: f32) -> bool {
+    HUM_NOTCH_RANGE.contains(&mains_hz)
+}
+
+/// Check if a filter at `freq_hz` is stable at `sample_rate`.
+fn is_stable(freq_hz: f32, sample_rate: u32) -> bool {
+    freq_hz > 0.0 && freq_hz < sample_rate as f32 / 2.0
+}
+
+/// A second-order IIR section (RBJ cookbook form).
+#[derive(Debug, Clone)]
+pub struct Biquad {
+    b0: f32,
+    b1: f32,
+    b2: f32,
+    a1: f32,
+    a2: f32,
+    x1: f32,
+    x2: f32,
+    y1: f32,
+    y2: f32,
+}
+
+impl Biquad {
+    /// Build a section from unnormalized coefficients.
+    fn from_coefficients(b: [f32; 3], a: [f32; 3]) -> Self {
+        Self {
+            b0: b[0] / a[0],
+            b1: b[1] / a[0],
+            b2: b[2] / a[0],
+            a1: a[1] / a[0],
+            a2: a[2] / a[0],
+            x1: 0.0,
+            x2: 0.0,
+            y1: 0.0,
+            y2: 0.0,
+        }
+    }
+
+    /// Butterworth high-pass passing everything above `cutoff_hz`.
+    pub fn highpass(cutoff_hz: f32, sample_rate: u32) -> Self {
+        let w0 = TAU * cutoff_hz / sample_rate as f32;
+        let alpha = w0.sin() / (2.0 * FRAC_1_SQRT_2);
+        let cos = w0.cos();
+        Self::from_coefficients(
+            [(1.0 + cos) / 2.0, -(1.0 + cos), (1.0 + cos) / 2.0],
+            [1.0 + alpha, -2.0 * cos, 1.0 - alpha],
+        )
+    }
+
+    /// Notch removing a narrow band around `freq_hz`.
+    pub fn notch(freq_hz: f32, q: f32, sample_rate: u32) -> Self {
+        let w0 = TAU * freq_hz / sample_rate as f32;
+        let alpha = w0.sin() / (2.0 * q);
+        let cos = w0.cos();
+        Self::from_coefficients(
+            [1.0, -2.0 * cos, 1.0],
+            [1.0 + alpha, -2.0 * cos, 1.0 - alpha],
+        )
+    }
+
+    /// Filter one sample.
+    pub fn process(&mut self, x: f32) -> f32 {
+        let y = self.b0 * x + self.b1 * self.x1 + self.b2 * self.x2
+            - self.a1 * self.y1
+            - self.a2 * self.y2;
+        self.x2 = self.x1;
+        self.x1 = x;
+        self.y2 = self.y1;
+        self.y1 = y;
+        y
+    }
+}
+
+/// Chain of filters applied to input before detection.
+#[derive(Debug, Clone, Default)]
+pub struct PreFilter {
+    stages: Vec<Biquad>,
+}
+
+impl PreFilter {
+    /// Create a filter that passes audio unchanged.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a high-pass at `cutoff_hz`, e.g. 20 Hz to drop rumble below A0.
+    ///
+    /// A cutoff that is not positive or not below Nyquist is ignored, as
+    /// the filter would be unstable.
+    pub fn with_highpass(mut self, cutoff_hz: f32, sample_rate: u32) -> Self {
+        if is_stable(cutoff_hz, sample_rate) {
+            self.stages.push(Biquad::highpass(cutoff_hz, sample_rate));
+        }
+        self
+    }
+
+    /// Add a notch on the mains frequency (50 or 60 Hz).
+    ///
+    /// A frequency that is not positive or not below Nyquist is ignored.
+    pub fn with_hum_notch(mut self, mains_hz: f32, sample_rate: u32) -> Self {
+        if is_stable(mains_hz, sample_rate) {
+            self.stages
+                .push(Biquad::notch(mains_hz, HUM_NOTCH_Q, sample_rate));
+        }
+        self
+    }
+
+    /// Check if the filter passes audio unchanged.
+    pub fn is_empty(&self) -> bool {
+        self.stages.is_empty()
+    }
+
+    /// Filter one sample through every stage.
+    pub fn process(&mut self, x: f32) -> f32 {
+        self.stages.iter_mut().fold(x, |x, stage| stage.process(x))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::audio::gate::rms;
+    use crate::audio::PitchDetector;
+
+    const SAMPLE_RATE: u32 = 44100;
+
+    fn sine(freq: f32, secs: f32) -> Vec<f32> {
+        (0..(SAMPLE_RATE as f32 * secs) as usize)
+            .map(|i| 0.5 * (TAU * freq * i as f32 / SAMPLE_RATE as f32).sin())
+            .collect()
+    }
+
+    /// Filter `samples`, keeping the last half once the filter has settled.
+    fn settled(filter: &mut PreFilter, samples: &[f32]) -> Vec<f32> {
+        let filtered: Vec<f32> = samples.iter().map(|&x| filter.process(x)).collect();
+        filtered[filtered.len() / 2..].to_vec()
+    }
+
+    #[test]
+    fn test_hum_notch_removes_hum_and_keeps_a1() {
+        let notch = || PreFilter::new().with_hum_notch(60.0, SAMPLE_RATE);
+
+        let hum = sine(60.0, 2.0);
+        let hum_left = rms(&settled(&mut notch(), &hum));
+        assert!(hum_left < rms(&hum) * 0.1, "hum left at {hum_left}");
+
+        let a1 = sine(55.0, 2.0);
+        let a1_left = rms(&settled(&mut notch(), &a1));
+        assert!(a1_left > rms(&a1) * 0.8, "A1 cut to {a1_left}");
+    }
+
+    #[test]
+    fn test_a1_detects_through_hum_once_notched() {
+        let detector = PitchDetector::new(SAMPLE_RATE);
+        let mixed: Vec<f32> = sine(55.0, 2.0)
+            .iter()
+            .zip(sine(60.0, 2.0))
+            .map(|(tone, hum)| tone + hum)
+            .collect();
+
+        let cleaned = settled(
+            &mut PreFilter::new().with_hum_notch(60.0, SAMPLE_RATE),
+            &mixed,
+        );
+        let window = detector.window_for_freq(55.0);
+        let result = detector
+            .detect(&cleaned[cleaned.len() - window..])
+            .expect("A1 should detect");
+        let cents = 1200.0 * (result.frequency / 55.0).log2();
+        assert!(cents.abs() < 5.0, "read {:.2} Hz", result.frequency);
+    }
+
+    #[test]
+    fn test_highpass_passes_a0_and_cuts_rumble() {
+        let highpass = || PreFilter::new().with_highpass(20.0, SAMPLE_RATE);
+
+        let rumble = sine(5.0, 4.0);
+        assert!(rms(&settled(&mut highpass(), &rumble)) < rms(&rumble) * 0.1);
+
+        let a0 = sine(27.5, 2.0);
+        assert!(rms(&settled(&mut highpass(), &a0)) > rms(&a0) * 0.7);
+    }
+
+    #[test]
+    fn test_unstable_settings_are_ignored() {
+        for bad in [-20.0, 0.0, 22050.0, 30000.0] {
+            let filter = PreFilter::new()
+                .with_highpass(bad, SAMPLE_RATE)
+                .with_hum_notch(bad, SAMPLE_RATE);
+            assert!(filter.is_empty(), "{bad} Hz should be ignored");
+        }
+    }
+
+    #[test]
+    fn test_valid_ranges() {
+        assert!(is_valid_highpass(20.0));
+        assert!(is_valid_highpass(MAX_HIGHPASS_HZ));
+        assert!(!is_valid_highpass(0.0));
+        assert!(!is_valid_highpass(500.0));
+        assert!(is_valid_hum_notch(50.0) && is_valid_hum_notch(60.0));
+        assert!(!is_valid_hum_notch(120.0));
+    }
+
+    #[test]
+    fn test_empty_filter_passes_audio_unchanged() {
+        let mut filter = PreFilter::new();
+        assert!(filter.is_empty());
+        assert_eq!(filter.process(0.25), 0.25);
+    }
+}
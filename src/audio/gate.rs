@@ -0,0 +1,139 @@
+//! Signal-presence gate with hysteresis.
+//!
+//! A struck string decays slowly through the noise floor. A single level
+//! threshold lets that tail flicker between sounding and silent; separate
+//! open and close levels keep a decaying note active until it has dropped
+//! well below the level that opened the gate.
+
+/// RMS level that opens the gate (about -40 dBFS).
+pub const DEFAULT_OPEN_RMS: f32 = 0.01;
+/// RMS level the signal must fall below to close the gate (about -50 dBFS).
+pub const DEFAULT_CLOSE_RMS: f32 = 0.003;
+
+/// Sample magnitude treated as hitting full scale.
+pub const CLIP_LEVEL: f32 = 0.99;
+/// Share of samples at full scale that counts as clipping.
+pub const CLIP_FRACTION: f32 = 0.01;
+
+/// Root-mean-square level of a buffer.
+pub fn rms(samples: &[f32]) -> f32 {
+    if samples.is_empty() {
+        return 0.0;
+    }
+    (samples.iter().map(|s| s * s).sum::<f32>() / samples.len() as f32).sqrt()
+}
+
+/// Check if enough samples sit at full scale to mean the input is clipping.
+pub fn is_clipping(samples: &[f32]) -> bool {
+    if samples.is_empty() {
+        return false;
+    }
+    let clipped = samples.iter().filter(|s| s.abs() >= CLIP_LEVEL).count();
+    clipped as f32 / samples.len() as f32 >= CLIP_FRACTION
+}
+
+/// Tracks whether a signal is present, with separate open and close levels.
+#[derive(Debug, Clone)]
+pub struct SignalGate {
+    open_rms: f32,
+    close_rms: f32,
+    open: bool,
+}
+
+impl SignalGate {
+    /// Create a gate with the default levels.
+    pub fn new() -> Self {
+        Self::with_levels(DEFAULT_OPEN_RMS, DEFAULT_CLOSE_RMS)
+    }
+
+    /// Create a gate that opens at `open_rms` and closes below `close_rms`.
+    ///
+    /// `close_rms` is capped at `open_rms`; equal levels give no hysteresis.
+    pub fn with_levels(open_rms: f32, close_rms: f32) -> Self {
+        Self {
+            open_rms,
+            close_rms: close_rms.min(open_rms),
+            open: false,
+        }
+    }
+
+    /// Feed a buffer, returning whether a signal is present.
+    pub fn update(&mut self, samples: &[f32]) -> bool {
+        self.update_rms(rms(samples))
+    }
+
+    /// Feed a buffer's RMS level, returning whether a signal is present.
+    pub fn update_rms(&mut self, level: f32) -> bool {
+        self.open = if self.open {
+            level >= self.close_rms
+        } else {
+            level >= self.open_rms
+        };
+        self.open
+    }
+
+    /// Check if the gate is open.
+    pub fn is_open(&self) -> bool {
+        self.open
+    }
+}
+
+impl Default for SignalGate {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Decaying note whose level wobbles around the open threshold.
+    const ENVELOPE: [f32; 8] = [0.05, 0.02, 0.011, 0.009, 0.012, 0.008, 0.0105, 0.007];
+
+    #[test]
+    fn test_single_threshold_flickers() {
+        let mut gate = SignalGate::with_levels(0.01, 0.01);
+        let states: Vec<bool> = ENVELOPE.iter().map(|&l| gate.update_rms(l)).collect();
+        let flips = states.windows(2).filter(|w| w[0] != w[1]).count();
+        assert!(flips >= 4, "expected flicker, got {states:?}");
+    }
+
+    #[test]
+    fn test_hysteresis_stays_active() {
+        let mut gate = SignalGate::new();
+        for level in ENVELOPE {
+            assert!(gate.update_rms(level), "closed at {level}");
+        }
+
+        // Only closes once well below the open level
+        assert!(!gate.update_rms(0.002));
+        assert!(!gate.update_rms(0.008), "should need the open level again");
+        assert!(gate.update_rms(0.02));
+    }
+
+    #[test]
+    fn test_update_from_samples() {
+        let mut gate = SignalGate::new();
+        assert!(!gate.update(&[0.0; 64]));
+
+        let tone: Vec<f32> = (0..441)
+            .map(|i| 0.1 * (2.0 * std::f32::consts::PI * 440.0 * i as f32 / 44100.0).sin())
+            .collect();
+        assert!(gate.update(&tone));
+        assert!(gate.is_open());
+        assert!((rms(&tone) - 0.1 / 2.0_f32.sqrt()).abs() < 1e-3);
+    }
+
+    #[test]
+    fn test_clipping_needs_full_scale_samples() {
+        assert!(!is_clipping(&[]));
+        assert!(is_clipping(&[1.0, -1.0, 1.0, -1.0]));
+        assert!(!is_clipping(&[0.5, -0.5, 0.98, -0.98]));
+
+        // A single stray peak in a long buffer is not clipping
+        let mut samples = vec![0.2; 1000];
+        samples[500] = 1.0;
+        assert!(!is_clipping(&samples));
+    }
+}
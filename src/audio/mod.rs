@@ -1,11 +1,28 @@
 //! Audio capture, pitch detection, and reference tone generation.
 
+pub mod beats;
 pub mod capture;
+pub mod decimate;
+pub mod doctor;
+pub mod filter;
+pub mod gate;
+pub mod onset;
 pub mod pitch;
 pub mod reference;
+pub mod silence;
+pub mod smoother;
 pub mod traits;
 
-pub use capture::{AudioOutput, CaptureError, MicCapture};
-pub use pitch::{PitchDetector, PitchResult};
-pub use reference::ReferenceTone;
+pub use beats::BeatCounter;
+pub use capture::{
+    default_input_info, input_device_names, AudioOutput, CaptureError, InputInfo, MicCapture,
+};
+pub use doctor::{CaptureStats, DoctorReport};
+pub use filter::PreFilter;
+pub use gate::SignalGate;
+pub use onset::AttackTracker;
+pub use pitch::{FalseBeatWindow, PitchDetector, PitchResult, MIN_DETECT_CONFIDENCE};
+pub use reference::{Cue, ReferenceTone, RegisterGain};
+pub use silence::SilenceWatchdog;
+pub use smoother::{PitchSmoother, ResponsePreset};
 pub use traits::{AudioSink, AudioSource, TestAudioSink, TestAudioSource, WavAudioSource};
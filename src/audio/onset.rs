@@ -0,0 +1,157 @@
+//! Onset detection and attack-window pitch capture.
+//!
+//! A struck string reads most accurately in the loud stretch just after the
+//! hammer blow; as it decays, the reading wanders and the detector works on
+//! ever less signal. The tracker notices the jump in level that marks a new
+//! strike and keeps only the readings taken shortly after it.
+
+use super::gate::DEFAULT_OPEN_RMS;
+
+/// Level rise, relative to the previous block, that marks a new strike.
+pub const ONSET_RATIO: f32 = 2.0;
+/// Seconds after an onset whose readings count as the attack.
+pub const ATTACK_WINDOW_SECS: f32 = 0.3;
+
+/// Collects the readings taken during the attack of the latest strike.
+#[derive(Debug, Clone, Default)]
+pub struct AttackTracker {
+    previous_level: f32,
+    since_onset: Option<f32>,
+    readings: Vec<f32>,
+}
+
+impl AttackTracker {
+    /// Create a tracker that has heard no strike yet.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Feed the input level over the last `elapsed_secs`, returning whether
+    /// it marks a new strike. A strike starts a fresh attack window.
+    pub fn update_level(&mut self, level: f32, elapsed_secs: f32) -> bool {
+        let onset = level >= DEFAULT_OPEN_RMS && level >= self.previous_level * ONSET_RATIO;
+        if onset {
+            self.since_onset = Some(0.0);
+            self.readings.clear();
+        } else if let Some(since) = &mut self.since_onset {
+            *since += elapsed_secs.max(0.0);
+        }
+        self.previous_level = level;
+        onset
+    }
+
+    /// Record a reading in cents, kept only while inside the attack window.
+    pub fn record(&mut self, cents: f32) {
+        if self.in_attack() && cents.is_finite() {
+            self.readings.push(cents);
+        }
+    }
+
+    /// Check if the latest strike is still within its attack window.
+    pub fn in_attack(&self) -> bool {
+        self.since_onset
+            .is_some_and(|since| since <= ATTACK_WINDOW_SECS)
+    }
+
+    /// Median reading from the latest strike's attack, if any was taken.
+    pub fn attack_cents(&self) -> Option<f32> {
+        if self.readings.is_empty() {
+            return None;
+        }
+        let mut sorted = self.readings.clone();
+        sorted.sort_by(f32::total_cmp);
+        Some(sorted[sorted.len() / 2])
+    }
+
+    /// Forget the latest strike, e.g. when moving to another note.
+    pub fn reset(&mut self) {
+        *self = Self::default();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::audio::gate::rms;
+    use crate::audio::PitchDetector;
+    use std::f32::consts::TAU;
+
+    const SAMPLE_RATE: u32 = 44100;
+    const BLOCK: usize = 4410;
+
+    /// A strike at 440 Hz that sags to 443 Hz as it decays, in 100ms blocks.
+    fn attack_then_decay() -> Vec<Vec<f32>> {
+        let mut phase = 0.0f32;
+        let mut blocks = vec![vec![0.0; BLOCK]; 2];
+        for b in 0..15 {
+            let (freq, amplitude) = if b < 3 {
+                (440.0, 0.8)
+            } else {
+                (443.0, 0.8 * 0.75f32.powi(b - 2))
+            };
+            let block = (0..BLOCK)
+                .map(|_| {
+                    phase = (phase + TAU * freq / SAMPLE_RATE as f32) % TAU;
+                    amplitude * phase.sin()
+                })
+                .collect();
+            blocks.push(block);
+        }
+        blocks
+    }
+
+    #[test]
+    fn test_attack_pitch_beats_decayed_reading() {
+        let detector = PitchDetector::new(SAMPLE_RATE);
+        let mut tracker = AttackTracker::new();
+        let mut onsets = 0;
+        let mut latest = None;
+
+        for block in attack_then_decay() {
+            onsets += tracker.update_level(rms(&block), 0.1) as usize;
+            if let Some(result) = detector.detect(&block) {
+                let cents = 1200.0 * (result.frequency / 440.0).log2();
+                tracker.record(cents);
+                latest = Some(cents);
+            }
+        }
+
+        assert_eq!(onsets, 1);
+        let attack = tracker.attack_cents().expect("attack readings");
+        assert!(attack.abs() < 1.0, "attack read {attack:.2} cents");
+        let latest = latest.unwrap();
+        assert!(latest > 10.0, "decayed reading {latest:.2} cents");
+    }
+
+    #[test]
+    fn test_readings_after_window_are_ignored() {
+        let mut tracker = AttackTracker::new();
+        assert!(tracker.update_level(0.5, 0.1));
+        tracker.record(1.0);
+        tracker.update_level(0.4, 0.5);
+        tracker.record(9.0);
+
+        assert!(!tracker.in_attack());
+        assert_eq!(tracker.attack_cents(), Some(1.0));
+    }
+
+    #[test]
+    fn test_no_onset_without_a_strike() {
+        let mut tracker = AttackTracker::new();
+        // Quiet room noise never counts as a strike
+        assert!(!tracker.update_level(0.002, 0.1));
+        tracker.record(3.0);
+        assert_eq!(tracker.attack_cents(), None);
+
+        // A new strike replaces the last one's readings
+        tracker.update_level(0.3, 0.1);
+        tracker.record(2.0);
+        tracker.update_level(0.1, 0.1);
+        tracker.update_level(0.4, 0.1);
+        tracker.record(-1.0);
+        assert_eq!(tracker.attack_cents(), Some(-1.0));
+
+        tracker.reset();
+        assert_eq!(tracker.attack_cents(), None);
+    }
+}
@@ -3,13 +3,51 @@
 //! Implementation based on:
 //! de Cheveigné, A., & Kawahara, H. (2002). "YIN, a fundamental frequency estimator for speech and music."
 
+use rustfft::{num_complex::Complex, FftPlanner};
+
+use super::beats::{BeatCounter, BLOCK_SECS};
+
+/// Second-partial to fundamental magnitude ratio above which the octave is
+/// considered to be sounding as well (e.g. sympathetic resonance).
+pub const OCTAVE_AMBIGUITY_RATIO: f32 = 0.7;
+
+/// Envelope swing, relative to its peak, above which a single note is
+/// considered to beat against itself.
+pub const FALSE_BEAT_DEPTH: f32 = 0.3;
+/// Seconds of a note collected before checking it for false beats.
+pub const FALSE_BEAT_MIN_SECS: f32 = 2.0;
+/// Seconds of a note kept for the false beat check, so even a half-hertz
+/// beat fades twice within it.
+pub const FALSE_BEAT_WINDOW_SECS: f32 = 4.0;
+
+/// Periods of the target note an analysis window spans.
+const WINDOW_PERIODS: f32 = 16.0;
+/// Shortest analysis window, in seconds, so treble readings stay steady.
+const MIN_WINDOW_SECS: f32 = 0.05;
+/// Longest analysis window, in seconds, so bass readings stay responsive.
+const MAX_WINDOW_SECS: f32 = 0.25;
+
+/// Confidence at or below which `detect` reports no pitch.
+pub const MIN_DETECT_CONFIDENCE: f32 = 0.5;
+
+/// How far, as a fraction of the fundamental, a partial may sit from its
+/// harmonic; leaves room for the stretch of stiff treble strings.
+const PARTIAL_SEARCH_WIDTH: f32 = 0.3;
+/// Magnitude, relative to the strongest partial, below which a partial
+/// counts as missing (-40 dB).
+const PARTIAL_FLOOR: f32 = 0.01;
+
 /// Pitch detection result.
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone)]
 pub struct PitchResult {
     /// Detected frequency in Hz.
     pub frequency: f32,
     /// Confidence score (0.0 to 1.0, higher is better).
     pub confidence: f32,
+    /// Frequency of each partial in Hz, the fundamental first, with 0.0
+    /// for a partial too weak to find. Empty unless the detector was set
+    /// up `with_partials`.
+    pub partials: Vec<f32>,
 }
 
 /// YIN-based pitch detector.
@@ -18,6 +56,7 @@ pub struct PitchDetector {
     threshold: f32,
     min_frequency: f32,
     max_frequency: f32,
+    partials: usize,
 }
 
 impl PitchDetector {
@@ -28,9 +67,18 @@ impl PitchDetector {
             threshold: 0.1,
             min_frequency: 27.5,   // A0
             max_frequency: 4186.0, // C8
+            partials: 0,
         }
     }
 
+    /// Also measure the first `count` partials from the spectrum.
+    ///
+    /// Costs an extra FFT per reading, so it is off by default.
+    pub fn with_partials(mut self, count: usize) -> Self {
+        self.partials = count;
+        self
+    }
+
     /// Set the confidence threshold for detection.
     pub fn with_threshold(mut self, threshold: f32) -> Self {
         self.threshold = threshold;
@@ -44,16 +92,60 @@ impl PitchDetector {
         self
     }
 
+    /// Smallest buffer that can hold two periods of the lowest frequency.
+    ///
+    /// Shorter buffers, such as right after capture starts or is flushed,
+    /// only give noise or `None`.
+    pub fn min_samples(&self) -> usize {
+        2 * (self.sample_rate as f32 / self.min_frequency).ceil() as usize + 2
+    }
+
+    /// Analysis window in samples for a note at `hz`.
+    ///
+    /// Low notes need more periods' worth of signal for an accurate reading;
+    /// high notes get a short window so the needle follows the hammer quickly.
+    pub fn window_for_freq(&self, hz: f32) -> usize {
+        let secs = if hz > 0.0 {
+            (WINDOW_PERIODS / hz).clamp(MIN_WINDOW_SECS, MAX_WINDOW_SECS)
+        } else {
+            MAX_WINDOW_SECS
+        };
+        (secs * self.sample_rate as f32).ceil() as usize
+    }
+
+    /// Longest window `window_for_freq` returns.
+    pub fn max_window(&self) -> usize {
+        (MAX_WINDOW_SECS * self.sample_rate as f32).ceil() as usize
+    }
+
+    /// Check if `available` samples are too few for a reliable reading.
+    pub fn is_warming_up(&self, available: usize) -> bool {
+        available < self.min_samples()
+    }
+
     /// Detect pitch from audio samples using the YIN algorithm.
+    ///
+    /// Readings at or below `MIN_DETECT_CONFIDENCE` are dropped; use
+    /// `estimate` to keep them.
     pub fn detect(&self, samples: &[f32]) -> Option<PitchResult> {
+        self.estimate(samples)
+            .filter(|result| result.confidence > MIN_DETECT_CONFIDENCE)
+    }
+
+    /// Best-guess pitch, however unsure, for signal that `detect` rejects
+    /// such as an inharmonic attack transient.
+    ///
+    /// Only returns `None` when the buffer is too short to analyse.
+    pub fn estimate(&self, samples: &[f32]) -> Option<PitchResult> {
         if samples.len() < 2 {
             return None;
         }
 
-        // Calculate tau range from frequency range
+        // Calculate tau range from frequency range, limited to what a short
+        // window can hold so treble windows still search their own register
         let tau_min = (self.sample_rate as f32 / self.max_frequency) as usize;
-        let tau_max =
-            (self.sample_rate as f32 / self.min_frequency).min((samples.len() / 2) as f32) as usize;
+        let tau_max = (self.sample_rate as f32 / self.min_frequency)
+            .min((samples.len() / 2 - 1) as f32) as usize;
 
         if tau_max <= tau_min || tau_max >= samples.len() / 2 {
             return None;
@@ -77,9 +169,16 @@ impl PitchDetector {
         // Calculate confidence (1 - cmnd value at the dip)
         let confidence = 1.0 - cmnd[tau].min(1.0);
 
+        let partials = if self.partials > 0 {
+            find_partials(samples, self.sample_rate, frequency, self.partials)
+        } else {
+            Vec::new()
+        };
+
         Some(PitchResult {
             frequency,
             confidence,
+            partials,
         })
     }
 
@@ -152,7 +251,7 @@ impl PitchDetector {
             }
         }
 
-        // If no threshold crossing, find absolute minimum (fallback)
+        // If no threshold crossing, fall back to the absolute minimum
         let mut min_tau = tau_min;
         let mut min_val = cmnd[tau_min];
 
@@ -164,12 +263,7 @@ impl PitchDetector {
             }
         }
 
-        // Only return if it's a reasonable minimum
-        if min_val < 0.5 {
-            Some(min_tau)
-        } else {
-            None
-        }
+        Some(min_tau)
     }
 
     /// Step 5: Parabolic interpolation for sub-sample accuracy.
@@ -195,6 +289,178 @@ impl PitchDetector {
     }
 }
 
+/// Check whether both `frequency` and `2 * frequency` carry strong energy.
+///
+/// A piano's second partial is normally well below the fundamental; when it
+/// rivals it, a neighboring octave string is probably ringing too.
+pub fn octave_ambiguity(samples: &[f32], sample_rate: u32, frequency: f32) -> bool {
+    let nyquist = sample_rate as f32 / 2.0;
+    if samples.is_empty() || frequency <= 0.0 || frequency * 2.0 >= nyquist {
+        return false;
+    }
+
+    let spectrum = magnitude_spectrum(samples);
+    let bin_hz = sample_rate as f32 / (spectrum.len() * 2) as f32;
+
+    let fundamental = peak_near(&spectrum, frequency / bin_hz);
+    let octave = peak_near(&spectrum, frequency * 2.0 / bin_hz);
+
+    fundamental > 0.0 && octave / fundamental >= OCTAVE_AMBIGUITY_RATIO
+}
+
+/// Check whether a single note beats against itself.
+///
+/// A twisted string or poor bridge contact makes one string swell and fade
+/// as if two were slightly out of unison. Needs a window spanning at least
+/// two beats to see it.
+pub fn false_beats(samples: &[f32], sample_rate: u32, frequency: f32) -> bool {
+    if frequency <= 0.0 {
+        return false;
+    }
+
+    // Blocks of two periods keep bass waveforms out of the envelope
+    let counter = BeatCounter::new(sample_rate).with_block_secs((2.0 / frequency).max(BLOCK_SECS));
+    counter.modulation_depth(samples) >= FALSE_BEAT_DEPTH
+        && counter.beats_per_second(samples).is_some()
+}
+
+/// Collects the latest samples of a note to check it for false beats.
+///
+/// A single detection buffer is far shorter than a beat, so the check runs
+/// on up to the last `FALSE_BEAT_WINDOW_SECS` of input instead.
+#[derive(Debug, Clone, Default)]
+pub struct FalseBeatWindow {
+    samples: Vec<f32>,
+}
+
+impl FalseBeatWindow {
+    /// Create an empty window.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a buffer, keeping only the latest `FALSE_BEAT_WINDOW_SECS`.
+    pub fn push(&mut self, samples: &[f32], sample_rate: u32) {
+        self.samples.extend_from_slice(samples);
+        let keep = secs_len(sample_rate, FALSE_BEAT_WINDOW_SECS);
+        if self.samples.len() > keep {
+            self.samples.drain(..self.samples.len() - keep);
+        }
+    }
+
+    /// Check the collected note for false beats.
+    ///
+    /// Returns `None` until `FALSE_BEAT_MIN_SECS` have been collected.
+    pub fn check(&self, sample_rate: u32, frequency: f32) -> Option<bool> {
+        (self.samples.len() >= secs_len(sample_rate, FALSE_BEAT_MIN_SECS))
+            .then(|| false_beats(&self.samples, sample_rate, frequency))
+    }
+
+    /// Forget the collected samples, e.g. on a new note or strike.
+    pub fn reset(&mut self) {
+        self.samples.clear();
+    }
+}
+
+fn secs_len(sample_rate: u32, secs: f32) -> usize {
+    (sample_rate as f32 * secs) as usize
+}
+
+/// Measure up to `count` partials of a note at `fundamental` Hz.
+///
+/// Each partial is the strongest spectral peak near its harmonic, refined
+/// between bins; partials below `PARTIAL_FLOOR` of the strongest read 0.0.
+/// Stops at the Nyquist frequency, so treble notes may list fewer.
+pub fn find_partials(
+    samples: &[f32],
+    sample_rate: u32,
+    fundamental: f32,
+    count: usize,
+) -> Vec<f32> {
+    let nyquist = sample_rate as f32 / 2.0;
+    if samples.is_empty() || fundamental <= 0.0 {
+        return Vec::new();
+    }
+
+    let spectrum = magnitude_spectrum(samples);
+    let bin_hz = sample_rate as f32 / (spectrum.len() * 2) as f32;
+    let width = (fundamental * PARTIAL_SEARCH_WIDTH / bin_hz).max(1.0);
+
+    let peaks: Vec<(f32, f32)> = (1..=count)
+        .map(|n| n as f32 * fundamental)
+        .take_while(|&harmonic| harmonic < nyquist)
+        .map(|harmonic| {
+            let center = harmonic / bin_hz;
+            let start = ((center - width).floor().max(1.0)) as usize;
+            let end = ((center + width).ceil() as usize).min(spectrum.len() - 2);
+            let peak = (start..=end)
+                .max_by(|&a, &b| spectrum[a].total_cmp(&spectrum[b]))
+                .unwrap_or(start);
+            (interpolate_peak(&spectrum, peak) * bin_hz, spectrum[peak])
+        })
+        .collect();
+
+    let strongest = peaks.iter().map(|&(_, m)| m).fold(0.0, f32::max);
+    peaks
+        .into_iter()
+        .map(|(freq, magnitude)| {
+            if strongest > 0.0 && magnitude >= strongest * PARTIAL_FLOOR {
+                freq
+            } else {
+                0.0
+            }
+        })
+        .collect()
+}
+
+/// Fractional bin of a spectral peak, from a parabola through the log
+/// magnitudes around it.
+fn interpolate_peak(spectrum: &[f32], bin: usize) -> f32 {
+    if bin == 0 || bin + 1 >= spectrum.len() {
+        return bin as f32;
+    }
+    let level = |i: usize| spectrum[i].max(1e-12).ln();
+    let (s0, s1, s2) = (level(bin - 1), level(bin), level(bin + 1));
+    let denominator = s0 - 2.0 * s1 + s2;
+    if denominator.abs() < 1e-10 {
+        return bin as f32;
+    }
+    bin as f32 + (0.5 * (s0 - s2) / denominator).clamp(-0.5, 0.5)
+}
+
+/// Hann-windowed magnitude spectrum (positive frequencies only).
+fn magnitude_spectrum(samples: &[f32]) -> Vec<f32> {
+    let size = samples.len().next_power_of_two();
+    let last = (samples.len().max(2) - 1) as f32;
+
+    let mut buffer: Vec<Complex<f32>> = samples
+        .iter()
+        .enumerate()
+        .map(|(i, &s)| {
+            let window = 0.5 - 0.5 * (2.0 * std::f32::consts::PI * i as f32 / last).cos();
+            Complex::new(s * window, 0.0)
+        })
+        .collect();
+    buffer.resize(size, Complex::new(0.0, 0.0));
+
+    FftPlanner::new()
+        .plan_fft_forward(size)
+        .process(&mut buffer);
+
+    buffer[..size / 2].iter().map(|c| c.norm()).collect()
+}
+
+/// Largest magnitude within one bin of a fractional bin position.
+fn peak_near(spectrum: &[f32], bin: f32) -> f32 {
+    let center = bin.round() as usize;
+    let start = center.saturating_sub(1);
+    let end = (center + 2).min(spectrum.len());
+    spectrum
+        .get(start..end)
+        .map(|s| s.iter().copied().fold(0.0, f32::max))
+        .unwrap_or(0.0)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -208,6 +474,53 @@ mod tests {
         detector.detect(source.samples())
     }
 
+    #[test]
+    fn test_window_grows_towards_the_bass() {
+        let detector = PitchDetector::new(SAMPLE_RATE);
+        let a0 = detector.window_for_freq(27.5);
+        let a4 = detector.window_for_freq(440.0);
+        let c8 = detector.window_for_freq(4186.0);
+
+        assert!(a0 > a4 && a4 >= c8, "{a0} {a4} {c8}");
+        assert_eq!(a0, detector.max_window());
+        assert_eq!(c8, (MIN_WINDOW_SECS * SAMPLE_RATE as f32).ceil() as usize);
+        assert_eq!(detector.window_for_freq(0.0), detector.max_window());
+    }
+
+    #[test]
+    fn test_detects_within_register_window() {
+        let detector = PitchDetector::new(SAMPLE_RATE);
+        for freq in [27.5, 110.0, 4186.0] {
+            let window = detector.window_for_freq(freq);
+            let source = TestAudioSource::sine(freq, 0.3, SAMPLE_RATE);
+            let result = detector
+                .detect(&source.samples()[..window])
+                .expect("Should detect within its window");
+            assert!(
+                (result.frequency - freq).abs() / freq < 0.01,
+                "{freq}: {}",
+                result.frequency
+            );
+        }
+    }
+
+    #[test]
+    fn test_warming_up_until_min_samples() {
+        let detector = PitchDetector::new(SAMPLE_RATE);
+        let source = TestAudioSource::sine(110.0, 0.2, SAMPLE_RATE);
+        let min = detector.min_samples();
+
+        assert!(detector.is_warming_up(0));
+        assert!(detector.is_warming_up(min - 1));
+        assert!(!detector.is_warming_up(min));
+
+        // One window is enough for a reading
+        let result = detector
+            .detect(&source.samples()[..min])
+            .expect("Should detect once primed");
+        assert!((result.frequency - 110.0).abs() < 0.5);
+    }
+
     #[test]
     fn test_detect_a4_440hz() {
         let result = detect_frequency(440.0).expect("Should detect pitch");
@@ -293,9 +606,8 @@ mod tests {
         assert!(result.is_none(), "Silence should return None");
     }
 
-    #[test]
-    fn test_noise_low_confidence() {
-        // Generate noise that's less periodic by using a better PRNG and mixing
+    /// Noise that's less periodic, from a better PRNG and mixing.
+    fn noise() -> Vec<f32> {
         let mut noise = Vec::with_capacity(8192);
         let mut x = 12345_u64;
         for i in 0..8192 {
@@ -309,9 +621,28 @@ mod tests {
             let high_freq = ((i as f32 * 0.7654321) * std::f32::consts::PI * 73.0).sin() * 0.3;
             noise.push((sample + high_freq).clamp(-1.0, 1.0));
         }
+        noise
+    }
+
+    #[test]
+    fn test_estimate_keeps_unsure_readings() {
+        let detector = PitchDetector::new(SAMPLE_RATE);
+
+        let estimate = detector.estimate(&noise()).expect("Should always guess");
+        assert!(estimate.confidence <= MIN_DETECT_CONFIDENCE, "{estimate:?}");
+        assert!(detector.detect(&noise()).is_none());
+
+        // Confident readings come through both
+        let source = TestAudioSource::sine(440.0, 0.2, SAMPLE_RATE);
+        let detected = detector.detect(source.samples()).expect("Should detect");
+        let estimated = detector.estimate(source.samples()).expect("Should guess");
+        assert_eq!(detected.frequency, estimated.frequency);
+    }
 
+    #[test]
+    fn test_noise_low_confidence() {
         let detector = PitchDetector::new(SAMPLE_RATE).with_threshold(0.1);
-        let result = detector.detect(&noise);
+        let result = detector.detect(&noise());
 
         // Noise should either return None or the detector should reject it
         // due to the threshold (which results in None)
@@ -354,4 +685,132 @@ mod tests {
             );
         }
     }
+
+    /// A sine at `frequency` whose level dips by `depth` `rate` times per second.
+    fn modulated(frequency: f32, rate: f32, depth: f32, secs: f32) -> Vec<f32> {
+        let source = TestAudioSource::sine(frequency, secs, SAMPLE_RATE);
+        source
+            .samples()
+            .iter()
+            .enumerate()
+            .map(|(i, s)| {
+                let t = i as f32 / SAMPLE_RATE as f32;
+                let dip = 0.5 * depth * (1.0 - (2.0 * std::f32::consts::PI * rate * t).cos());
+                s * (1.0 - dip)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_amplitude_modulated_tone_flags_false_beats() {
+        let samples = modulated(110.0, 4.0, 0.5, 1.0);
+        assert!(false_beats(&samples, SAMPLE_RATE, 110.0));
+    }
+
+    #[test]
+    fn test_window_checks_slow_beats_across_buffers() {
+        let samples = modulated(110.0, 0.5, 0.5, 5.0);
+        let mut window = FalseBeatWindow::new();
+        let mut flagged = Vec::new();
+        for buffer in samples.chunks(SAMPLE_RATE as usize / 10) {
+            assert!(!false_beats(buffer, SAMPLE_RATE, 110.0));
+            window.push(buffer, SAMPLE_RATE);
+            flagged.push(window.check(SAMPLE_RATE, 110.0));
+        }
+
+        // Nothing is judged until two seconds have arrived
+        assert!(flagged[..19].iter().all(Option::is_none));
+        assert_eq!(flagged.last(), Some(&Some(true)));
+
+        window.reset();
+        assert_eq!(window.check(SAMPLE_RATE, 110.0), None);
+    }
+
+    #[test]
+    fn test_steady_and_decaying_tones_have_no_false_beats() {
+        let steady = TestAudioSource::sine(55.0, 1.0, SAMPLE_RATE);
+        assert!(!false_beats(steady.samples(), SAMPLE_RATE, 55.0));
+
+        let decaying: Vec<f32> = steady
+            .samples()
+            .iter()
+            .enumerate()
+            .map(|(i, s)| s * (-3.0 * i as f32 / SAMPLE_RATE as f32).exp())
+            .collect();
+        assert!(!false_beats(&decaying, SAMPLE_RATE, 55.0));
+    }
+
+    #[test]
+    fn test_shallow_modulation_not_flagged() {
+        let samples = modulated(220.0, 4.0, 0.1, 1.0);
+        assert!(!false_beats(&samples, SAMPLE_RATE, 220.0));
+    }
+
+    #[test]
+    fn test_octave_doubled_signal_is_ambiguous() {
+        // Note and its octave sounding at equal strength
+        let source = TestAudioSource::sine_with_harmonics(220.0, &[(2.0, 1.0)], 0.2, SAMPLE_RATE);
+        assert!(octave_ambiguity(source.samples(), SAMPLE_RATE, 220.0));
+    }
+
+    #[test]
+    fn test_partials_of_harmonic_tone_near_integer_multiples() {
+        // A rich tone on A2 with its fourth partial missing
+        let source = TestAudioSource::sine_with_harmonics(
+            110.0,
+            &[(2.0, 0.5), (3.0, 0.33), (5.0, 0.2), (6.0, 0.17)],
+            0.2,
+            SAMPLE_RATE,
+        );
+        let detector = PitchDetector::new(SAMPLE_RATE).with_partials(6);
+        let result = detector
+            .detect(source.samples())
+            .expect("Should detect pitch");
+
+        assert_eq!(result.partials.len(), 6);
+        for (i, &partial) in result.partials.iter().enumerate() {
+            let n = i as f32 + 1.0;
+            if n == 4.0 {
+                assert_eq!(partial, 0.0, "missing partial read {partial:.1} Hz");
+            } else {
+                let ratio = partial / result.frequency;
+                assert!(
+                    (ratio - n).abs() < 0.01 * n,
+                    "partial {n} at ratio {ratio:.3}"
+                );
+            }
+        }
+
+        // Without partials set up, only the pitch is measured
+        let plain = PitchDetector::new(SAMPLE_RATE)
+            .detect(source.samples())
+            .expect("Should detect pitch");
+        assert!(plain.partials.is_empty());
+    }
+
+    #[test]
+    fn test_partials_stop_at_nyquist() {
+        let source = TestAudioSource::sine(4000.0, 0.1, SAMPLE_RATE);
+        let partials = find_partials(source.samples(), SAMPLE_RATE, 4000.0, 8);
+        assert_eq!(partials.len(), 5);
+        assert!((partials[0] - 4000.0).abs() < 5.0);
+    }
+
+    #[test]
+    fn test_clean_tone_is_not_ambiguous() {
+        let source = TestAudioSource::sine(220.0, 0.2, SAMPLE_RATE);
+        assert!(!octave_ambiguity(source.samples(), SAMPLE_RATE, 220.0));
+    }
+
+    #[test]
+    fn test_normal_harmonics_not_ambiguous() {
+        // Typical piano-like partials fall off above the fundamental
+        let source = TestAudioSource::sine_with_harmonics(
+            440.0,
+            &[(2.0, 0.5), (3.0, 0.3), (4.0, 0.2)],
+            0.2,
+            SAMPLE_RATE,
+        );
+        assert!(!octave_ambiguity(source.samples(), SAMPLE_RATE, 440.0));
+    }
 }
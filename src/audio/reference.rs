@@ -1,43 +1,191 @@
 //! Reference tone generation.
 
+use serde::{Deserialize, Serialize};
+
 use super::traits::AudioSink;
 
+/// Top of the bass register (C3).
+pub const BASS_MAX_HZ: f32 = 130.81;
+/// Bottom of the treble register (C6).
+pub const TREBLE_MIN_HZ: f32 = 1046.5;
+
+/// Playback gain per register, so quiet-sounding bass tones can be boosted.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RegisterGain {
+    /// Gain below C3.
+    pub bass: f32,
+    /// Gain from C3 up to C6.
+    pub mid: f32,
+    /// Gain from C6 up.
+    pub treble: f32,
+}
+
+impl RegisterGain {
+    /// Gain for a tone at `frequency`.
+    pub fn gain_for(&self, frequency: f32) -> f32 {
+        if frequency < BASS_MAX_HZ {
+            self.bass
+        } else if frequency < TREBLE_MIN_HZ {
+            self.mid
+        } else {
+            self.treble
+        }
+    }
+}
+
+impl Default for RegisterGain {
+    fn default() -> Self {
+        Self {
+            bass: 1.0,
+            mid: 1.0,
+            treble: 1.0,
+        }
+    }
+}
+
+/// Short cue marking progress through a note.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Cue {
+    /// One string of a bichord or trichord is done.
+    StringDone,
+    /// Every string of the note is done.
+    NoteDone,
+}
+
 /// Reference tone generator for pure sine waves.
 pub struct ReferenceTone {
     sample_rate: u32,
+    gain: RegisterGain,
 }
 
 impl ReferenceTone {
     /// Create a new reference tone generator.
     pub fn new(sample_rate: u32) -> Self {
-        Self { sample_rate }
+        Self {
+            sample_rate,
+            gain: RegisterGain::default(),
+        }
+    }
+
+    /// Apply per-register gain to generated tones.
+    pub fn with_gain(mut self, gain: RegisterGain) -> Self {
+        self.gain = gain;
+        self
     }
 
     /// Generate a sine wave at the given frequency.
+    ///
+    /// Boosted samples are clipped to [-1, 1].
     pub fn generate(&self, frequency: f32, duration_secs: f32) -> Vec<f32> {
         let num_samples = (self.sample_rate as f32 * duration_secs) as usize;
+        let gain = self.gain.gain_for(frequency);
         let mut samples = Vec::with_capacity(num_samples);
 
         for i in 0..num_samples {
             let t = i as f32 / self.sample_rate as f32;
-            let sample = (2.0 * std::f32::consts::PI * frequency * t).sin();
-            samples.push(sample);
+            let sample = gain * (2.0 * std::f32::consts::PI * frequency * t).sin();
+            samples.push(sample.clamp(-1.0, 1.0));
         }
 
         samples
     }
 
+    /// Generate a sine gliding from `f_start` to `f_end` Hz, like a siren.
+    ///
+    /// The glide is exponential, so it spends equal time on each octave and
+    /// passes the geometric mean halfway. The phase is continuous throughout,
+    /// leaving no clicks for a detector to trip on. Returns no samples unless
+    /// both frequencies are positive.
+    pub fn generate_sweep(&self, f_start: f32, f_end: f32, duration_secs: f32) -> Vec<f32> {
+        if f_start <= 0.0 || f_end <= 0.0 {
+            return Vec::new();
+        }
+        let num_samples = (self.sample_rate as f32 * duration_secs) as usize;
+        let duration = duration_secs as f64;
+        let (f0, rate) = (f_start as f64, (f_end as f64 / f_start as f64).ln());
+
+        (0..num_samples)
+            .map(|i| {
+                let t = i as f64 / self.sample_rate as f64;
+                // Integral of the instantaneous frequency f0 * e^(rate * t / T)
+                let cycles = if rate.abs() < 1e-9 {
+                    f0 * t
+                } else {
+                    f0 * duration / rate * ((rate * t / duration).exp() - 1.0)
+                };
+                let phase = std::f64::consts::TAU * cycles.fract();
+                let frequency = Self::sweep_frequency(f_start, f_end, (t / duration) as f32);
+                (self.gain.gain_for(frequency) * phase.sin() as f32).clamp(-1.0, 1.0)
+            })
+            .collect()
+    }
+
+    /// Instantaneous frequency of a sweep from `f_start` to `f_end` Hz,
+    /// `fraction` of the way through.
+    pub fn sweep_frequency(f_start: f32, f_end: f32, fraction: f32) -> f32 {
+        f_start * (f_end / f_start).powf(fraction)
+    }
+
+    /// Tones making up a cue, as `(frequency, seconds)` pairs.
+    ///
+    /// Both sit away from the lock beep's A6 so they can't be confused
+    /// with it: a single soft tick for a string, a rising fifth for a note.
+    pub fn cue_tones(cue: Cue) -> &'static [(f32, f32)] {
+        match cue {
+            // E6 for 40ms
+            Cue::StringDone => &[(1318.51, 0.04)],
+            // C6 then G6
+            Cue::NoteDone => &[(1046.5, 0.08), (1567.98, 0.12)],
+        }
+    }
+
+    /// Play a cue through the given sink.
+    pub fn play_cue<S: AudioSink>(&self, sink: &mut S, cue: Cue) {
+        for &(frequency, duration_secs) in Self::cue_tones(cue) {
+            self.play(sink, frequency, duration_secs);
+        }
+    }
+
     /// Play a reference tone through the given sink.
     pub fn play<S: AudioSink>(&self, sink: &mut S, frequency: f32, duration_secs: f32) {
         let samples = self.generate(frequency, duration_secs);
         sink.write_samples(&samples);
     }
+
+    /// Play each frequency in turn, separated by `gap_secs` of silence.
+    ///
+    /// `should_stop` is asked before each tone with its index; returns the
+    /// number of tones played.
+    pub fn sweep<S: AudioSink>(
+        &self,
+        sink: &mut S,
+        frequencies: &[f32],
+        duration_secs: f32,
+        gap_secs: f32,
+        mut should_stop: impl FnMut(usize) -> bool,
+    ) -> usize {
+        let gap = vec![0.0; (self.sample_rate as f32 * gap_secs) as usize];
+
+        for (i, &frequency) in frequencies.iter().enumerate() {
+            if should_stop(i) {
+                return i;
+            }
+            self.play(sink, frequency, duration_secs);
+            if !gap.is_empty() {
+                sink.write_samples(&gap);
+            }
+        }
+
+        frequencies.len()
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::audio::traits::TestAudioSink;
+    use crate::audio::PitchDetector;
 
     #[test]
     fn test_generate_correct_sample_count() {
@@ -71,7 +219,7 @@ mod tests {
         // Sine wave should be in range [-1, 1]
         assert!(max > 0.99 && max <= 1.0, "max should be ~1.0, got {}", max);
         assert!(
-            min < -0.99 && min >= -1.0,
+            (-1.0..-0.99).contains(&min),
             "min should be ~-1.0, got {}",
             min
         );
@@ -139,6 +287,133 @@ mod tests {
         assert_eq!(sink.samples().len(), 4410);
     }
 
+    /// Sink that keeps each write separate.
+    struct WriteLog {
+        writes: Vec<Vec<f32>>,
+    }
+
+    impl AudioSink for WriteLog {
+        fn write_samples(&mut self, samples: &[f32]) {
+            self.writes.push(samples.to_vec());
+        }
+
+        fn sample_rate(&self) -> u32 {
+            44100
+        }
+    }
+
+    #[test]
+    fn test_sweep_queues_each_frequency() {
+        let gen = ReferenceTone::new(44100);
+        let mut sink = WriteLog { writes: Vec::new() };
+        let targets = [174.61, 185.0, 196.0, 207.65, 220.0];
+
+        let played = gen.sweep(&mut sink, &targets, 0.5, 0.1, |_| false);
+        assert_eq!(played, targets.len());
+
+        let tones: Vec<&Vec<f32>> = sink
+            .writes
+            .iter()
+            .filter(|w| w.iter().any(|&s| s != 0.0))
+            .collect();
+        assert_eq!(tones.len(), targets.len());
+        assert_eq!(sink.writes.len(), targets.len() * 2, "one gap per tone");
+
+        for (tone, &target) in tones.iter().zip(&targets) {
+            let crossings = tone
+                .windows(2)
+                .filter(|w| w[0] < 0.0 && w[1] >= 0.0)
+                .count();
+            let freq = crossings as f32 / 0.5;
+            assert!((freq - target).abs() < 4.0, "{target}: got {freq}");
+        }
+    }
+
+    /// Frequency from the positive zero crossings in `samples`.
+    fn crossing_frequency(samples: &[f32], sample_rate: u32) -> f32 {
+        let crossings: Vec<usize> = (1..samples.len())
+            .filter(|&i| samples[i - 1] < 0.0 && samples[i] >= 0.0)
+            .collect();
+        let (first, last) = (crossings[0], crossings[crossings.len() - 1]);
+        (crossings.len() - 1) as f32 * sample_rate as f32 / (last - first) as f32
+    }
+
+    #[test]
+    fn test_generate_sweep_passes_geometric_mean_halfway() {
+        let gen = ReferenceTone::new(44100);
+        let samples = gen.generate_sweep(110.0, 880.0, 4.0);
+        assert_eq!(samples.len(), 4 * 44100);
+
+        // 50ms around the midpoint
+        let middle = &samples[2 * 44100 - 1102..2 * 44100 + 1102];
+        let freq = crossing_frequency(middle, 44100);
+        let mean = (110.0f32 * 880.0).sqrt();
+        assert!(
+            (freq - mean).abs() < 3.0,
+            "expected ~{mean:.1} Hz, got {freq:.1}"
+        );
+        assert_eq!(ReferenceTone::sweep_frequency(110.0, 880.0, 0.5), mean);
+
+        // Continuous phase: no step bigger than the top frequency allows
+        let max_step = std::f32::consts::TAU * 880.0 / 44100.0;
+        assert!(samples
+            .windows(2)
+            .all(|w| (w[1] - w[0]).abs() <= max_step * 1.01));
+    }
+
+    #[test]
+    fn test_detection_follows_sweep() {
+        let gen = ReferenceTone::new(44100);
+        let (f_start, f_end, secs) = (110.0, 1760.0, 8.0);
+        let samples = gen.generate_sweep(f_start, f_end, secs);
+        let detector = PitchDetector::new(44100);
+
+        for fraction in [0.1, 0.3, 0.5, 0.7, 0.9] {
+            let expected = ReferenceTone::sweep_frequency(f_start, f_end, fraction);
+            let window = detector.window_for_freq(expected);
+            let center = (fraction * secs * 44100.0) as usize;
+            let result = detector
+                .detect(&samples[center - window / 2..center + window / 2])
+                .expect("Should detect the sweep");
+            let cents = 1200.0 * (result.frequency / expected).log2();
+            assert!(
+                cents.abs() < 10.0,
+                "at {fraction}: expected {expected:.1} Hz, read {:.1}",
+                result.frequency
+            );
+        }
+    }
+
+    #[test]
+    fn test_generate_sweep_rejects_nonpositive_frequencies() {
+        let gen = ReferenceTone::new(44100);
+        assert!(gen.generate_sweep(0.0, 440.0, 1.0).is_empty());
+        // A flat sweep is a steady tone
+        let flat = gen.generate_sweep(440.0, 440.0, 1.0);
+        assert!((crossing_frequency(&flat, 44100) - 440.0).abs() < 1.0);
+    }
+
+    #[test]
+    fn test_sweep_stops_when_asked() {
+        let gen = ReferenceTone::new(44100);
+        let mut sink = TestAudioSink::new(44100);
+
+        let played = gen.sweep(&mut sink, &[220.0, 440.0, 880.0], 0.1, 0.0, |i| i == 2);
+        assert_eq!(played, 2);
+        assert_eq!(sink.samples().len(), 2 * 4410);
+    }
+
+    #[test]
+    fn test_cues_are_distinct() {
+        let string_done = ReferenceTone::cue_tones(Cue::StringDone);
+        let note_done = ReferenceTone::cue_tones(Cue::NoteDone);
+        assert_ne!(string_done, note_done);
+
+        let mut sink = WriteLog { writes: Vec::new() };
+        ReferenceTone::new(44100).play_cue(&mut sink, Cue::NoteDone);
+        assert_eq!(sink.writes.len(), note_done.len());
+    }
+
     #[test]
     fn test_play_multiple_times() {
         let gen = ReferenceTone::new(44100);
@@ -158,6 +433,34 @@ mod tests {
         assert_eq!(samples.len(), 0);
     }
 
+    #[test]
+    fn test_register_gain_boosts_bass() {
+        let gain = RegisterGain {
+            bass: 2.0,
+            mid: 1.0,
+            treble: 0.5,
+        };
+        assert_eq!(gain.gain_for(55.0), 2.0); // A1
+        assert_eq!(gain.gain_for(440.0), 1.0); // A4
+        assert_eq!(gain.gain_for(1760.0), 0.5); // A6
+    }
+
+    #[test]
+    fn test_generate_applies_register_gain() {
+        let gain = RegisterGain {
+            bass: 1.0,
+            mid: 1.0,
+            treble: 0.25,
+        };
+        let gen = ReferenceTone::new(44100).with_gain(gain);
+        let peak = |samples: Vec<f32>| samples.iter().fold(0.0_f32, |m, s| m.max(s.abs()));
+
+        let bass = peak(gen.generate(55.0, 0.1));
+        let treble = peak(gen.generate(1760.0, 0.1));
+        assert!(bass > 0.99);
+        assert!((treble - 0.25).abs() < 0.01);
+    }
+
     #[test]
     fn test_sine_wave_starts_at_zero() {
         let gen = ReferenceTone::new(44100);
@@ -0,0 +1,91 @@
+//! Watchdog for a microphone that has gone quiet.
+//!
+//! A muted or misconfigured input delivers near-digital silence, well below
+//! any room's noise floor. Once that lasts long enough, the tuner is better
+//! served by a hint to check the microphone than by "Listening..." forever.
+
+/// RMS level below which input counts as dead silence (about -70 dBFS).
+pub const SILENCE_FLOOR_RMS: f32 = 0.0003;
+/// Seconds of dead silence before the input is reported as missing.
+pub const DEFAULT_SILENCE_SECS: f32 = 10.0;
+
+/// Accumulates time spent below the silence floor.
+#[derive(Debug, Clone)]
+pub struct SilenceWatchdog {
+    floor_rms: f32,
+    limit_secs: f32,
+    silent_secs: f32,
+}
+
+impl SilenceWatchdog {
+    /// Create a watchdog with the default floor and time limit.
+    pub fn new() -> Self {
+        Self::with_limit(DEFAULT_SILENCE_SECS)
+    }
+
+    /// Create a watchdog that trips after `limit_secs` of silence.
+    pub fn with_limit(limit_secs: f32) -> Self {
+        Self {
+            floor_rms: SILENCE_FLOOR_RMS,
+            limit_secs,
+            silent_secs: 0.0,
+        }
+    }
+
+    /// Feed the input level over the last `elapsed_secs`, returning whether
+    /// the input has been silent for too long. Any audio resets the count.
+    pub fn update(&mut self, level: f32, elapsed_secs: f32) -> bool {
+        if level >= self.floor_rms {
+            self.silent_secs = 0.0;
+        } else {
+            self.silent_secs += elapsed_secs.max(0.0);
+        }
+        self.is_silent()
+    }
+
+    /// Check if the input has been silent for longer than the limit.
+    pub fn is_silent(&self) -> bool {
+        self.silent_secs >= self.limit_secs
+    }
+
+    /// Forget any accumulated silence.
+    pub fn reset(&mut self) {
+        self.silent_secs = 0.0;
+    }
+}
+
+impl Default for SilenceWatchdog {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_silence_beyond_limit_trips() {
+        let mut watchdog = SilenceWatchdog::with_limit(2.0);
+        for _ in 0..19 {
+            assert!(!watchdog.update(0.0, 0.1));
+        }
+        assert!(watchdog.update(0.0001, 0.1));
+        assert!(watchdog.is_silent());
+    }
+
+    #[test]
+    fn test_any_audio_resets() {
+        let mut watchdog = SilenceWatchdog::with_limit(2.0);
+        watchdog.update(0.0, 3.0);
+        assert!(watchdog.is_silent());
+
+        // Quiet room noise is still audio
+        assert!(!watchdog.update(0.001, 0.1));
+        assert!(!watchdog.update(0.0, 1.5));
+
+        watchdog.update(0.0, 1.0);
+        watchdog.reset();
+        assert!(!watchdog.is_silent());
+    }
+}
@@ -0,0 +1,165 @@
+//! Smoothing of successive pitch readings.
+
+use std::collections::VecDeque;
+
+use serde::{Deserialize, Serialize};
+
+/// Readings kept by default (~0.5 second of 100ms buffers).
+pub const DEFAULT_WINDOW: usize = 5;
+
+/// How quickly the display follows the pitch, trading latency for steadiness.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize, clap::ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum ResponsePreset {
+    /// Unsmoothed and snappy, but jittery.
+    Fast,
+    /// Half a second of smoothing.
+    #[default]
+    Balanced,
+    /// Slow to move, rock-solid once settled.
+    Stable,
+}
+
+impl ResponsePreset {
+    /// Readings in the smoothing window.
+    pub fn smoothing_window(self) -> usize {
+        match self {
+            Self::Fast => 1,
+            Self::Balanced => DEFAULT_WINDOW,
+            Self::Stable => 9,
+        }
+    }
+
+    /// Seconds a reading stays up after the pitch drops out.
+    pub fn hold_secs(self) -> f32 {
+        match self {
+            Self::Fast => 0.0,
+            Self::Balanced => 0.2,
+            Self::Stable => 0.5,
+        }
+    }
+
+    /// Detector confidence a reading needs to be shown.
+    pub fn min_confidence(self) -> f32 {
+        match self {
+            Self::Fast => 0.5,
+            Self::Balanced => 0.6,
+            Self::Stable => 0.75,
+        }
+    }
+}
+
+/// Median filter over the most recent pitch readings.
+///
+/// A median rather than a mean keeps a single octave glitch from dragging
+/// the displayed value.
+#[derive(Debug, Clone)]
+pub struct PitchSmoother {
+    window: usize,
+    readings: VecDeque<f32>,
+}
+
+impl PitchSmoother {
+    /// Create a smoother with the default window.
+    pub fn new() -> Self {
+        Self::with_window(DEFAULT_WINDOW)
+    }
+
+    /// Create a smoother over the last `window` readings (at least 1).
+    pub fn with_window(window: usize) -> Self {
+        let window = window.max(1);
+        Self {
+            window,
+            readings: VecDeque::with_capacity(window),
+        }
+    }
+
+    /// Get the window length.
+    pub fn window(&self) -> usize {
+        self.window
+    }
+
+    /// Add a reading and return the smoothed value.
+    pub fn push(&mut self, value: f32) -> f32 {
+        if self.readings.len() == self.window {
+            self.readings.pop_front();
+        }
+        self.readings.push_back(value);
+
+        let mut sorted: Vec<f32> = self.readings.iter().copied().collect();
+        sorted.sort_by(|a, b| a.total_cmp(b));
+
+        let mid = sorted.len() / 2;
+        if sorted.len() & 1 == 1 {
+            sorted[mid]
+        } else {
+            (sorted[mid - 1] + sorted[mid]) / 2.0
+        }
+    }
+
+    /// Drop all readings so the next push starts fresh.
+    pub fn reset(&mut self) {
+        self.readings.clear();
+    }
+}
+
+impl Default for PitchSmoother {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_response_presets() {
+        let fast = ResponsePreset::Fast;
+        assert_eq!(fast.smoothing_window(), 1);
+        assert_eq!(fast.hold_secs(), 0.0);
+        assert_eq!(fast.min_confidence(), 0.5);
+
+        let balanced = ResponsePreset::default();
+        assert_eq!(balanced, ResponsePreset::Balanced);
+        assert_eq!(balanced.smoothing_window(), DEFAULT_WINDOW);
+        assert_eq!(balanced.hold_secs(), 0.2);
+        assert_eq!(balanced.min_confidence(), 0.6);
+
+        let stable = ResponsePreset::Stable;
+        assert_eq!(stable.smoothing_window(), 9);
+        assert_eq!(stable.hold_secs(), 0.5);
+        assert_eq!(stable.min_confidence(), 0.75);
+    }
+
+    #[test]
+    fn test_median_rejects_outlier() {
+        let mut smoother = PitchSmoother::with_window(3);
+        smoother.push(440.0);
+        smoother.push(441.0);
+        // Octave glitch
+        assert_eq!(smoother.push(880.0), 441.0);
+    }
+
+    #[test]
+    fn test_window_drops_old_readings() {
+        let mut smoother = PitchSmoother::with_window(3);
+        for _ in 0..3 {
+            smoother.push(220.0);
+        }
+        smoother.push(440.0);
+        smoother.push(440.0);
+        assert_eq!(smoother.push(440.0), 440.0);
+    }
+
+    #[test]
+    fn test_reset_returns_first_value_unsmoothed() {
+        let mut smoother = PitchSmoother::new();
+        for _ in 0..DEFAULT_WINDOW {
+            smoother.push(261.6);
+        }
+
+        smoother.reset();
+        assert_eq!(smoother.push(293.7), 293.7);
+    }
+}
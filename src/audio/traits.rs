@@ -9,6 +9,9 @@ pub trait AudioSource {
 
     /// Get the sample rate in Hz.
     fn sample_rate(&self) -> u32;
+
+    /// Discard buffered audio so the next read only sees fresh samples.
+    fn flush(&mut self) {}
 }
 
 /// Audio output sink trait.
@@ -6,6 +6,21 @@ use serde::{Deserialize, Serialize};
 use std::fs;
 use std::path::PathBuf;
 
+use crate::audio::filter::{
+    is_valid_highpass, is_valid_hum_notch, HUM_NOTCH_RANGE, MAX_HIGHPASS_HZ,
+};
+use crate::audio::{RegisterGain, ResponsePreset};
+use crate::tuning::notes::{parse_midi_range, Note, NoteSpelling, StringLayout};
+use crate::tuning::order::{DEFAULT_TEMPERAMENT_START, TEMPERAMENT_START_RANGE};
+use crate::tuning::storage::data_dir;
+use crate::tuning::temperament::A4Preset;
+use crate::ui::app::ConfirmAction;
+use crate::ui::components::{KeyColors, MeterCurve};
+use crate::ui::format::{DEFAULT_CENTS_PRECISION, MAX_CENTS_PRECISION};
+use crate::ui::language::Language;
+use crate::ui::screens::profiling::EmptyConfirm;
+use crate::ui::screens::tuning::KeyboardView;
+
 /// CLI Piano Tuner with guided coaching.
 #[derive(Parser, Debug)]
 #[command(name = "pianito")]
@@ -26,18 +41,122 @@ pub struct Args {
     #[arg(long)]
     pub a4: Option<f32>,
 
+    /// Common A4 reference by name, e.g. baroque (415.3 Hz).
+    #[arg(long, value_enum, conflicts_with = "a4")]
+    pub a4_preset: Option<A4Preset>,
+
     /// Enable audio confirmation beep.
     #[arg(long)]
     pub beep: bool,
+
+    /// Never play any tones (reference, beeps, fanfare).
+    #[arg(long)]
+    pub quiet: bool,
+
+    /// Note name spelling for black keys.
+    #[arg(long, value_enum)]
+    pub spelling: Option<NoteSpelling>,
+
+    /// Language for coaching instructions.
+    #[arg(long, value_enum)]
+    pub language: Option<Language>,
+
+    /// Reading responsiveness: fast (jittery), balanced, or stable (slow).
+    #[arg(long, value_enum)]
+    pub response: Option<ResponsePreset>,
+
+    /// What confirming a note does: advance to the next note, or stay on it.
+    #[arg(long, value_enum)]
+    pub on_confirm: Option<ConfirmAction>,
+
+    /// Keyboard progress: chromatic, or order (marks the next note to tune).
+    #[arg(long, value_enum)]
+    pub keyboard: Option<KeyboardView>,
+
+    /// Meter scale: linear, or expanded (stretches the ±10 cent region).
+    #[arg(long, value_enum)]
+    pub meter_curve: Option<MeterCurve>,
+
+    /// Deviation colors on the keyboard: fixed (5/15 cents), pass (in or
+    /// out of tolerance), or bands (in, within 3× tolerance, beyond).
+    #[arg(long, value_enum)]
+    pub key_colors: Option<KeyColors>,
+
+    /// Decimal places on cents readouts (0, 1 or 2).
+    #[arg(long, value_parser = clap::value_parser!(u8).range(0..=2))]
+    pub cents_precision: Option<u8>,
+
+    /// Note the temperament octave starts from, C3 to C4 (default F3).
+    #[arg(long, value_name = "NOTE", value_parser = parse_temperament_start)]
+    pub temperament_start: Option<u8>,
+
+    /// While profiling, record each key once its reading holds steady.
+    #[arg(long)]
+    pub auto_profile: bool,
+
+    /// While profiling, what confirming a key with no reading does:
+    /// reject (stay on it), or unmeasured (move on, flagging it).
+    #[arg(long, value_enum)]
+    pub empty_confirm: Option<EmptyConfirm>,
+
+    /// Label for the piano being tuned, shown in session reports.
+    #[arg(long)]
+    pub piano: Option<String>,
+
+    /// Tune the high treble by its beats against the octave below.
+    #[arg(long)]
+    pub octave_beats: bool,
+
+    /// Keep the listening indicator still instead of animating it.
+    #[arg(long)]
+    pub reduced_motion: bool,
+
+    /// Confirm notes with the pitch read just after the strike, rather
+    /// than the latest (decayed) reading.
+    #[arg(long)]
+    pub attack_confirm: bool,
+
+    /// Downsample mid and high notes by this factor before detection, to
+    /// save CPU on slow devices (e.g. 2 or 4).
+    #[arg(long, value_parser = clap::value_parser!(u32).range(1..=8))]
+    pub decimate: Option<u32>,
+
+    /// High-pass the microphone at this frequency in Hz, e.g. 20 to drop
+    /// rumble below A0 (up to 27.5).
+    #[arg(long, value_name = "HZ", value_parser = parse_highpass)]
+    pub highpass: Option<f32>,
+
+    /// Notch out mains hum at this frequency in Hz (50 or 60; 40 to 70).
+    #[arg(long, value_name = "HZ", value_parser = parse_hum_notch)]
+    pub hum_notch: Option<f32>,
+
+    /// Play tones through the output device with this name.
+    #[arg(long)]
+    pub output_device: Option<String>,
+
+    /// Remember this run's settings for `--use-last`.
+    #[arg(long)]
+    pub remember: bool,
+
+    /// Restore the settings saved by the last `--remember` run.
+    #[arg(long)]
+    pub use_last: bool,
 }
 
 /// Subcommands.
 #[derive(Subcommand, Debug)]
 pub enum Command {
-    /// Analyze a recording for pitch.
+    /// Analyze a recording for pitch, or compare two recordings.
     Analyze {
         /// Path to WAV file.
-        file: String,
+        #[arg(required_unless_present = "before", conflicts_with = "before")]
+        file: Option<String>,
+        /// Recording made before tuning, to compare with `--after`.
+        #[arg(long, requires = "after")]
+        before: Option<String>,
+        /// Recording of the same notes after tuning.
+        #[arg(long, requires = "before")]
+        after: Option<String>,
     },
     /// Generate a reference tone.
     Reference {
@@ -47,8 +166,49 @@ pub enum Command {
         #[arg(long, default_value = "2.0")]
         duration: f32,
     },
+    /// Play every target in tuning order to check a finished tuning by ear.
+    Sweep {
+        /// Seconds each note plays.
+        #[arg(long, default_value = "0.5")]
+        duration: f32,
+        /// Seconds of silence between notes.
+        #[arg(long, default_value = "0.1")]
+        gap: f32,
+        /// Apply stretch tuning to the targets.
+        #[arg(long)]
+        stretch: bool,
+    },
+    /// Tune a single note, then exit.
+    Tune {
+        /// Note name (e.g., "A4", "C#5").
+        note: String,
+    },
+    /// Practise setting the temperament octave, with a per-interval report.
+    Practice,
+    /// Show expected beat rates for checking the temperament octave.
+    Intervals,
+    /// Check the microphone and pitch detection, printing a report.
+    Doctor {
+        /// Seconds to capture.
+        #[arg(long, default_value = "2.0")]
+        seconds: f32,
+    },
+    /// Play an exact reference tone to check against another tuner, and
+    /// show what the microphone hears of it.
+    Loopback {
+        /// Frequency of the tone in Hz.
+        #[arg(long, default_value = "440.0")]
+        freq: f32,
+        /// Seconds to play and listen.
+        #[arg(long, default_value = "3.0")]
+        seconds: f32,
+    },
     /// Show tuning history.
-    History,
+    History {
+        /// Print a full report of the latest session instead.
+        #[arg(long)]
+        report: bool,
+    },
     /// Clear saved sessions.
     Reset,
 }
@@ -62,22 +222,169 @@ pub struct Config {
     /// Default tolerance in cents.
     #[serde(default = "default_tolerance")]
     pub tolerance: f32,
+    /// Tolerance in cents for a note to count as complete (defaults to `tolerance`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub confirm_tolerance: Option<f32>,
     /// Enable beep on lock.
     #[serde(default)]
     pub beep: bool,
+    /// Suppress all tone playback.
+    #[serde(default)]
+    pub quiet: bool,
+    /// Save the session every N notes (0 = only on quit).
+    #[serde(default = "default_autosave_every")]
+    pub autosave_every: usize,
     /// Default tuning mode ("concert" or "quick").
     #[serde(default = "default_mode")]
     pub default_mode: String,
+    /// Per-instrument string count overrides.
+    #[serde(default)]
+    pub strings: StringsConfig,
+    /// Note name spelling ("sharps", "flats", or "both").
+    #[serde(default)]
+    pub spelling: NoteSpelling,
+    /// Coaching instruction language ("english" or "spanish").
+    #[serde(default)]
+    pub language: Language,
+    /// Reading responsiveness ("fast", "balanced", or "stable").
+    #[serde(default)]
+    pub response: ResponsePreset,
+    /// What confirming a note does ("advance" or "stay").
+    #[serde(default)]
+    pub on_confirm: ConfirmAction,
+    /// Keyboard progress view ("chromatic" or "order").
+    #[serde(default)]
+    pub keyboard: KeyboardView,
+    /// Meter scale ("linear" or "expanded").
+    #[serde(default)]
+    pub meter_curve: MeterCurve,
+    /// Keyboard deviation colors ("fixed", "pass" or "bands").
+    #[serde(default)]
+    pub key_colors: KeyColors,
+    /// Decimal places on cents readouts (0 to 2).
+    #[serde(default = "default_cents_precision")]
+    pub cents_precision: u8,
+    /// Note the temperament octave starts from, e.g. "A3" (C3 to C4).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub temperament_start: Option<String>,
+    /// Auto-confirm steady readings while profiling.
+    #[serde(default)]
+    pub auto_profile: bool,
+    /// What confirming a profiled key with no reading does.
+    #[serde(default)]
+    pub empty_confirm: EmptyConfirm,
+    /// Coach high treble notes by their octave beats.
+    #[serde(default)]
+    pub octave_beats: bool,
+    /// Keep the listening indicator still.
+    #[serde(default)]
+    pub reduced_motion: bool,
+    /// Confirm notes with the pitch read during the strike's attack.
+    #[serde(default)]
+    pub attack_confirm: bool,
+    /// Downsampling factor for detection on mid and high notes (1 = off).
+    #[serde(default = "default_decimate")]
+    pub decimate: u32,
+    /// High-pass cutoff for the microphone in Hz.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub highpass: Option<f32>,
+    /// Mains hum frequency to notch out, in Hz.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hum_notch: Option<f32>,
+    /// Reference tone gain per register.
+    #[serde(default)]
+    pub volume: RegisterGain,
+}
+
+/// String count overrides by MIDI range, e.g. `mono = "21-27"`.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct StringsConfig {
+    /// Range of monochord (1 string) notes.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mono: Option<String>,
+    /// Range of bichord (2 string) notes.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bi: Option<String>,
+    /// Range of trichord (3 string) notes.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tri: Option<String>,
+}
+
+impl StringsConfig {
+    /// Build the string layout. Unparseable ranges are ignored.
+    pub fn layout(&self) -> StringLayout {
+        [(&self.mono, 1), (&self.bi, 2), (&self.tri, 3)]
+            .into_iter()
+            .filter_map(|(range, strings)| {
+                range
+                    .as_deref()
+                    .and_then(parse_midi_range)
+                    .map(|r| (r, strings))
+            })
+            .fold(StringLayout::new(), |layout, (range, strings)| {
+                layout.with_range(range, strings)
+            })
+    }
 }
 
 fn default_a4() -> f32 {
     440.0
 }
 
+fn default_cents_precision() -> u8 {
+    DEFAULT_CENTS_PRECISION
+}
+
+/// Parse a high-pass cutoff, which must keep A0.
+fn parse_highpass(text: &str) -> Result<f32, String> {
+    let hz: f32 = text
+        .parse()
+        .map_err(|_| format!("not a number: {}", text))?;
+    if !is_valid_highpass(hz) {
+        return Err(format!(
+            "must be above 0 and at most {} Hz",
+            MAX_HIGHPASS_HZ
+        ));
+    }
+    Ok(hz)
+}
+
+/// Parse a mains hum frequency to notch out.
+fn parse_hum_notch(text: &str) -> Result<f32, String> {
+    let hz: f32 = text
+        .parse()
+        .map_err(|_| format!("not a number: {}", text))?;
+    if !is_valid_hum_notch(hz) {
+        return Err(format!(
+            "must be {} to {} Hz",
+            HUM_NOTCH_RANGE.start(),
+            HUM_NOTCH_RANGE.end()
+        ));
+    }
+    Ok(hz)
+}
+
+/// Parse a temperament starting note like "A3" into its MIDI number.
+fn parse_temperament_start(name: &str) -> Result<u8, String> {
+    let note = Note::from_name(name).ok_or_else(|| format!("unknown note: {}", name))?;
+    if !TEMPERAMENT_START_RANGE.contains(&note.midi) {
+        return Err(format!("{} is outside C3 to C4", note.display_name()));
+    }
+    Ok(note.midi)
+}
+
 fn default_tolerance() -> f32 {
     5.0
 }
 
+fn default_autosave_every() -> usize {
+    1
+}
+
+fn default_decimate() -> u32 {
+    1
+}
+
 fn default_mode() -> String {
     "concert".to_string()
 }
@@ -87,8 +394,30 @@ impl Default for Config {
         Self {
             a4: default_a4(),
             tolerance: default_tolerance(),
+            confirm_tolerance: None,
             beep: false,
+            quiet: false,
+            autosave_every: default_autosave_every(),
             default_mode: default_mode(),
+            strings: StringsConfig::default(),
+            spelling: NoteSpelling::default(),
+            language: Language::default(),
+            response: ResponsePreset::default(),
+            on_confirm: ConfirmAction::default(),
+            keyboard: KeyboardView::default(),
+            meter_curve: MeterCurve::default(),
+            key_colors: KeyColors::default(),
+            cents_precision: default_cents_precision(),
+            temperament_start: None,
+            auto_profile: false,
+            empty_confirm: EmptyConfirm::default(),
+            octave_beats: false,
+            reduced_motion: false,
+            attack_confirm: false,
+            decimate: default_decimate(),
+            highpass: None,
+            hum_notch: None,
+            volume: RegisterGain::default(),
         }
     }
 }
@@ -133,29 +462,180 @@ impl Config {
 
     /// Merge CLI args with config, returning effective settings.
     pub fn merge_with_args(&self, args: &Args) -> EffectiveConfig {
+        self.merge_with_last(args, None)
+    }
+
+    /// Merge CLI args, last-used settings, and config.
+    ///
+    /// Precedence: explicit flags > last-used > config defaults.
+    pub fn merge_with_last(&self, args: &Args, last: Option<&LastUsed>) -> EffectiveConfig {
+        let last = last.cloned().unwrap_or_default();
         EffectiveConfig {
-            a4: args.a4.unwrap_or(self.a4),
+            a4: args
+                .a4
+                .or(args.a4_preset.map(A4Preset::frequency))
+                .or(last.a4)
+                .unwrap_or(self.a4),
             tolerance: self.tolerance,
-            beep: args.beep || self.beep,
-            quick_mode: args.quick || self.default_mode == "quick",
+            confirm_tolerance: self.confirm_tolerance.unwrap_or(self.tolerance),
+            beep: args.beep || last.beep.unwrap_or(self.beep),
+            quiet: args.quiet || self.quiet,
+            autosave_every: self.autosave_every,
+            quick_mode: args.quick || last.quick_mode.unwrap_or(self.default_mode == "quick"),
             resume: args.resume,
+            string_layout: self.strings.layout(),
+            spelling: args.spelling.or(last.spelling).unwrap_or(self.spelling),
+            language: args.language.unwrap_or(self.language),
+            response: args.response.unwrap_or(self.response),
+            on_confirm: args.on_confirm.unwrap_or(self.on_confirm),
+            keyboard: args.keyboard.unwrap_or(self.keyboard),
+            meter_curve: args.meter_curve.unwrap_or(self.meter_curve),
+            key_colors: args.key_colors.unwrap_or(self.key_colors),
+            cents_precision: args
+                .cents_precision
+                .unwrap_or(self.cents_precision)
+                .min(MAX_CENTS_PRECISION),
+            temperament_start: args
+                .temperament_start
+                .or_else(|| {
+                    let name = self.temperament_start.as_deref()?;
+                    parse_temperament_start(name).ok()
+                })
+                .unwrap_or(DEFAULT_TEMPERAMENT_START),
+            auto_profile: args.auto_profile || self.auto_profile,
+            empty_confirm: args.empty_confirm.unwrap_or(self.empty_confirm),
+            octave_beats: args.octave_beats || self.octave_beats,
+            reduced_motion: args.reduced_motion || self.reduced_motion,
+            attack_confirm: args.attack_confirm || self.attack_confirm,
+            decimate: args.decimate.unwrap_or(self.decimate).max(1),
+            highpass: args
+                .highpass
+                .or(self.highpass.filter(|&hz| is_valid_highpass(hz))),
+            hum_notch: args
+                .hum_notch
+                .or(self.hum_notch.filter(|&hz| is_valid_hum_notch(hz))),
+            register_gain: self.volume,
+            piano: args.piano.clone(),
+            output_device: args.output_device.clone(),
         }
     }
 }
 
+/// Settings remembered from the last `--remember` run.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct LastUsed {
+    /// A4 reference frequency.
+    #[serde(default)]
+    pub a4: Option<f32>,
+    /// Enable beep on lock.
+    #[serde(default)]
+    pub beep: Option<bool>,
+    /// Use quick tune mode.
+    #[serde(default)]
+    pub quick_mode: Option<bool>,
+    /// Note name spelling.
+    #[serde(default)]
+    pub spelling: Option<NoteSpelling>,
+}
+
+impl LastUsed {
+    /// Capture the effective settings of a run.
+    pub fn from_effective(effective: &EffectiveConfig) -> Self {
+        Self {
+            a4: Some(effective.a4),
+            beep: Some(effective.beep),
+            quick_mode: Some(effective.quick_mode),
+            spelling: Some(effective.spelling),
+        }
+    }
+
+    /// Get the last-used file path.
+    pub fn path() -> Option<PathBuf> {
+        data_dir().map(|dir| dir.join("last_used.json"))
+    }
+
+    /// Load last-used settings, if any were saved.
+    pub fn load() -> Option<Self> {
+        let content = fs::read_to_string(Self::path()?).ok()?;
+        serde_json::from_str(&content).ok()
+    }
+
+    /// Save last-used settings.
+    pub fn save(&self) -> anyhow::Result<()> {
+        let path =
+            Self::path().ok_or_else(|| anyhow::anyhow!("Could not determine data directory"))?;
+
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        fs::write(&path, serde_json::to_string_pretty(self)?)?;
+
+        Ok(())
+    }
+}
+
 /// Effective configuration after merging config file and CLI args.
 #[derive(Debug, Clone)]
 pub struct EffectiveConfig {
     /// A4 reference frequency.
     pub a4: f32,
-    /// Tolerance in cents.
+    /// Tolerance in cents for the meter's in-tune zone.
     pub tolerance: f32,
+    /// Tolerance in cents for completing a note.
+    pub confirm_tolerance: f32,
     /// Enable beep on lock.
     pub beep: bool,
+    /// Suppress all tone playback.
+    pub quiet: bool,
+    /// Save the session every N notes (0 = only on quit).
+    pub autosave_every: usize,
     /// Use quick tune mode.
     pub quick_mode: bool,
     /// Resume previous session.
     pub resume: bool,
+    /// Effective string counts for this instrument.
+    pub string_layout: StringLayout,
+    /// Note name spelling.
+    pub spelling: NoteSpelling,
+    /// Coaching instruction language.
+    pub language: Language,
+    /// Reading responsiveness.
+    pub response: ResponsePreset,
+    /// What confirming a note does.
+    pub on_confirm: ConfirmAction,
+    /// Keyboard progress view.
+    pub keyboard: KeyboardView,
+    /// Meter scale.
+    pub meter_curve: MeterCurve,
+    /// Keyboard deviation colors.
+    pub key_colors: KeyColors,
+    /// Decimal places on cents readouts (0 to 2).
+    pub cents_precision: u8,
+    /// MIDI note the temperament octave starts from.
+    pub temperament_start: u8,
+    /// Auto-confirm steady readings while profiling.
+    pub auto_profile: bool,
+    /// What confirming a profiled key with no reading does.
+    pub empty_confirm: EmptyConfirm,
+    /// Coach high treble notes by their octave beats.
+    pub octave_beats: bool,
+    /// Keep the listening indicator still.
+    pub reduced_motion: bool,
+    /// Confirm notes with the pitch read during the strike's attack.
+    pub attack_confirm: bool,
+    /// Downsampling factor for detection on mid and high notes (1 = off).
+    pub decimate: u32,
+    /// High-pass cutoff for the microphone in Hz.
+    pub highpass: Option<f32>,
+    /// Mains hum frequency to notch out, in Hz.
+    pub hum_notch: Option<f32>,
+    /// Reference tone gain per register.
+    pub register_gain: RegisterGain,
+    /// Label for the piano being tuned.
+    pub piano: Option<String>,
+    /// Output device for tones, instead of the default.
+    pub output_device: Option<String>,
 }
 
 #[cfg(test)]
@@ -182,13 +662,7 @@ mod tests {
     #[test]
     fn test_merge_with_args_defaults() {
         let config = Config::default();
-        let args = Args {
-            command: None,
-            resume: false,
-            quick: false,
-            a4: None,
-            beep: false,
-        };
+        let args = plain_args();
         let effective = config.merge_with_args(&args);
 
         assert_eq!(effective.a4, 440.0);
@@ -202,25 +676,57 @@ mod tests {
     fn test_merge_with_args_overrides_a4() {
         let config = Config::default();
         let args = Args {
-            command: None,
-            resume: false,
-            quick: false,
             a4: Some(442.0),
-            beep: false,
+            ..plain_args()
         };
         let effective = config.merge_with_args(&args);
         assert_eq!(effective.a4, 442.0);
     }
 
+    #[test]
+    fn test_history_report_flag() {
+        let args = Args::try_parse_from(["pianito", "history", "--report"]).unwrap();
+        assert!(matches!(
+            args.command,
+            Some(Command::History { report: true })
+        ));
+
+        let args = Args::try_parse_from(["pianito", "--piano", "Studio Steinway"]).unwrap();
+        let effective = Config::default().merge_with_args(&args);
+        assert_eq!(effective.piano.as_deref(), Some("Studio Steinway"));
+    }
+
+    #[test]
+    fn test_a4_preset_resolves_by_name() {
+        let args = Args::try_parse_from(["pianito", "--a4-preset", "baroque"]).unwrap();
+        assert_eq!(args.a4_preset, Some(A4Preset::Baroque));
+        let effective = Config::default().merge_with_args(&args);
+        assert_eq!(effective.a4, 415.3);
+
+        let args = Args::try_parse_from(["pianito", "--a4-preset", "442"]).unwrap();
+        assert_eq!(Config::default().merge_with_args(&args).a4, 442.0);
+
+        // A preset beats remembered and configured pitches, but not both flags
+        let last = LastUsed {
+            a4: Some(443.0),
+            ..Default::default()
+        };
+        let args = Args::try_parse_from(["pianito", "--a4-preset", "verdi"]).unwrap();
+        assert_eq!(
+            Config::default().merge_with_last(&args, Some(&last)).a4,
+            432.0
+        );
+        assert!(
+            Args::try_parse_from(["pianito", "--a4", "441", "--a4-preset", "baroque"]).is_err()
+        );
+    }
+
     #[test]
     fn test_merge_with_args_enables_beep() {
         let config = Config::default();
         let args = Args {
-            command: None,
-            resume: false,
-            quick: false,
-            a4: None,
             beep: true,
+            ..plain_args()
         };
         let effective = config.merge_with_args(&args);
         assert!(effective.beep);
@@ -230,11 +736,8 @@ mod tests {
     fn test_merge_with_args_quick_mode_from_arg() {
         let config = Config::default();
         let args = Args {
-            command: None,
-            resume: false,
             quick: true,
-            a4: None,
-            beep: false,
+            ..plain_args()
         };
         let effective = config.merge_with_args(&args);
         assert!(effective.quick_mode);
@@ -242,15 +745,11 @@ mod tests {
 
     #[test]
     fn test_merge_with_args_quick_mode_from_config() {
-        let mut config = Config::default();
-        config.default_mode = "quick".to_string();
-        let args = Args {
-            command: None,
-            resume: false,
-            quick: false,
-            a4: None,
-            beep: false,
+        let config = Config {
+            default_mode: "quick".to_string(),
+            ..Default::default()
         };
+        let args = plain_args();
         let effective = config.merge_with_args(&args);
         assert!(effective.quick_mode);
     }
@@ -259,11 +758,8 @@ mod tests {
     fn test_merge_with_args_resume_flag() {
         let config = Config::default();
         let args = Args {
-            command: None,
             resume: true,
-            quick: false,
-            a4: None,
-            beep: false,
+            ..plain_args()
         };
         let effective = config.merge_with_args(&args);
         assert!(effective.resume);
@@ -271,15 +767,11 @@ mod tests {
 
     #[test]
     fn test_merge_beep_from_config() {
-        let mut config = Config::default();
-        config.beep = true;
-        let args = Args {
-            command: None,
-            resume: false,
-            quick: false,
-            a4: None,
-            beep: false,
+        let config = Config {
+            beep: true,
+            ..Default::default()
         };
+        let args = plain_args();
         let effective = config.merge_with_args(&args);
         assert!(effective.beep); // Config beep is true
     }
@@ -291,6 +783,7 @@ mod tests {
             tolerance: 10.0,
             beep: true,
             default_mode: "quick".to_string(),
+            ..Default::default()
         };
 
         let toml = toml::to_string(&config).expect("Should serialize");
@@ -348,6 +841,7 @@ mod tests {
             tolerance: 10.0,
             beep: true,
             default_mode: "quick".to_string(),
+            ..Default::default()
         };
 
         // Save to temp file
@@ -364,6 +858,399 @@ mod tests {
         assert_eq!(loaded.default_mode, "quick");
     }
 
+    #[test]
+    fn test_strings_override_parsing() {
+        let toml = r#"
+            [strings]
+            mono = "21-27"
+            bi = "28-39"
+        "#;
+
+        let config: Config = toml::from_str(toml).expect("Should deserialize");
+        assert_eq!(config.strings.mono.as_deref(), Some("21-27"));
+        assert_eq!(config.strings.bi.as_deref(), Some("28-39"));
+        assert!(config.strings.tri.is_none());
+
+        let layout = config.strings.layout();
+        let note = crate::tuning::notes::Note::from_midi(30).unwrap(); // F#1
+        assert_eq!(note.strings, 1);
+        assert_eq!(layout.strings_for(note), 2);
+    }
+
+    #[test]
+    fn test_strings_invalid_range_ignored() {
+        let strings = StringsConfig {
+            mono: Some("nonsense".to_string()),
+            ..Default::default()
+        };
+        assert!(strings.layout().is_standard());
+    }
+
+    #[test]
+    fn test_merge_with_args_string_layout() {
+        let config = Config {
+            strings: StringsConfig {
+                bi: Some("28-39".to_string()),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let args = plain_args();
+        let effective = config.merge_with_args(&args);
+        assert!(!effective.string_layout.is_standard());
+    }
+
+    #[test]
+    fn test_spelling_from_config_and_args() {
+        let config: Config = toml::from_str(r#"spelling = "flats""#).expect("Should deserialize");
+        assert_eq!(config.spelling, NoteSpelling::Flats);
+
+        let mut args = plain_args();
+        assert_eq!(config.merge_with_args(&args).spelling, NoteSpelling::Flats);
+
+        args.spelling = Some(NoteSpelling::Both);
+        assert_eq!(config.merge_with_args(&args).spelling, NoteSpelling::Both);
+    }
+
+    fn plain_args() -> Args {
+        Args {
+            command: None,
+            resume: false,
+            quick: false,
+            a4: None,
+            beep: false,
+            spelling: None,
+            language: None,
+            quiet: false,
+            response: None,
+            on_confirm: None,
+            keyboard: None,
+            meter_curve: None,
+            key_colors: None,
+            cents_precision: None,
+            temperament_start: None,
+            piano: None,
+            decimate: None,
+            highpass: None,
+            hum_notch: None,
+            output_device: None,
+            a4_preset: None,
+            auto_profile: false,
+            empty_confirm: None,
+            octave_beats: false,
+            reduced_motion: false,
+            attack_confirm: false,
+            remember: false,
+            use_last: false,
+        }
+    }
+
+    #[test]
+    fn test_last_used_overrides_config() {
+        let config = Config::default();
+        let last = LastUsed {
+            a4: Some(442.0),
+            beep: Some(true),
+            quick_mode: Some(true),
+            spelling: Some(NoteSpelling::Flats),
+        };
+
+        let effective = config.merge_with_last(&plain_args(), Some(&last));
+        assert_eq!(effective.a4, 442.0);
+        assert!(effective.beep);
+        assert!(effective.quick_mode);
+        assert_eq!(effective.spelling, NoteSpelling::Flats);
+    }
+
+    #[test]
+    fn test_explicit_flags_override_last_used() {
+        let config = Config::default();
+        let last = LastUsed {
+            a4: Some(442.0),
+            spelling: Some(NoteSpelling::Flats),
+            ..Default::default()
+        };
+        let args = Args {
+            a4: Some(415.0),
+            spelling: Some(NoteSpelling::Both),
+            ..plain_args()
+        };
+
+        let effective = config.merge_with_last(&args, Some(&last));
+        assert_eq!(effective.a4, 415.0);
+        assert_eq!(effective.spelling, NoteSpelling::Both);
+    }
+
+    #[test]
+    fn test_partial_last_used_falls_back_to_config() {
+        let config = Config {
+            a4: 441.0,
+            default_mode: "quick".to_string(),
+            ..Default::default()
+        };
+        let last: LastUsed = serde_json::from_str(r#"{"beep": true}"#).expect("Should parse");
+
+        let effective = config.merge_with_last(&plain_args(), Some(&last));
+        assert_eq!(effective.a4, 441.0);
+        assert!(effective.beep);
+        assert!(effective.quick_mode);
+    }
+
+    #[test]
+    fn test_last_used_roundtrip_from_effective() {
+        let effective = Config::default().merge_with_args(&Args {
+            a4: Some(442.0),
+            ..plain_args()
+        });
+        let last = LastUsed::from_effective(&effective);
+
+        let json = serde_json::to_string(&last).expect("Should serialize");
+        let restored: LastUsed = serde_json::from_str(&json).expect("Should deserialize");
+        assert_eq!(restored, last);
+        assert_eq!(restored.a4, Some(442.0));
+    }
+
+    #[test]
+    fn test_confirm_tolerance_defaults_to_tolerance() {
+        let config: Config = toml::from_str("tolerance = 4.0").unwrap();
+        let effective = config.merge_with_args(&plain_args());
+        assert_eq!(effective.tolerance, 4.0);
+        assert_eq!(effective.confirm_tolerance, 4.0);
+    }
+
+    #[test]
+    fn test_confirm_tolerance_separate() {
+        let config: Config = toml::from_str("tolerance = 5.0\nconfirm_tolerance = 2.0").unwrap();
+        let effective = config.merge_with_args(&plain_args());
+        assert_eq!(effective.tolerance, 5.0);
+        assert_eq!(effective.confirm_tolerance, 2.0);
+    }
+
+    #[test]
+    fn test_autosave_every() {
+        assert_eq!(Config::default().autosave_every, 1);
+
+        let config: Config = toml::from_str("autosave_every = 10").unwrap();
+        assert_eq!(config.merge_with_args(&plain_args()).autosave_every, 10);
+    }
+
+    #[test]
+    fn test_volume_section() {
+        let config: Config = toml::from_str("[volume]\nbass = 2.5").unwrap();
+        let gain = config.merge_with_args(&plain_args()).register_gain;
+        assert_eq!(gain.bass, 2.5);
+        assert_eq!(gain.mid, 1.0);
+        assert_eq!(gain.treble, 1.0);
+    }
+
+    #[test]
+    fn test_language_from_config_and_args() {
+        let config: Config = toml::from_str(r#"language = "spanish""#).unwrap();
+        assert_eq!(
+            config.merge_with_args(&plain_args()).language,
+            Language::Spanish
+        );
+
+        let args = Args {
+            language: Some(Language::English),
+            ..plain_args()
+        };
+        assert_eq!(config.merge_with_args(&args).language, Language::English);
+    }
+
+    #[test]
+    fn test_quiet_from_config_or_flag() {
+        let config: Config = toml::from_str("quiet = true").unwrap();
+        assert!(config.merge_with_args(&plain_args()).quiet);
+
+        let args = Args {
+            quiet: true,
+            ..plain_args()
+        };
+        assert!(!Config::default().merge_with_args(&plain_args()).quiet);
+        assert!(Config::default().merge_with_args(&args).quiet);
+    }
+
+    #[test]
+    fn test_response_from_config_and_flag() {
+        let config: Config = toml::from_str(r#"response = "stable""#).unwrap();
+        assert_eq!(
+            config.merge_with_args(&plain_args()).response,
+            ResponsePreset::Stable
+        );
+
+        let args = Args::try_parse_from(["pianito", "--response", "fast"]).unwrap();
+        assert_eq!(config.merge_with_args(&args).response, ResponsePreset::Fast);
+    }
+
+    #[test]
+    fn test_on_confirm_from_config_and_flag() {
+        assert_eq!(
+            Config::default().merge_with_args(&plain_args()).on_confirm,
+            ConfirmAction::Advance
+        );
+
+        let config: Config = toml::from_str(r#"on_confirm = "stay""#).unwrap();
+        assert_eq!(
+            config.merge_with_args(&plain_args()).on_confirm,
+            ConfirmAction::Stay
+        );
+
+        let args = Args::try_parse_from(["pianito", "--on-confirm", "advance"]).unwrap();
+        assert_eq!(
+            config.merge_with_args(&args).on_confirm,
+            ConfirmAction::Advance
+        );
+    }
+
+    #[test]
+    fn test_keyboard_view_from_config_and_flag() {
+        let config: Config = toml::from_str(r#"keyboard = "order""#).unwrap();
+        assert_eq!(
+            config.merge_with_args(&plain_args()).keyboard,
+            KeyboardView::Order
+        );
+
+        let args = Args::try_parse_from(["pianito", "--keyboard", "chromatic"]).unwrap();
+        assert_eq!(
+            config.merge_with_args(&args).keyboard,
+            KeyboardView::Chromatic
+        );
+    }
+
+    #[test]
+    fn test_mic_filters_reject_out_of_range_values() {
+        let args =
+            Args::try_parse_from(["pianito", "--highpass", "20", "--hum-notch", "50"]).unwrap();
+        assert_eq!(args.highpass, Some(20.0));
+        assert_eq!(args.hum_notch, Some(50.0));
+
+        for bad in [
+            ["--highpass", "-5"],
+            ["--highpass", "0"],
+            ["--highpass", "500"],
+            ["--hum-notch", "120"],
+            ["--hum-notch", "hum"],
+        ] {
+            let parsed = Args::try_parse_from(["pianito", bad[0], bad[1]]);
+            assert!(parsed.is_err(), "{bad:?} should be rejected");
+        }
+
+        // Out-of-range config values are left off
+        let config: Config = toml::from_str("highpass = 500.0\nhum_notch = 60.0").unwrap();
+        let effective = config.merge_with_args(&plain_args());
+        assert_eq!(effective.highpass, None);
+        assert_eq!(effective.hum_notch, Some(60.0));
+    }
+
+    #[test]
+    fn test_key_colors_from_config_and_flag() {
+        assert_eq!(
+            Config::default().merge_with_args(&plain_args()).key_colors,
+            KeyColors::Fixed
+        );
+
+        let config: Config = toml::from_str(r#"key_colors = "bands""#).unwrap();
+        assert_eq!(
+            config.merge_with_args(&plain_args()).key_colors,
+            KeyColors::Bands
+        );
+
+        let args = Args::try_parse_from(["pianito", "--key-colors", "pass"]).unwrap();
+        assert_eq!(config.merge_with_args(&args).key_colors, KeyColors::Pass);
+    }
+
+    #[test]
+    fn test_meter_curve_from_config_and_flag() {
+        let config: Config = toml::from_str(r#"meter_curve = "expanded""#).unwrap();
+        assert_eq!(
+            config.merge_with_args(&plain_args()).meter_curve,
+            MeterCurve::Expanded
+        );
+
+        let args = Args::try_parse_from(["pianito", "--meter-curve", "linear"]).unwrap();
+        assert_eq!(
+            config.merge_with_args(&args).meter_curve,
+            MeterCurve::Linear
+        );
+    }
+
+    #[test]
+    fn test_cents_precision_from_config_and_flag() {
+        assert_eq!(
+            Config::default()
+                .merge_with_args(&plain_args())
+                .cents_precision,
+            1
+        );
+
+        let config: Config = toml::from_str("cents_precision = 2").unwrap();
+        assert_eq!(config.merge_with_args(&plain_args()).cents_precision, 2);
+
+        let args = Args::try_parse_from(["pianito", "--cents-precision", "0"]).unwrap();
+        assert_eq!(config.merge_with_args(&args).cents_precision, 0);
+        assert!(Args::try_parse_from(["pianito", "--cents-precision", "3"]).is_err());
+    }
+
+    #[test]
+    fn test_temperament_start_from_config_and_flag() {
+        assert_eq!(
+            Config::default()
+                .merge_with_args(&plain_args())
+                .temperament_start,
+            53
+        );
+
+        let config: Config = toml::from_str("temperament_start = \"C4\"").unwrap();
+        assert_eq!(config.merge_with_args(&plain_args()).temperament_start, 60);
+
+        let args = Args::try_parse_from(["pianito", "--temperament-start", "A3"]).unwrap();
+        assert_eq!(args.temperament_start, Some(57));
+        assert_eq!(config.merge_with_args(&args).temperament_start, 57);
+
+        assert!(Args::try_parse_from(["pianito", "--temperament-start", "A4"]).is_err());
+        assert!(Args::try_parse_from(["pianito", "--temperament-start", "H3"]).is_err());
+    }
+
+    #[test]
+    fn test_analyze_takes_one_file_or_a_pair() {
+        let args = Args::try_parse_from(["pianito", "analyze", "a.wav"]).unwrap();
+        assert!(matches!(
+            args.command,
+            Some(Command::Analyze {
+                file: Some(_),
+                before: None,
+                after: None
+            })
+        ));
+
+        let args = Args::try_parse_from([
+            "pianito", "analyze", "--before", "a.wav", "--after", "b.wav",
+        ])
+        .unwrap();
+        assert!(matches!(
+            args.command,
+            Some(Command::Analyze {
+                file: None,
+                before: Some(_),
+                after: Some(_)
+            })
+        ));
+
+        assert!(Args::try_parse_from(["pianito", "analyze"]).is_err());
+        assert!(Args::try_parse_from(["pianito", "analyze", "--before", "a.wav"]).is_err());
+    }
+
+    #[test]
+    fn test_tune_subcommand_parses_note() {
+        let args = Args::try_parse_from(["pianito", "--a4", "442", "tune", "C#5"]).unwrap();
+        assert_eq!(args.a4, Some(442.0));
+        match args.command {
+            Some(Command::Tune { note }) => assert_eq!(note, "C#5"),
+            other => panic!("Expected tune command, got {:?}", other),
+        }
+    }
+
     #[test]
     fn test_invalid_toml_falls_back_to_default() {
         let invalid_toml = "this is not valid toml {{{}";
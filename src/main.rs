@@ -2,28 +2,82 @@
 //!
 //! A terminal-based piano tuning application with guided coaching.
 
-use std::time::Duration;
+use std::cell::Cell;
+use std::time::{Duration, Instant};
 
 use clap::Parser;
 
-use pianito::audio::{AudioOutput, AudioSource, MicCapture, PitchDetector, WavAudioSource};
-use pianito::config::{Args, Command, Config};
+use pianito::audio::{
+    capture, decimate, default_input_info, gate, input_device_names, pitch, AudioOutput, AudioSink,
+    AudioSource, CaptureError, CaptureStats, DoctorReport, MicCapture, PitchDetector,
+    ReferenceTone, SignalGate, WavAudioSource, MIN_DETECT_CONFIDENCE,
+};
+use pianito::config::{Args, Command, Config, LastUsed};
+use pianito::tuning::intervals;
 use pianito::tuning::notes::Note;
+use pianito::tuning::order::TuningOrder;
+use pianito::tuning::practice::TemperamentPractice;
+use pianito::tuning::recording;
 use pianito::tuning::session::Session;
+use pianito::tuning::stretch::StretchCurve;
+use pianito::tuning::targets::TargetTable;
 use pianito::tuning::temperament::Temperament;
 use pianito::ui::{self, App};
 
+/// Length of each chunk queued for a looping reference tone, in seconds.
+const LOOP_CHUNK_SECS: f32 = 0.5;
+/// Queue another chunk once less than this much is left, in seconds.
+const LOOP_REFILL_SECS: f32 = 0.2;
+/// Output level for swept tones, matching `AudioOutput::play_sine`.
+const SWEEP_LEVEL: f32 = 0.3;
+
 fn main() -> anyhow::Result<()> {
     let args = Args::parse();
     let config = Config::load();
-    let effective = config.merge_with_args(&args);
+    let last_used = if args.use_last {
+        LastUsed::load()
+    } else {
+        None
+    };
+    let effective = config.merge_with_last(&args, last_used.as_ref());
+
+    if args.remember {
+        if let Err(e) = LastUsed::from_effective(&effective).save() {
+            eprintln!("Warning: could not remember settings: {}", e);
+        }
+    }
 
     match args.command {
-        Some(Command::Analyze { file }) => analyze_file(&file)?,
-        Some(Command::Reference { note, duration }) => play_reference(&note, duration)?,
-        Some(Command::History) => show_history()?,
+        Some(Command::Analyze {
+            before: Some(before),
+            after: Some(after),
+            ..
+        }) => compare_recordings(&before, &after)?,
+        Some(Command::Analyze { file, .. }) => {
+            let file = file.ok_or_else(|| anyhow::anyhow!("No recording given"))?;
+            analyze_file(&file)?
+        }
+        Some(Command::Reference { .. } | Command::Sweep { .. }) if effective.quiet => {
+            println!("Quiet mode is on; not playing a reference tone.")
+        }
+        Some(Command::Reference { note, duration }) => play_reference(&effective, &note, duration)?,
+        Some(Command::Sweep {
+            duration,
+            gap,
+            stretch,
+        }) => sweep_reference(&effective, duration, gap, stretch)?,
+        Some(Command::Tune { note }) => {
+            let note =
+                Note::from_name(&note).ok_or_else(|| anyhow::anyhow!("Unknown note: {}", note))?;
+            run_interactive(effective, Launch::SingleNote(note))?
+        }
+        Some(Command::Practice) => run_interactive(effective, Launch::TemperamentPractice)?,
+        Some(Command::Intervals) => show_intervals(effective.a4),
+        Some(Command::Doctor { seconds }) => run_doctor(seconds),
+        Some(Command::Loopback { freq, seconds }) => run_loopback(&effective, freq, seconds)?,
+        Some(Command::History { report }) => show_history(report)?,
         Some(Command::Reset) => reset_sessions()?,
-        None => run_interactive(effective)?,
+        None => run_interactive(effective, Launch::Menu)?,
     }
 
     Ok(())
@@ -100,8 +154,107 @@ fn analyze_file(path: &str) -> anyhow::Result<()> {
     Ok(())
 }
 
+/// Compare the notes in recordings made before and after tuning.
+fn compare_recordings(before: &str, after: &str) -> anyhow::Result<()> {
+    let temperament = Temperament::new();
+    let segments = |path: &str| -> anyhow::Result<_> {
+        let mut source = WavAudioSource::new(std::fs::File::open(path)?)?;
+        Ok(recording::segment_notes(&mut source, &temperament))
+    };
+
+    println!("Comparing {} with {}...", before, after);
+    let before = segments(before)?;
+    let after = segments(after)?;
+    if before.len() != after.len() {
+        println!(
+            "Warning: found {} notes before and {} after; comparing in order.",
+            before.len(),
+            after.len()
+        );
+    }
+
+    let changes = recording::compare_segments(&before, &after);
+    if changes.is_empty() {
+        println!("No matching notes found in both recordings.");
+        return Ok(());
+    }
+
+    println!(
+        "\n{:<8} {:>10} {:>10} {:>10}",
+        "Note", "Before", "After", "Change"
+    );
+    println!("{}", "-".repeat(41));
+    for change in &changes {
+        let name = Note::from_midi(change.midi)
+            .map(|n| n.display_name())
+            .unwrap_or_default();
+        println!(
+            "{:<8} {:>+10.1} {:>+10.1} {:>+10.1}",
+            name,
+            change.before_cents,
+            change.after_cents,
+            change.delta()
+        );
+    }
+
+    if let Some(average) = recording::average_improvement(&changes) {
+        println!(
+            "\nAverage improvement: {:+.1} cents over {} notes",
+            average,
+            changes.len()
+        );
+    }
+    Ok(())
+}
+
+/// Open the configured output device, or the default one.
+fn open_output(config: &pianito::config::EffectiveConfig) -> Result<AudioOutput, CaptureError> {
+    match &config.output_device {
+        Some(name) => AudioOutput::with_device(name),
+        None => AudioOutput::new(),
+    }
+}
+
+/// Open the microphone with the configured rumble and hum filters.
+fn open_mic(config: &pianito::config::EffectiveConfig) -> Result<MicCapture, CaptureError> {
+    let mut mic = MicCapture::new()?;
+    if let Some(cutoff) = config.highpass {
+        mic = mic.with_highpass(cutoff);
+    }
+    if let Some(mains) = config.hum_notch {
+        mic = mic.with_hum_notch(mains);
+    }
+    Ok(mic)
+}
+
+/// Run or pause the microphone and output, keeping the output running
+/// while it still has tones queued.
+fn set_devices_running(
+    mic: &mut MicCapture,
+    output: Option<&mut AudioOutput>,
+    running: bool,
+) -> Result<(), CaptureError> {
+    if running {
+        mic.resume()?;
+    } else {
+        mic.pause()?;
+    }
+    if let Some(output) = output {
+        if running || output.queued_secs() > 0.0 {
+            output.resume()?;
+        } else {
+            output.pause()?;
+        }
+    }
+    Ok(())
+}
+
 /// Play a reference tone for a given note.
-fn play_reference(note_name: &str, duration: f32) -> anyhow::Result<()> {
+fn play_reference(
+    config: &pianito::config::EffectiveConfig,
+    note_name: &str,
+    duration: f32,
+) -> anyhow::Result<()> {
     let note =
         Note::from_name(note_name).ok_or_else(|| anyhow::anyhow!("Unknown note: {}", note_name))?;
 
@@ -115,7 +268,7 @@ fn play_reference(note_name: &str, duration: f32) -> anyhow::Result<()> {
         duration
     );
 
-    let output = AudioOutput::new()?;
+    let output = open_output(config)?.with_gain(config.register_gain);
     output.play_sine(frequency, duration)?;
 
     // Wait for playback to complete
@@ -125,8 +278,170 @@ fn play_reference(note_name: &str, duration: f32) -> anyhow::Result<()> {
     Ok(())
 }
 
-/// Show tuning session history.
-fn show_history() -> anyhow::Result<()> {
+/// Output that waits for each write to finish playing, stopping early on a key press.
+struct PacedOutput<'a> {
+    output: &'a AudioOutput,
+    stopped: &'a Cell<bool>,
+}
+
+impl AudioSink for PacedOutput<'_> {
+    fn write_samples(&mut self, samples: &[f32]) {
+        let scaled: Vec<f32> = samples.iter().map(|s| s * SWEEP_LEVEL).collect();
+        self.output.queue(&scaled);
+
+        while self.output.queued_secs() > 0.0 && !self.stopped.get() {
+            let pressed = ui::poll_event(Duration::from_millis(10))
+                .ok()
+                .flatten()
+                .and_then(|event| ui::is_key_press(&event));
+            if pressed.is_some() {
+                self.stopped.set(true);
+                self.output.clear();
+            }
+        }
+    }
+
+    fn sample_rate(&self) -> u32 {
+        self.output.sample_rate()
+    }
+}
+
+/// Play every target in tuning order, until done or a key is pressed.
+fn sweep_reference(
+    config: &pianito::config::EffectiveConfig,
+    duration: f32,
+    gap: f32,
+    stretch: bool,
+) -> anyhow::Result<()> {
+    let temperament = Temperament::with_a4(config.a4);
+    let curve = stretch.then(StretchCurve::new);
+    let table = TargetTable::build(&temperament, curve.as_ref(), 0.0);
+    let targets = TuningOrder::new().target_frequencies(&table);
+    let frequencies: Vec<f32> = targets.iter().map(|&(_, freq)| freq).collect();
+
+    let output = open_output(config)?;
+    let tone = ReferenceTone::new(output.sample_rate()).with_gain(config.register_gain);
+    let stopped = Cell::new(false);
+    let mut sink = PacedOutput {
+        output: &output,
+        stopped: &stopped,
+    };
+
+    println!(
+        "Sweeping {} notes (A4 = {:.1} Hz). Press any key to stop.",
+        targets.len(),
+        config.a4
+    );
+
+    // Raw mode so a single key press stops the sweep
+    crossterm::terminal::enable_raw_mode()?;
+    let played = tone.sweep(&mut sink, &frequencies, duration, gap, |i| {
+        if !stopped.get() {
+            let (note, freq) = targets[i];
+            print!("{:<4} {:>8.2} Hz\r\n", note.display_name(), freq);
+            let _ = std::io::Write::flush(&mut std::io::stdout());
+        }
+        stopped.get()
+    });
+    crossterm::terminal::disable_raw_mode()?;
+
+    if stopped.get() {
+        println!("Stopped after {} of {} notes.", played, targets.len());
+    } else {
+        println!("Done.");
+    }
+    Ok(())
+}
+
+/// Capture from the default microphone and print a detection report.
+fn run_doctor(seconds: f32) {
+    let mut report = DoctorReport {
+        devices: input_device_names(),
+        ..DoctorReport::default()
+    };
+
+    match default_input_info() {
+        Ok(info) => report.input = Some(info),
+        Err(e) => report.error = Some(e.to_string()),
+    }
+
+    if report.input.is_some() {
+        match MicCapture::new() {
+            Ok(mut mic) => {
+                println!("Listening for {:.1} s; play a note...", seconds);
+                report.stats = Some(capture_stats(&mut mic, seconds));
+            }
+            Err(e) => report.error = Some(e.to_string()),
+        }
+    }
+
+    println!("{}", report);
+}
+
+/// Play an exact tone through the speakers while listening to it.
+///
+/// Another tuner should read the tone as exact; pianito should too, within
+/// a cent or so, once the tone reaches the microphone.
+fn run_loopback(
+    config: &pianito::config::EffectiveConfig,
+    frequency: f32,
+    seconds: f32,
+) -> anyhow::Result<()> {
+    let output = open_output(config)?;
+    let mut mic = open_mic(config)?;
+
+    println!(
+        "Playing exactly {:.3} Hz for {:.1}s. Check it with another tuner.",
+        frequency, seconds
+    );
+    output.play_sine(frequency, seconds)?;
+    let stats = capture_stats(&mut mic, seconds);
+
+    match (stats.detected, stats.cents_from(frequency)) {
+        (Some(detected), Some(cents)) => {
+            println!("pianito heard {:.3} Hz ({:+.1} cents)", detected, cents);
+        }
+        _ => println!(
+            "pianito heard no steady pitch. Turn up the volume or move the microphone closer."
+        ),
+    }
+    Ok(())
+}
+
+/// Read from `source` for `seconds`, analysing each new block.
+fn capture_stats(source: &mut impl AudioSource, seconds: f32) -> CaptureStats {
+    let detector = PitchDetector::new(source.sample_rate());
+    let mut buffer = vec![0.0f32; source.sample_rate() as usize / 10]; // 100ms buffer
+    let mut stats = CaptureStats::default();
+
+    let start = Instant::now();
+    while start.elapsed().as_secs_f32() < seconds {
+        let read = source.read_samples(&mut buffer);
+        if read > 0 && !detector.is_warming_up(read) {
+            let samples = &buffer[..read];
+            stats.record(samples, detector.detect(samples).as_ref());
+        }
+        std::thread::sleep(Duration::from_millis(100));
+    }
+    stats.seconds = start.elapsed().as_secs_f32();
+    stats
+}
+
+/// Show beat rates of the temperament octave check intervals.
+fn show_intervals(a4: f32) {
+    let temperament = Temperament::with_a4(a4);
+
+    println!("Temperament checks (A4 = {:.1} Hz):", a4);
+    println!("{:<14} {:>12}", "Interval", "Beats/sec");
+    println!("{}", "-".repeat(27));
+
+    for check in intervals::temperament_checks(&temperament, intervals::TEMPERAMENT_LOW_MIDI) {
+        println!("{:<14} {:>12.2}", check.label(), check.beats_per_second);
+    }
+}
+
+/// Show tuning session history, or a full report of the latest session.
+fn show_history(report: bool) -> anyhow::Result<()> {
     let sessions = Session::list_all()?;
 
     if sessions.is_empty() {
@@ -134,6 +449,11 @@ fn show_history() -> anyhow::Result<()> {
         return Ok(());
     }
 
+    if report {
+        print!("{}", sessions[0].report_string());
+        return Ok(());
+    }
+
     println!("Tuning History:");
     println!(
         "{:<24} {:<10} {:<12} {:<10}",
@@ -151,6 +471,11 @@ fn show_history() -> anyhow::Result<()> {
             "{:<24} {:<10} {:<12} {:<10}",
             date, mode, progress, avg_cents
         );
+        for note in &session.completed_notes {
+            if let Some(comment) = &note.comment {
+                println!("    {}: {}", note.note, comment);
+            }
+        }
     }
 
     Ok(())
@@ -175,10 +500,73 @@ fn reset_sessions() -> anyhow::Result<()> {
     Ok(())
 }
 
+/// Pick which incomplete session to resume, asking when there are several.
+fn choose_session(mut sessions: Vec<Session>) -> anyhow::Result<Option<Session>> {
+    if sessions.len() < 2 {
+        return Ok(sessions.pop());
+    }
+
+    println!("Incomplete sessions:");
+    for (i, session) in sessions.iter().enumerate() {
+        println!("  {}. {}", i + 1, session.summary());
+    }
+    use std::io::{self, Write};
+    loop {
+        print!("Resume which session? [1] ");
+        io::stdout().flush()?;
+
+        // Enter (or end of input) resumes the newest
+        let mut input = String::new();
+        io::stdin().read_line(&mut input)?;
+        let input = input.trim();
+        if input.is_empty() {
+            return Ok(Some(sessions.swap_remove(0)));
+        }
+        match input.parse::<usize>() {
+            Ok(n) if (1..=sessions.len()).contains(&n) => {
+                return Ok(Some(sessions.swap_remove(n - 1)));
+            }
+            _ => println!("Enter a number from 1 to {}.", sessions.len()),
+        }
+    }
+}
+
+/// What the interactive application starts with.
+enum Launch {
+    /// Mode selection, or a resumed session.
+    Menu,
+    /// Tune one note, then exit.
+    SingleNote(&'static Note),
+    /// Loop the temperament octave with scoring.
+    TemperamentPractice,
+}
+
+/// Print the per-interval accuracy of a temperament practice.
+fn show_practice_report(practice: &TemperamentPractice) {
+    println!("Temperament practice: {} passes", practice.passes());
+
+    let report = practice.report();
+    if report.is_empty() {
+        println!("No intervals completed.");
+        return;
+    }
+
+    println!("{:<10} {:>8} {:>14}", "Interval", "Checks", "Beat error/s");
+    println!("{}", "-".repeat(34));
+    for accuracy in report {
+        println!(
+            "{:<10} {:>8} {:>14.2}",
+            accuracy.interval.label(),
+            accuracy.checks,
+            accuracy.average_error_bps
+        );
+    }
+}
+
 /// Run the interactive tuning application.
-fn run_interactive(config: pianito::config::EffectiveConfig) -> anyhow::Result<()> {
+fn run_interactive(config: pianito::config::EffectiveConfig, launch: Launch) -> anyhow::Result<()> {
     // Initialize audio capture
-    let mut mic = match MicCapture::new() {
+    let mut mic = match open_mic(&config) {
         Ok(m) => m,
         Err(e) => {
             eprintln!("Error: Could not access microphone: {}", e);
@@ -189,10 +577,17 @@ fn run_interactive(config: pianito::config::EffectiveConfig) -> anyhow::Result<(
 
     let sample_rate = mic.sample_rate();
     let detector = PitchDetector::new(sample_rate);
+    let decimation = config.decimate as usize;
+    let decimated_detector = PitchDetector::new(sample_rate / config.decimate);
+    let mut gate = SignalGate::new();
 
     // Create or resume app
-    let mut app = if config.resume {
-        match Session::load_recent()? {
+    let app = if let Launch::SingleNote(note) = launch {
+        App::single_note(note, config.a4)
+    } else if let Launch::TemperamentPractice = launch {
+        App::temperament_practice(config.a4)
+    } else if config.resume {
+        match choose_session(Session::list_incomplete()?)? {
             Some(session) => {
                 println!(
                     "Resuming session from {}...",
@@ -204,50 +599,185 @@ fn run_interactive(config: pianito::config::EffectiveConfig) -> anyhow::Result<(
             None => {
                 println!("No incomplete session found. Starting new session.");
                 std::thread::sleep(Duration::from_millis(500));
-                App::new()
+                App::new().with_a4(config.a4)
             }
         }
     } else {
-        App::new()
+        App::new().with_a4(config.a4)
+    };
+    let mut app = app
+        .with_string_layout(config.string_layout.clone())
+        .with_spelling(config.spelling)
+        .with_language(config.language)
+        .with_response(config.response)
+        .with_confirm_action(config.on_confirm)
+        .with_keyboard_view(config.keyboard)
+        .with_meter_curve(config.meter_curve)
+        .with_key_colors(config.key_colors)
+        .with_cents_precision(config.cents_precision)
+        .with_temperament_start(config.temperament_start)
+        .with_auto_profile(config.auto_profile)
+        .with_empty_confirm(config.empty_confirm)
+        .with_octave_beats(config.octave_beats)
+        .with_reduced_motion(config.reduced_motion)
+        .with_attack_confirm(config.attack_confirm)
+        .with_key_release(ui::reports_key_release())
+        .with_beep(config.beep)
+        .with_quiet(config.quiet)
+        .with_tolerances(config.tolerance, config.confirm_tolerance)
+        .with_autosave_every(config.autosave_every)
+        .with_piano(config.piano.clone());
+
+    // Output is optional: tuning works without it, only played tones are
+    // lost. A named device that doesn't exist is a mistake worth reporting.
+    let mut output = match open_output(&config) {
+        Ok(output) => Some(output.with_gain(config.register_gain)),
+        Err(e @ CaptureError::OutputDeviceNotFound { .. }) => return Err(e.into()),
+        Err(_) => None,
     };
 
     // Initialize terminal
     let mut terminal = ui::init()?;
 
     // Main loop
-    let mut audio_buffer = vec![0.0f32; sample_rate as usize / 10]; // 100ms buffer
+    let mut looping = false;
+    let mut audio_buffer = vec![0.0f32; detector.max_window()];
+    let mut last_read = Instant::now();
 
     let result = loop {
-        // Read audio and detect pitch
-        let read = mic.read_samples(&mut audio_buffer);
+        // Read a window sized to the note's register, 100ms without a target
+        let window = app
+            .current_target_freq()
+            .map_or(sample_rate as usize / 10, |hz| detector.window_for_freq(hz));
+        let read = mic.read_samples(&mut audio_buffer[..window]);
+        let captured_at = mic.newest_sample_at();
+        // A stalled input delivers nothing, which counts as silence too
+        let level = if read > 0 {
+            gate::rms(&audio_buffer[..read])
+        } else {
+            0.0
+        };
+        app.update_input_level(level, last_read.elapsed().as_secs_f32());
+        last_read = Instant::now();
         if read > 0 {
-            if let Some(pitch_result) = detector.detect(&audio_buffer[..read]) {
+            // Show "warming up" rather than noise until a full window arrives
+            let warming_up = read < window && detector.is_warming_up(read);
+            app.set_warming_up(warming_up);
+            app.set_clipping(mic.is_clipping());
+            // Only detect while a note is sounding, so its decaying tail
+            // doesn't flicker between a reading and "listening"
+            let sounding = gate.update(&audio_buffer[..read]);
+            if warming_up || !sounding {
+                app.clear_pitch();
+            } else if let Some(pitch_result) = {
+                // Mid and high notes may detect on downsampled audio
+                let factor = app
+                    .current_target_freq()
+                    .map_or(1, |hz| decimate::factor_for(hz, sample_rate, decimation));
+                if factor > 1 {
+                    decimated_detector.estimate(&decimate::decimate(&audio_buffer[..read], factor))
+                } else {
+                    detector.estimate(&audio_buffer[..read])
+                }
+            } {
+                // Unsure readings still reach the app, which shows them as
+                // a tagged estimate, but can't be trusted for warnings
+                let confident = pitch_result.confidence > MIN_DETECT_CONFIDENCE;
                 app.update_pitch(pitch_result.frequency, pitch_result.confidence);
+                app.set_latency_ms(captured_at.map(|at| capture::latency_ms(at, Instant::now())));
+                // The octave check needs a spectrum, so skip it unless shown
+                let octave_ambiguous = confident
+                    && app.shows_octave_warning()
+                    && pitch::octave_ambiguity(
+                        &audio_buffer[..read],
+                        sample_rate,
+                        pitch_result.frequency,
+                    );
+                app.set_octave_warning(octave_ambiguous);
+                app.update_false_beats(
+                    &audio_buffer[..read],
+                    mic.fresh_samples(),
+                    sample_rate,
+                    confident,
+                );
             } else {
                 app.clear_pitch();
             }
         }
 
         // Render UI
+        app.tick();
         terminal.draw(|frame| {
             app.render(frame);
         })?;
 
         // Handle input (non-blocking)
         if let Some(event) = ui::poll_event(Duration::from_millis(50))? {
+            if let Some(key) = ui::is_key_release(&event) {
+                app.handle_key_release(key);
+            }
             if let Some(key) = ui::is_key_press(&event) {
+                let previous_target = app.current_target_freq();
                 app.handle_key(key);
+
+                // Don't let the previous note's audio leak into the new one
+                if app.current_target_freq() != previous_target {
+                    mic.flush();
+                    if let Some(output) = &output {
+                        output.clear();
+                    }
+                }
+            }
+        }
+
+        // Play any tones the app queued
+        for tone in app.take_tone_requests() {
+            if let Some(output) = &output {
+                let _ = output.play_sine(tone.frequency, tone.duration);
             }
         }
 
+        // Keep a looping reference topped up, and silence it when it stops
+        if let Some(output) = &output {
+            match app.looping_tone() {
+                Some(freq) if output.queued_secs() < LOOP_REFILL_SECS => {
+                    // Whole cycles so consecutive chunks join without a click
+                    let cycles = (LOOP_CHUNK_SECS * freq).ceil();
+                    let _ = output.play_sine(freq, cycles / freq);
+                }
+                Some(_) => {}
+                None if looping => output.clear(),
+                None => {}
+            }
+        }
+        looping = app.looping_tone().is_some();
+
+        // Release audio devices on screens that don't use them, letting
+        // queued tones such as the completion fanfare finish first
+        let needs_audio = app.state().needs_audio();
+        if let Err(e) = set_devices_running(&mut mic, output.as_mut(), needs_audio) {
+            // Leave through the cleanup below so the terminal is restored
+            break Err(e.into());
+        }
+
         // Check for quit
         if app.should_quit() {
             break Ok(());
         }
     };
 
+    // Stop streams before the terminal is restored
+    let _ = mic.pause();
+    if let Some(output) = &mut output {
+        let _ = output.pause();
+    }
+
     // Restore terminal
     ui::restore()?;
 
+    if let Some(practice) = app.practice() {
+        show_practice_report(practice);
+    }
+
     result
 }
@@ -0,0 +1,87 @@
+//! Crash-safe file writes.
+
+use std::fs;
+use std::io::{BufWriter, Write};
+use std::path::{Path, PathBuf};
+
+/// Write a file by filling a sibling temp file and renaming it into place.
+///
+/// If `write` fails, or the process dies part way, the file at `path` is
+/// left as it was and the temp file is removed.
+pub(crate) fn write_atomic(
+    path: &Path,
+    write: impl FnOnce(&mut BufWriter<fs::File>) -> anyhow::Result<()>,
+) -> anyhow::Result<()> {
+    let temp = temp_path(path);
+    let result = fill(&temp, write).and_then(|()| Ok(fs::rename(&temp, path)?));
+    if result.is_err() {
+        let _ = fs::remove_file(&temp);
+    }
+    result
+}
+
+/// Write and sync the temp file so the rename never exposes partial data.
+fn fill(
+    temp: &Path,
+    write: impl FnOnce(&mut BufWriter<fs::File>) -> anyhow::Result<()>,
+) -> anyhow::Result<()> {
+    let mut writer = BufWriter::new(fs::File::create(temp)?);
+    write(&mut writer)?;
+    writer.flush()?;
+    writer.get_ref().sync_all()?;
+    Ok(())
+}
+
+/// Hidden temp file next to `path`, so the rename stays on one filesystem.
+fn temp_path(path: &Path) -> PathBuf {
+    let name = path
+        .file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    path.with_file_name(format!(".{name}.tmp"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_write_replaces_file() {
+        let dir = TempDir::new().expect("temp dir");
+        let path = dir.path().join("data.json");
+        fs::write(&path, "old").expect("seed file");
+
+        write_atomic(&path, |w| Ok(w.write_all(b"new")?)).expect("write");
+
+        assert_eq!(fs::read_to_string(&path).expect("read"), "new");
+        assert!(!temp_path(&path).exists());
+    }
+
+    #[test]
+    fn test_failed_write_leaves_existing_file_intact() {
+        let dir = TempDir::new().expect("temp dir");
+        let path = dir.path().join("data.json");
+        fs::write(&path, "old").expect("seed file");
+
+        let result = write_atomic(&path, |w| {
+            w.write_all(b"{\"half\":")?;
+            anyhow::bail!("disk full")
+        });
+
+        assert!(result.is_err());
+        assert_eq!(fs::read_to_string(&path).expect("read"), "old");
+        assert!(!temp_path(&path).exists(), "temp file cleaned up");
+    }
+
+    #[test]
+    fn test_failed_write_creates_nothing() {
+        let dir = TempDir::new().expect("temp dir");
+        let path = dir.path().join("data.json");
+
+        let result = write_atomic(&path, |_| anyhow::bail!("disk full"));
+
+        assert!(result.is_err());
+        assert_eq!(fs::read_dir(dir.path()).expect("list").count(), 0);
+    }
+}
@@ -0,0 +1,64 @@
+//! Unique IDs for saved files.
+
+use std::sync::atomic::{AtomicU32, Ordering};
+
+use chrono::{DateTime, SecondsFormat, Utc};
+
+/// IDs handed out by this process, so two made in the same instant differ.
+static ID_COUNTER: AtomicU32 = AtomicU32::new(0);
+
+/// Build a unique ID from a creation time.
+///
+/// Nanosecond precision keeps IDs from separate runs apart; a per-process
+/// counter suffix separates IDs made on the same clock tick.
+pub(crate) fn unique_id(now: DateTime<Utc>) -> String {
+    let n = ID_COUNTER.fetch_add(1, Ordering::Relaxed);
+    format!(
+        "{}-{:04}",
+        now.to_rfc3339_opts(SecondsFormat::Nanos, true),
+        n
+    )
+}
+
+/// Turn an ID into a safe file name stem, replacing anything but letters,
+/// digits, `-`, `_`, `.` and `+`.
+///
+/// Files saved before sanitizing only had `:` replaced; keeping `+` gives
+/// those timestamp IDs the same stem, so they save back over their file.
+pub(crate) fn file_stem(id: &str) -> String {
+    id.chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.' | '+') {
+                c
+            } else {
+                '-'
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_same_instant_ids_differ() {
+        let now = Utc::now();
+        assert_ne!(unique_id(now), unique_id(now));
+    }
+
+    #[test]
+    fn test_file_stem_sanitizes() {
+        assert_eq!(
+            file_stem("2024-01-01T10:00:00.5Z-0001"),
+            "2024-01-01T10-00-00.5Z-0001"
+        );
+        assert_eq!(file_stem("a/b\\c d:e*f?"), "a-b-c-d-e-f-");
+    }
+
+    #[test]
+    fn test_file_stem_matches_legacy_names() {
+        let legacy = "2024-01-01T10:00:00.123456789+00:00";
+        assert_eq!(file_stem(legacy), legacy.replace(':', "-"));
+    }
+}
@@ -0,0 +1,218 @@
+//! Interval checks for verifying the temperament octave by ear.
+//!
+//! Two notes a tempered interval apart have partials that nearly coincide.
+//! The beat rate is the difference between those partials, e.g. for a major
+//! third the 5th partial of the lower note against the 4th of the upper.
+
+use super::notes::Note;
+use super::temperament::Temperament;
+
+/// Lowest note of the temperament octave (F3).
+pub const TEMPERAMENT_LOW_MIDI: u8 = 53;
+/// Highest note of the temperament octave (F4).
+pub const TEMPERAMENT_HIGH_MIDI: u8 = 65;
+/// Lowest note tuned by its octave beats when that mode is on (C6).
+pub const OCTAVE_BEATS_LOW_MIDI: u8 = 84;
+
+/// An interval used to check the temperament.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Interval {
+    /// Major third (4 semitones, 5:4).
+    MajorThird,
+    /// Perfect fourth (5 semitones, 4:3).
+    Fourth,
+    /// Perfect fifth (7 semitones, 3:2).
+    Fifth,
+    /// Octave (12 semitones, 2:1), used for tuning the high treble.
+    Octave,
+}
+
+impl Interval {
+    /// All temperament check intervals, in the order they are usually listened to.
+    pub const ALL: [Interval; 3] = [Interval::MajorThird, Interval::Fourth, Interval::Fifth];
+
+    /// Size in semitones.
+    pub fn semitones(&self) -> u8 {
+        match self {
+            Interval::MajorThird => 4,
+            Interval::Fourth => 5,
+            Interval::Fifth => 7,
+            Interval::Octave => 12,
+        }
+    }
+
+    /// Coinciding partials as (lower note partial, upper note partial).
+    pub fn partials(&self) -> (f32, f32) {
+        match self {
+            Interval::MajorThird => (5.0, 4.0),
+            Interval::Fourth => (4.0, 3.0),
+            Interval::Fifth => (3.0, 2.0),
+            Interval::Octave => (2.0, 1.0),
+        }
+    }
+
+    /// Short label (e.g. "M3").
+    pub fn label(&self) -> &'static str {
+        match self {
+            Interval::MajorThird => "M3",
+            Interval::Fourth => "P4",
+            Interval::Fifth => "P5",
+            Interval::Octave => "P8",
+        }
+    }
+}
+
+/// Theoretical beat rate in Hz between two frequencies forming `interval`.
+pub fn beat_rate(interval: Interval, lower: f32, upper: f32) -> f32 {
+    let (lower_partial, upper_partial) = interval.partials();
+    (lower * lower_partial - upper * upper_partial).abs()
+}
+
+/// A single interval check.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct IntervalCheck {
+    /// Interval being checked.
+    pub interval: Interval,
+    /// Lower note MIDI number.
+    pub lower_midi: u8,
+    /// Upper note MIDI number.
+    pub upper_midi: u8,
+    /// Expected beat rate in Hz.
+    pub beats_per_second: f32,
+}
+
+impl IntervalCheck {
+    /// Build a check for `interval` starting at `lower_midi`.
+    pub fn new(temperament: &Temperament, interval: Interval, lower_midi: u8) -> Self {
+        let upper_midi = lower_midi + interval.semitones();
+        Self {
+            interval,
+            lower_midi,
+            upper_midi,
+            beats_per_second: beat_rate(
+                interval,
+                temperament.frequency(lower_midi),
+                temperament.frequency(upper_midi),
+            ),
+        }
+    }
+
+    /// Display label (e.g. "M3 C4-E4").
+    pub fn label(&self) -> String {
+        let name = |midi| {
+            Note::from_midi(midi)
+                .map(|n| n.display_name())
+                .unwrap_or_default()
+        };
+        format!(
+            "{} {}-{}",
+            self.interval.label(),
+            name(self.lower_midi),
+            name(self.upper_midi)
+        )
+    }
+}
+
+/// All interval checks that fit within the temperament octave starting at
+/// `low_midi`.
+pub fn temperament_checks(temperament: &Temperament, low_midi: u8) -> Vec<IntervalCheck> {
+    let high_midi = low_midi + (TEMPERAMENT_HIGH_MIDI - TEMPERAMENT_LOW_MIDI);
+    Interval::ALL
+        .iter()
+        .flat_map(move |&interval| {
+            (low_midi..=high_midi - interval.semitones())
+                .map(move |lower| IntervalCheck::new(temperament, interval, lower))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+
+    #[test]
+    fn test_octave_beats_against_tuned_lower_note() {
+        let temperament = Temperament::new();
+        let a6_target = temperament.frequency(93);
+
+        // A5 left exactly on target: the A6 target is beatless against it
+        let a5 = temperament.frequency(81);
+        assert_relative_eq!(
+            beat_rate(Interval::Octave, a5, a6_target),
+            0.0,
+            epsilon = 1e-3
+        );
+
+        // A5 left 2 cents sharp: its 2nd partial beats against the A6 target
+        let sharp_a5 = temperament.cents_to_frequency(a5, 2.0);
+        let expected = 2.0 * sharp_a5 - a6_target;
+        assert_relative_eq!(beat_rate(Interval::Octave, sharp_a5, a6_target), expected);
+        assert_relative_eq!(expected, 2.03, epsilon = 0.01);
+
+        // The beats vanish at exactly twice the lower note's actual pitch
+        assert_relative_eq!(beat_rate(Interval::Octave, sharp_a5, 2.0 * sharp_a5), 0.0);
+    }
+
+    #[test]
+    fn test_major_third_c4_e4() {
+        // ET C4-E4 beats at about 10.38 Hz
+        let check = IntervalCheck::new(&Temperament::new(), Interval::MajorThird, 60);
+        assert_eq!(check.upper_midi, 64);
+        assert_relative_eq!(check.beats_per_second, 10.38, epsilon = 0.02);
+        assert_eq!(check.label(), "M3 C4-E4");
+    }
+
+    #[test]
+    fn test_fifth_and_fourth_beat_slowly() {
+        let temperament = Temperament::new();
+
+        // ET A3-E4 fifth beats under 1 Hz, C4-F4 fourth around 1.2 Hz
+        let fifth = IntervalCheck::new(&temperament, Interval::Fifth, 57);
+        assert_relative_eq!(fifth.beats_per_second, 0.74, epsilon = 0.02);
+
+        let fourth = IntervalCheck::new(&temperament, Interval::Fourth, 60);
+        assert_relative_eq!(fourth.beats_per_second, 1.18, epsilon = 0.02);
+    }
+
+    #[test]
+    fn test_pure_interval_has_no_beats() {
+        assert_eq!(beat_rate(Interval::MajorThird, 400.0, 500.0), 0.0);
+        assert_eq!(beat_rate(Interval::Fifth, 200.0, 300.0), 0.0);
+    }
+
+    #[test]
+    fn test_thirds_beat_faster_going_up() {
+        let checks: Vec<_> = temperament_checks(&Temperament::new(), TEMPERAMENT_LOW_MIDI)
+            .into_iter()
+            .filter(|c| c.interval == Interval::MajorThird)
+            .collect();
+
+        // F3-A3 up to C#4-F4
+        assert_eq!(checks.len(), 9);
+        for pair in checks.windows(2) {
+            assert!(pair[1].beats_per_second > pair[0].beats_per_second);
+        }
+    }
+
+    #[test]
+    fn test_checks_stay_in_temperament_octave() {
+        for check in temperament_checks(&Temperament::new(), TEMPERAMENT_LOW_MIDI) {
+            assert!(check.lower_midi >= TEMPERAMENT_LOW_MIDI);
+            assert!(check.upper_midi <= TEMPERAMENT_HIGH_MIDI);
+        }
+    }
+
+    #[test]
+    fn test_checks_follow_temperament_start() {
+        // A3 to A4
+        let checks = temperament_checks(&Temperament::new(), 57);
+        assert_eq!(
+            checks.first().map(|c| c.label()).as_deref(),
+            Some("M3 A3-C#4")
+        );
+        assert!(checks
+            .iter()
+            .all(|c| c.lower_midi >= 57 && c.upper_midi <= 69));
+    }
+}
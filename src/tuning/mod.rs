@@ -1,15 +1,29 @@
 //! Tuning logic, temperament calculations, and session management.
 
+mod atomic;
+mod id;
+pub mod intervals;
 pub mod notes;
 pub mod order;
+pub mod practice;
 pub mod profile;
+pub mod recording;
+pub mod relative;
 pub mod session;
+pub mod storage;
 pub mod stretch;
+pub mod targets;
 pub mod temperament;
+pub mod training;
 
-pub use notes::{Note, NOTES, NOTE_COUNT};
+pub use intervals::{Interval, IntervalCheck};
+pub use notes::{Note, NoteSpelling, StringLayout, NOTES, NOTE_COUNT};
 pub use order::TuningOrder;
+pub use practice::{IntervalAccuracy, TemperamentPractice};
 pub use profile::{PianoProfile, ProfiledNote};
-pub use session::{CompletedNote, Session, TuningMode};
+pub use relative::RelativeTargets;
+pub use session::{CompletedNote, Session, SessionEvent, SessionEventKind, TuningMode};
 pub use stretch::StretchCurve;
-pub use temperament::Temperament;
+pub use targets::TargetTable;
+pub use temperament::{A4Preset, Temperament, TuningName};
+pub use training::{TrainingResult, TrainingRound, TrainingStats};
@@ -1,5 +1,22 @@
 //! 88-key piano note definitions.
 
+use std::ops::RangeInclusive;
+
+use serde::{Deserialize, Serialize};
+
+/// Enharmonic spelling for black-key note names.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize, clap::ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum NoteSpelling {
+    /// Sharps only (e.g. "A#4").
+    #[default]
+    Sharps,
+    /// Flats only (e.g. "Bb4").
+    Flats,
+    /// Both spellings (e.g. "A#4/Bb4").
+    Both,
+}
+
 /// A piano note with its properties.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct Note {
@@ -29,6 +46,21 @@ impl Note {
         format!("{}{}", self.name, self.octave)
     }
 
+    /// Get display name in the given spelling.
+    /// White keys are the same in every spelling.
+    pub fn display_name_with(&self, spelling: NoteSpelling) -> String {
+        let flat = match flat_name(self.name) {
+            Some(flat) => format!("{}{}", flat, self.octave),
+            None => return self.display_name(),
+        };
+
+        match spelling {
+            NoteSpelling::Sharps => self.display_name(),
+            NoteSpelling::Flats => flat,
+            NoteSpelling::Both => format!("{}/{}", self.display_name(), flat),
+        }
+    }
+
     /// Check if this is a trichord (3 strings).
     pub fn is_trichord(&self) -> bool {
         self.strings == 3
@@ -42,9 +74,11 @@ impl Note {
         NOTES.get((midi - 21) as usize)
     }
 
-    /// Get note by name (e.g., "A4", "C#5").
+    /// Get note by name (e.g., "A4", "C#5", "Db5").
     pub fn from_name(name: &str) -> Option<&'static Note> {
-        NOTES.iter().find(|n| n.display_name() == name)
+        NOTES
+            .iter()
+            .find(|n| n.display_name() == name || n.display_name_with(NoteSpelling::Flats) == name)
     }
 }
 
@@ -53,6 +87,18 @@ const NOTE_NAMES: [&str; 12] = [
     "A", "A#", "B", "C", "C#", "D", "D#", "E", "F", "F#", "G", "G#",
 ];
 
+/// Flat spelling of a sharp note name, if it is a black key.
+fn flat_name(sharp: &str) -> Option<&'static str> {
+    match sharp {
+        "A#" => Some("Bb"),
+        "C#" => Some("Db"),
+        "D#" => Some("Eb"),
+        "F#" => Some("Gb"),
+        "G#" => Some("Ab"),
+        _ => None,
+    }
+}
+
 /// Generate all 88 piano notes.
 /// Piano range: A0 (MIDI 21) to C8 (MIDI 108)
 ///
@@ -107,6 +153,73 @@ pub fn note_at(index: usize) -> Option<&'static Note> {
 /// Total number of notes on a standard piano.
 pub const NOTE_COUNT: usize = 88;
 
+/// Parse a MIDI range like "21-27" (or a single note like "21").
+pub fn parse_midi_range(text: &str) -> Option<RangeInclusive<u8>> {
+    let text = text.trim();
+    let (start, end) = match text.split_once('-') {
+        Some((start, end)) => (start.trim().parse().ok()?, end.trim().parse().ok()?),
+        None => {
+            let midi = text.parse().ok()?;
+            (midi, midi)
+        }
+    };
+
+    if start > end || Note::from_midi(start).is_none() || Note::from_midi(end).is_none() {
+        return None;
+    }
+    Some(start..=end)
+}
+
+/// Per-instrument string counts.
+///
+/// Pianos differ in where the monochord/bichord/trichord transitions fall.
+/// Ranges override the table in `NOTES`; later ranges win over earlier ones.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct StringLayout {
+    ranges: Vec<(RangeInclusive<u8>, u8)>,
+}
+
+impl StringLayout {
+    /// Create a layout with no overrides (standard string counts).
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Override the string count for a MIDI range.
+    pub fn with_range(mut self, range: RangeInclusive<u8>, strings: u8) -> Self {
+        self.ranges.push((range, strings));
+        self
+    }
+
+    /// Check if the layout has no overrides.
+    pub fn is_standard(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    /// Effective string count for a note.
+    pub fn strings_for(&self, note: &Note) -> u8 {
+        self.ranges
+            .iter()
+            .rev()
+            .find(|(range, _)| range.contains(&note.midi))
+            .map(|&(_, strings)| strings)
+            .unwrap_or(note.strings)
+    }
+
+    /// Copy of a note with its effective string count.
+    pub fn apply(&self, note: &Note) -> Note {
+        Note {
+            strings: self.strings_for(note),
+            ..*note
+        }
+    }
+
+    /// All 88 notes with effective string counts.
+    pub fn notes(&self) -> Vec<Note> {
+        NOTES.iter().map(|n| self.apply(n)).collect()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -178,6 +291,27 @@ mod tests {
         assert_eq!(csharp5.midi, 73);
     }
 
+    #[test]
+    fn test_spellings_for_black_key() {
+        let note = Note::from_midi(70).unwrap();
+        assert_eq!(note.display_name_with(NoteSpelling::Sharps), "A#4");
+        assert_eq!(note.display_name_with(NoteSpelling::Flats), "Bb4");
+        assert_eq!(note.display_name_with(NoteSpelling::Both), "A#4/Bb4");
+    }
+
+    #[test]
+    fn test_spellings_leave_white_keys_unchanged() {
+        let note = Note::from_midi(69).unwrap();
+        assert_eq!(note.display_name_with(NoteSpelling::Flats), "A4");
+        assert_eq!(note.display_name_with(NoteSpelling::Both), "A4");
+    }
+
+    #[test]
+    fn test_from_name_accepts_flats() {
+        assert_eq!(Note::from_name("Bb4").unwrap().midi, 70);
+        assert_eq!(Note::from_name("Db5").unwrap().midi, 73);
+    }
+
     #[test]
     fn test_trichord_detection() {
         assert!(!Note::from_midi(21).unwrap().is_trichord()); // A0 (monochord)
@@ -195,6 +329,35 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_parse_midi_range() {
+        assert_eq!(parse_midi_range("21-27"), Some(21..=27));
+        assert_eq!(parse_midi_range(" 28 - 39 "), Some(28..=39));
+        assert_eq!(parse_midi_range("60"), Some(60..=60));
+        assert_eq!(parse_midi_range("27-21"), None);
+        assert_eq!(parse_midi_range("10-27"), None);
+        assert_eq!(parse_midi_range("low-high"), None);
+    }
+
+    #[test]
+    fn test_string_layout_overrides() {
+        let layout = StringLayout::new()
+            .with_range(21..=27, 1)
+            .with_range(28..=39, 2);
+
+        // B1 (MIDI 35) is outside the overrides and stays a bichord
+        assert_eq!(layout.strings_for(Note::from_midi(35).unwrap()), 2);
+        // F#1 (MIDI 30) moves from monochord to bichord
+        assert_eq!(Note::from_midi(30).unwrap().strings, 1);
+        assert_eq!(layout.strings_for(Note::from_midi(30).unwrap()), 2);
+        // A4 falls back to the table
+        assert_eq!(layout.strings_for(Note::from_midi(69).unwrap()), 3);
+
+        let notes = layout.notes();
+        assert_eq!(notes.len(), NOTE_COUNT);
+        assert_eq!(notes[30 - 21].strings, 2);
+    }
+
     #[test]
     fn test_chromatic_sequence() {
         // Check that notes follow chromatic order
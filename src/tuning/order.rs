@@ -4,9 +4,15 @@
 //! 1. Temperament octave (F3-F4): 12 notes that form the foundation
 //! 2. Octaves upward (F4→C8): Each note tuned as octave from below
 //! 3. Octaves downward (F3→A0): Each note tuned as octave from above
+//!
+//! Some tuners set the temperament from another note, such as A3 or C4;
+//! the octave then runs up from there and the phases shift with it.
+
+use std::ops::RangeInclusive;
 
 use super::notes::{Note, NOTES};
 use super::profile::PianoProfile;
+use super::targets::TargetTable;
 
 /// MIDI note numbers for key reference points.
 const F3_MIDI: u8 = 53;
@@ -19,48 +25,93 @@ const F3_INDEX: usize = (F3_MIDI - A0_MIDI) as usize; // 32
 const F4_INDEX: usize = (F4_MIDI - A0_MIDI) as usize; // 44
 const C8_INDEX: usize = (C8_MIDI - A0_MIDI) as usize; // 87
 
+/// Notes in the temperament octave, both ends included.
+const TEMPERAMENT_LEN: usize = 13;
+
+/// Note the temperament octave starts from by default (F3).
+pub const DEFAULT_TEMPERAMENT_START: u8 = F3_MIDI;
+/// Notes the temperament octave may start from (C3 to C4).
+pub const TEMPERAMENT_START_RANGE: RangeInclusive<u8> = 48..=60;
+
 /// Tuning order generator following traditional piano tuning order.
 pub struct TuningOrder {
     /// Ordered indices into the NOTES array.
     order: Vec<usize>,
+    /// Index in NOTES of the temperament octave's lowest note.
+    start: usize,
 }
 
 impl TuningOrder {
     /// Create a new tuning order.
     pub fn new() -> Self {
         Self {
-            order: Self::generate_order(),
+            order: Self::generate_order(F3_INDEX),
+            start: F3_INDEX,
         }
     }
 
-    /// Generate the traditional tuning order.
+    /// Create a tuning order whose temperament octave starts at `midi`.
     ///
-    /// Order:
+    /// Returns `None` outside [`TEMPERAMENT_START_RANGE`].
+    pub fn from_midi(midi: u8) -> Option<Self> {
+        if !TEMPERAMENT_START_RANGE.contains(&midi) {
+            return None;
+        }
+        let start = (midi - A0_MIDI) as usize;
+        Some(Self {
+            order: Self::generate_order(start),
+            start,
+        })
+    }
+
+    /// Create an order covering only the temperament octave (F3-F4).
+    pub fn only_temperament() -> Self {
+        Self {
+            order: (F3_INDEX..=F4_INDEX).collect(),
+            start: F3_INDEX,
+        }
+    }
+
+    /// Generate the traditional tuning order from the temperament octave
+    /// starting at index `start`.
+    ///
+    /// Order (from F3):
     /// 1. Temperament octave (F3-F4): 12 notes, indices 32-44
     /// 2. Octaves upward (F#4→C8): indices 45-87
     /// 3. Octaves downward (E3→A0): indices 31-0
-    fn generate_order() -> Vec<usize> {
+    fn generate_order(start: usize) -> Vec<usize> {
         let mut order = Vec::with_capacity(88);
+        let end = start + TEMPERAMENT_LEN - 1;
 
-        // 1. Temperament octave: F3 to F4 (inclusive)
+        // 1. Temperament octave, e.g. F3 to F4 (inclusive)
         // This is 13 notes (F3, F#3, G3, G#3, A3, A#3, B3, C4, C#4, D4, D#4, E4, F4)
-        for i in F3_INDEX..=F4_INDEX {
+        for i in start..=end {
             order.push(i);
         }
 
-        // 2. Octaves upward: F#4 to C8
-        for i in (F4_INDEX + 1)..=C8_INDEX {
+        // 2. Octaves upward, e.g. F#4 to C8
+        for i in (end + 1)..=C8_INDEX {
             order.push(i);
         }
 
-        // 3. Octaves downward: E3 to A0
-        for i in (0..F3_INDEX).rev() {
+        // 3. Octaves downward, e.g. E3 to A0
+        for i in (0..start).rev() {
             order.push(i);
         }
 
         order
     }
 
+    /// MIDI number of the note the temperament octave starts from.
+    pub fn temperament_start(&self) -> u8 {
+        A0_MIDI + self.start as u8
+    }
+
+    /// Number of notes tuned upward after the temperament octave.
+    fn upward_len(&self) -> usize {
+        C8_INDEX + 1 - (self.start + TEMPERAMENT_LEN)
+    }
+
     /// Get the ordered list of note indices.
     pub fn indices(&self) -> &[usize] {
         &self.order
@@ -71,6 +122,14 @@ impl TuningOrder {
         self.order.iter().map(|&i| &NOTES[i]).collect()
     }
 
+    /// Get each note in order with its target frequency from `targets`.
+    pub fn target_frequencies(&self, targets: &TargetTable) -> Vec<(&'static Note, f32)> {
+        self.notes()
+            .into_iter()
+            .map(|note| (note, targets.target(note.midi)))
+            .collect()
+    }
+
     /// Get the note at a specific position in the tuning order.
     pub fn note_at(&self, position: usize) -> Option<&'static Note> {
         self.order.get(position).map(|&i| &NOTES[i])
@@ -97,17 +156,18 @@ impl TuningOrder {
 
     /// Check if we're in the temperament octave phase.
     pub fn is_temperament_phase(&self, position: usize) -> bool {
-        position < 13 // F3 to F4 is 13 notes
+        position < TEMPERAMENT_LEN // F3 to F4 is 13 notes
     }
 
     /// Check if we're in the upward phase.
     pub fn is_upward_phase(&self, position: usize) -> bool {
-        (13..13 + 43).contains(&position) // F#4 to C8 is 43 notes
+        // F#4 to C8 is 43 notes
+        (TEMPERAMENT_LEN..TEMPERAMENT_LEN + self.upward_len()).contains(&position)
     }
 
     /// Check if we're in the downward phase.
     pub fn is_downward_phase(&self, position: usize) -> bool {
-        position >= 13 + 43 // E3 to A0 is 32 notes
+        position >= TEMPERAMENT_LEN + self.upward_len() // E3 to A0 is 32 notes
     }
 
     /// Get the phase name for a position.
@@ -121,25 +181,59 @@ impl TuningOrder {
         }
     }
 
+    /// Pick the already-tuned note to tune `midi` against.
+    ///
+    /// Octaves are the most reliable interval, so the octave below (as when
+    /// working upward) or above (working downward) wins. Otherwise, as in
+    /// the temperament octave, the nearest tuned note is used, lower first.
+    pub fn reference_for(midi: u8, is_done: impl Fn(u8) -> bool) -> Option<u8> {
+        let in_range = |m: u8| (A0_MIDI..=C8_MIDI).contains(&m) && m != midi && is_done(m);
+
+        [midi.checked_sub(12), midi.checked_add(12)]
+            .into_iter()
+            .flatten()
+            .find(|&m| in_range(m))
+            .or_else(|| {
+                (1..=C8_MIDI - A0_MIDI).find_map(|distance| {
+                    [midi.checked_sub(distance), midi.checked_add(distance)]
+                        .into_iter()
+                        .flatten()
+                        .find(|&m| in_range(m))
+                })
+            })
+    }
+
     /// Create a tuning order from a piano profile.
     ///
     /// Order:
     /// 1. Temperament octave (F3-F4): 13 notes, always first
     /// 2. Remaining 75 notes sorted by absolute cents deviation (worst first)
     pub fn from_profile(profile: &PianoProfile) -> Self {
-        let mut order = Vec::with_capacity(88);
+        Self::from_profile_at(profile, DEFAULT_TEMPERAMENT_START)
+            .expect("F3 is a valid temperament start")
+    }
 
-        // 1. Temperament octave first (F3-F4, indices 32-44)
-        for i in F3_INDEX..=F4_INDEX {
-            order.push(i);
+    /// Create a tuning order from a piano profile, with the temperament
+    /// octave starting at `midi`.
+    ///
+    /// Returns `None` outside [`TEMPERAMENT_START_RANGE`].
+    pub fn from_profile_at(profile: &PianoProfile, midi: u8) -> Option<Self> {
+        if !TEMPERAMENT_START_RANGE.contains(&midi) {
+            return None;
         }
+        let start = (midi - A0_MIDI) as usize;
+        let temperament = start..start + TEMPERAMENT_LEN;
+        let mut order = Vec::with_capacity(88);
+
+        // 1. Temperament octave first (e.g. F3-F4, indices 32-44)
+        order.extend(temperament.clone());
 
         // 2. Collect remaining notes with their deviations
         let mut remaining: Vec<(usize, f32)> = Vec::with_capacity(75);
 
         for i in 0..88 {
             // Skip temperament octave
-            if (F3_INDEX..=F4_INDEX).contains(&i) {
+            if temperament.contains(&i) {
                 continue;
             }
 
@@ -159,7 +253,7 @@ impl TuningOrder {
             order.push(idx);
         }
 
-        Self { order }
+        Some(Self { order, start })
     }
 }
 
@@ -172,6 +266,8 @@ impl Default for TuningOrder {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::tuning::stretch::StretchCurve;
+    use crate::tuning::temperament::Temperament;
 
     #[test]
     fn test_order_length() {
@@ -179,6 +275,55 @@ mod tests {
         assert_eq!(order.len(), 88, "Should have 88 notes");
     }
 
+    #[test]
+    fn test_reference_prefers_octave() {
+        let done = |m: u8| [57, 60, 69].contains(&m);
+        // A5 against A4, A3 against A4 (octave above when none below)
+        assert_eq!(TuningOrder::reference_for(81, done), Some(69));
+        assert_eq!(TuningOrder::reference_for(57 - 12, done), Some(57));
+        // B3 has no tuned octave; nearest is C4, one semitone up
+        assert_eq!(TuningOrder::reference_for(59, done), Some(60));
+        // Ties go to the lower note
+        assert_eq!(
+            TuningOrder::reference_for(58, |m| m == 57 || m == 59),
+            Some(57)
+        );
+    }
+
+    #[test]
+    fn test_reference_none_when_nothing_done() {
+        assert_eq!(TuningOrder::reference_for(53, |_| false), None);
+        // A note never references itself
+        assert_eq!(TuningOrder::reference_for(53, |m| m == 53), None);
+    }
+
+    #[test]
+    fn test_only_temperament() {
+        let order = TuningOrder::only_temperament();
+        assert_eq!(order.len(), 13);
+        assert_eq!(order.note_at(0).unwrap().display_name(), "F3");
+        assert_eq!(order.note_at(12).unwrap().display_name(), "F4");
+        assert_eq!(order.indices(), &TuningOrder::new().indices()[..13]);
+    }
+
+    #[test]
+    fn test_target_frequencies() {
+        let order = TuningOrder::new();
+        let temperament = Temperament::with_a4(442.0);
+
+        let targets = order.target_frequencies(&TargetTable::build(&temperament, None, 0.0));
+        assert_eq!(targets.len(), 88);
+        assert_eq!(targets[0].0.display_name(), "F3");
+        let a4 = targets.iter().find(|(n, _)| n.midi == 69).unwrap();
+        assert_eq!(a4.1, 442.0);
+
+        let stretch = StretchCurve::new();
+        let stretched =
+            order.target_frequencies(&TargetTable::build(&temperament, Some(&stretch), 0.0));
+        let c8 = stretched.iter().find(|(n, _)| n.midi == 108).unwrap();
+        assert_eq!(c8.1, stretch.apply(temperament.frequency(108), 108));
+    }
+
     #[test]
     fn test_all_notes_included() {
         let order = TuningOrder::new();
@@ -275,7 +420,7 @@ mod tests {
         // Downward phase starts at position 56
         let downward_start = 13 + 43;
 
-        for i in downward_start..(87) {
+        for i in downward_start..87 {
             let current = notes[i].midi;
             let next = notes[i + 1].midi;
             assert_eq!(
@@ -334,6 +479,41 @@ mod tests {
         assert!(order.is_downward_phase(87));
     }
 
+    #[test]
+    fn test_from_midi_starts_temperament_at_a3() {
+        let a3 = Note::from_name("A3").unwrap().midi;
+        let order = TuningOrder::from_midi(a3).expect("A3 is in range");
+        assert_eq!(order.temperament_start(), a3);
+
+        let names: Vec<_> = order.notes().iter().map(|n| n.display_name()).collect();
+        assert_eq!(names[0], "A3");
+        assert_eq!(names[12], "A4");
+        // Then up from A#4 to C8, and down from G#3 to A0
+        assert_eq!(names[13], "A#4");
+        assert_eq!(names[TEMPERAMENT_LEN + 39 - 1], "C8");
+        assert_eq!(names[TEMPERAMENT_LEN + 39], "G#3");
+        assert_eq!(names[87], "A0");
+
+        assert!(order.is_temperament_phase(12));
+        assert!(order.is_upward_phase(51));
+        assert!(order.is_downward_phase(52));
+        assert_eq!(order.indices().len(), 88);
+    }
+
+    #[test]
+    fn test_from_midi_rejects_out_of_range_starts() {
+        assert!(TuningOrder::from_midi(47).is_none());
+        assert!(TuningOrder::from_midi(61).is_none());
+        let c4 = TuningOrder::from_midi(60).expect("C4 is in range");
+        assert_eq!(c4.note_at(12).unwrap().display_name(), "C5");
+        assert_eq!(
+            TuningOrder::from_midi(DEFAULT_TEMPERAMENT_START)
+                .unwrap()
+                .indices(),
+            TuningOrder::new().indices()
+        );
+    }
+
     #[test]
     fn test_phase_names() {
         let order = TuningOrder::new();
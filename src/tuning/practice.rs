@@ -0,0 +1,181 @@
+//! Temperament practice: set the F3-F4 octave over and over with scoring.
+//!
+//! Each pass tunes the temperament octave once. As both notes of an interval
+//! check are set, the beat rate they actually produce is compared with the
+//! ideal one, building a per-interval accuracy report across passes.
+
+use std::collections::HashMap;
+
+use super::intervals::{
+    beat_rate, temperament_checks, Interval, IntervalCheck, TEMPERAMENT_LOW_MIDI,
+};
+use super::temperament::Temperament;
+
+/// Accuracy for one kind of interval across all passes.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct IntervalAccuracy {
+    /// Interval being reported.
+    pub interval: Interval,
+    /// Number of checks scored.
+    pub checks: usize,
+    /// Average beat rate error in Hz.
+    pub average_error_bps: f32,
+}
+
+/// Running results of a temperament practice.
+#[derive(Debug, Clone)]
+pub struct TemperamentPractice {
+    temperament: Temperament,
+    checks: Vec<IntervalCheck>,
+    /// Deviation of each note set in the current pass, by MIDI number.
+    pass: HashMap<u8, f32>,
+    /// Completed passes.
+    passes: usize,
+    /// Total absolute beat rate error and check count, per interval.
+    totals: HashMap<Interval, (f32, usize)>,
+}
+
+impl TemperamentPractice {
+    /// Start practising against `temperament`.
+    pub fn new(temperament: Temperament) -> Self {
+        Self {
+            checks: temperament_checks(&temperament, TEMPERAMENT_LOW_MIDI),
+            temperament,
+            pass: HashMap::new(),
+            passes: 0,
+            totals: HashMap::new(),
+        }
+    }
+
+    /// Record where a note ended up, scoring checks it completes.
+    ///
+    /// A check is scored once both of its notes are set in this pass.
+    pub fn record(&mut self, midi: u8, cents: f32) {
+        self.pass.insert(midi, cents);
+
+        for check in &self.checks {
+            if check.lower_midi != midi && check.upper_midi != midi {
+                continue;
+            }
+            let (Some(&lower), Some(&upper)) = (
+                self.pass.get(&check.lower_midi),
+                self.pass.get(&check.upper_midi),
+            ) else {
+                continue;
+            };
+
+            let actual = beat_rate(
+                check.interval,
+                self.frequency(check.lower_midi, lower),
+                self.frequency(check.upper_midi, upper),
+            );
+            let total = self.totals.entry(check.interval).or_default();
+            total.0 += (actual - check.beats_per_second).abs();
+            total.1 += 1;
+        }
+    }
+
+    /// Start the next pass through the octave.
+    pub fn next_pass(&mut self) {
+        self.pass.clear();
+        self.passes += 1;
+    }
+
+    /// Number of completed passes.
+    pub fn passes(&self) -> usize {
+        self.passes
+    }
+
+    /// Per-interval accuracy, for intervals with at least one scored check.
+    pub fn report(&self) -> Vec<IntervalAccuracy> {
+        Interval::ALL
+            .iter()
+            .filter_map(|&interval| {
+                let &(total, checks) = self.totals.get(&interval)?;
+                Some(IntervalAccuracy {
+                    interval,
+                    checks,
+                    average_error_bps: total / checks as f32,
+                })
+            })
+            .collect()
+    }
+
+    fn frequency(&self, midi: u8, cents: f32) -> f32 {
+        self.temperament
+            .cents_to_frequency(self.temperament.frequency(midi), cents)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tuning::intervals::TEMPERAMENT_HIGH_MIDI;
+
+    fn play_pass(practice: &mut TemperamentPractice, errors: &[(u8, f32)]) {
+        for midi in TEMPERAMENT_LOW_MIDI..=TEMPERAMENT_HIGH_MIDI {
+            let cents = errors
+                .iter()
+                .find(|&&(m, _)| m == midi)
+                .map_or(0.0, |&(_, c)| c);
+            practice.record(midi, cents);
+        }
+        practice.next_pass();
+    }
+
+    #[test]
+    fn test_perfect_pass_has_no_error() {
+        let mut practice = TemperamentPractice::new(Temperament::new());
+        play_pass(&mut practice, &[]);
+
+        let report = practice.report();
+        assert_eq!(practice.passes(), 1);
+        assert_eq!(report.len(), 3);
+        for accuracy in &report {
+            assert!(accuracy.average_error_bps < 1e-3, "{accuracy:?}");
+        }
+        let thirds = report[0];
+        assert_eq!(thirds.interval, Interval::MajorThird);
+        assert_eq!(thirds.checks, 9);
+    }
+
+    #[test]
+    fn test_known_error_accumulates() {
+        let temperament = Temperament::new();
+        let mut practice = TemperamentPractice::new(temperament);
+
+        // A3 set 4 cents sharp affects only checks that include A3
+        let a3 = 57;
+        play_pass(&mut practice, &[(a3, 4.0)]);
+        play_pass(&mut practice, &[]);
+
+        let sharp_a3 = temperament.cents_to_frequency(temperament.frequency(a3), 4.0);
+        let expected_error: f32 = temperament_checks(&temperament, TEMPERAMENT_LOW_MIDI)
+            .iter()
+            .filter(|c| c.interval == Interval::Fifth && c.lower_midi == a3)
+            .map(|c| {
+                let actual = beat_rate(c.interval, sharp_a3, temperament.frequency(c.upper_midi));
+                (actual - c.beats_per_second).abs()
+            })
+            .sum();
+
+        let fifths = practice
+            .report()
+            .into_iter()
+            .find(|a| a.interval == Interval::Fifth)
+            .unwrap();
+        // 6 fifths per pass, two passes, one of them off
+        assert_eq!(fifths.checks, 12);
+        assert!((fifths.average_error_bps - expected_error / 12.0).abs() < 1e-4);
+        assert!(fifths.average_error_bps > 0.0);
+    }
+
+    #[test]
+    fn test_empty_report_before_any_checks() {
+        let mut practice = TemperamentPractice::new(Temperament::new());
+        assert!(practice.report().is_empty());
+
+        practice.record(TEMPERAMENT_LOW_MIDI, 1.0);
+        assert!(practice.report().is_empty());
+    }
+}
@@ -1,12 +1,27 @@
 //! Piano profiling for deviation-based tuning order.
 
 use chrono::{DateTime, Utc};
-use directories::ProjectDirs;
 use serde::{Deserialize, Serialize};
 use std::fs;
+use std::io::{BufReader, Read, Write};
 use std::path::{Path, PathBuf};
 
+use super::atomic::write_atomic;
+use super::id::{file_stem, unique_id};
 use super::notes::{Note, NOTES, NOTE_COUNT};
+use super::storage::data_dir;
+use super::stretch::StretchCurve;
+
+/// Heat-map glyph for a note within 5 cents.
+pub const HEATMAP_GOOD: char = '=';
+/// Heat-map glyph for a note 5-15 cents off.
+pub const HEATMAP_WARNING: char = '~';
+/// Heat-map glyph for a note more than 15 cents off.
+pub const HEATMAP_BAD: char = '!';
+/// Heat-map glyph for a note that wasn't measured.
+pub const HEATMAP_MISSING: char = '.';
+/// Heat-map separator placed before each C.
+pub const HEATMAP_SEPARATOR: char = '|';
 
 /// A single profiled note measurement.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -36,10 +51,14 @@ impl ProfiledNote {
 /// A complete piano profile with measurements for all 88 keys.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PianoProfile {
-    /// Unique profile ID (ISO 8601 timestamp).
+    /// Unique profile ID (ISO 8601 timestamp with a uniqueness suffix).
     pub id: String,
     /// Measurements for each note (index 0 = A0, index 87 = C8).
     pub notes: Vec<Option<ProfiledNote>>,
+    /// Stretch curve the measurements were taken against, including any
+    /// hand edits.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stretch: Option<StretchCurve>,
     /// When this profile was created.
     pub created_at: DateTime<Utc>,
 }
@@ -49,8 +68,9 @@ impl PianoProfile {
     pub fn new() -> Self {
         let now = Utc::now();
         Self {
-            id: now.to_rfc3339(),
+            id: unique_id(now),
             notes: vec![None; NOTE_COUNT],
+            stretch: None,
             created_at: now,
         }
     }
@@ -123,20 +143,41 @@ impl PianoProfile {
         indexed
     }
 
+    /// One-line text summary of the whole piano, one glyph per key (A0 first).
+    ///
+    /// Octaves are separated before each C.
+    pub fn heatmap_string(&self) -> String {
+        let mut line = String::with_capacity(NOTE_COUNT + 8);
+
+        for (i, measured) in self.notes.iter().enumerate() {
+            if i > 0 && Self::note_at(i).is_some_and(|n| n.name == "C") {
+                line.push(HEATMAP_SEPARATOR);
+            }
+
+            line.push(match measured {
+                None => HEATMAP_MISSING,
+                Some(n) if n.cents.abs() <= 5.0 => HEATMAP_GOOD,
+                Some(n) if n.cents.abs() <= 15.0 => HEATMAP_WARNING,
+                Some(_) => HEATMAP_BAD,
+            });
+        }
+
+        line
+    }
+
     /// Get the profiles directory path.
     pub fn profiles_dir() -> Option<PathBuf> {
-        ProjectDirs::from("", "", "pianito").map(|dirs| dirs.data_dir().join("profiles"))
+        data_dir().map(|dir| dir.join("profiles"))
     }
 
     /// Get the path for this profile's file.
+    ///
+    /// Profiles saved under a bare timestamp ID keep their original name.
     fn profile_path(&self) -> Option<PathBuf> {
-        Self::profiles_dir().map(|dir| {
-            let safe_id = self.id.replace(':', "-");
-            dir.join(format!("{}.json", safe_id))
-        })
+        Self::profiles_dir().map(|dir| dir.join(format!("{}.json", file_stem(&self.id))))
     }
 
-    /// Save profile to disk.
+    /// Save profile to disk, replacing any previous copy atomically.
     pub fn save(&self) -> anyhow::Result<()> {
         let path = self
             .profile_path()
@@ -146,17 +187,24 @@ impl PianoProfile {
             fs::create_dir_all(parent)?;
         }
 
-        let json = serde_json::to_string_pretty(self)?;
-        fs::write(&path, json)?;
-
-        Ok(())
+        write_atomic(&path, |writer| self.write_to(writer))
     }
 
     /// Load a profile from a file path.
     pub fn load(path: impl AsRef<Path>) -> anyhow::Result<Self> {
-        let content = fs::read_to_string(path)?;
-        let profile: PianoProfile = serde_json::from_str(&content)?;
-        Ok(profile)
+        Self::read_from(BufReader::new(fs::File::open(path)?))
+    }
+
+    /// Write the profile as JSON to any writer.
+    pub fn write_to<W: Write>(&self, mut writer: W) -> anyhow::Result<()> {
+        serde_json::to_writer_pretty(&mut writer, self)?;
+        writer.flush()?;
+        Ok(())
+    }
+
+    /// Read a JSON profile from any reader.
+    pub fn read_from<R: Read>(reader: R) -> anyhow::Result<Self> {
+        Ok(serde_json::from_reader(reader)?)
     }
 
     /// List all saved profiles, most recent first.
@@ -183,7 +231,7 @@ impl PianoProfile {
             }
         }
 
-        profiles.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+        profiles.sort_by_key(|p| std::cmp::Reverse(p.created_at));
 
         Ok(profiles)
     }
@@ -213,6 +261,31 @@ impl Default for PianoProfile {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_profiles_made_together_get_distinct_paths() {
+        let paths: std::collections::HashSet<PathBuf> = (0..50)
+            .map(|_| PianoProfile::new().profile_path().unwrap())
+            .collect();
+        assert_eq!(paths.len(), 50);
+    }
+
+    #[test]
+    fn test_legacy_id_saves_back_to_its_file() {
+        let mut profile = PianoProfile::new();
+        profile.id = "2024-01-01T10:00:00.123456789+00:00".to_string();
+
+        let mut buffer = Vec::new();
+        profile.write_to(&mut buffer).expect("Should write");
+        let loaded = PianoProfile::read_from(buffer.as_slice()).expect("Should read");
+        assert_eq!(loaded.id, profile.id);
+
+        let path = loaded.profile_path().expect("Should have a path");
+        assert_eq!(
+            path.file_name().and_then(|n| n.to_str()),
+            Some("2024-01-01T10-00-00.123456789+00-00.json")
+        );
+    }
+
     #[test]
     fn test_new_profile() {
         let profile = PianoProfile::new();
@@ -221,6 +294,81 @@ mod tests {
         assert_eq!(profile.average_deviation(), 0.0);
     }
 
+    #[test]
+    fn test_write_read_roundtrip_in_memory() {
+        let mut profile = PianoProfile::new();
+        profile.record_note(69, 441.0, 3.9);
+
+        let mut buffer = Vec::new();
+        profile.write_to(&mut buffer).expect("Should write");
+        let restored = PianoProfile::read_from(buffer.as_slice()).expect("Should read");
+
+        assert_eq!(restored.progress(), (1, 88));
+        assert_eq!(restored.average_deviation(), profile.average_deviation());
+    }
+
+    #[test]
+    fn test_edited_stretch_round_trips() {
+        let mut curve = StretchCurve::new();
+        curve.set_point(90, 12.0);
+        let mut profile = PianoProfile::new();
+        profile.stretch = Some(curve.clone());
+
+        let mut buffer = Vec::new();
+        profile.write_to(&mut buffer).expect("Should write");
+        let restored = PianoProfile::read_from(buffer.as_slice()).expect("Should read");
+        assert_eq!(restored.stretch, Some(curve));
+
+        let mut plain = Vec::new();
+        PianoProfile::new()
+            .write_to(&mut plain)
+            .expect("Should write");
+        assert!(!String::from_utf8(plain).unwrap().contains("stretch"));
+    }
+
+    #[test]
+    fn test_heatmap_all_in_tune() {
+        let mut profile = PianoProfile::new();
+        for note in NOTES.iter() {
+            profile.record_note(note.midi, 0.0, 1.0);
+        }
+
+        let heatmap = profile.heatmap_string();
+        let keys: Vec<char> = heatmap
+            .chars()
+            .filter(|&c| c != HEATMAP_SEPARATOR)
+            .collect();
+        assert_eq!(keys.len(), NOTE_COUNT);
+        assert!(keys.iter().all(|&c| c == HEATMAP_GOOD));
+
+        // A0-B0, seven full octaves, then C8
+        assert_eq!(heatmap.split(HEATMAP_SEPARATOR).count(), 9);
+        assert!(heatmap.starts_with("===|"));
+        assert!(heatmap.ends_with("|="));
+    }
+
+    #[test]
+    fn test_heatmap_marks_bad_note() {
+        let mut profile = PianoProfile::new();
+        for note in NOTES.iter() {
+            profile.record_note(note.midi, 0.0, 0.0);
+        }
+        profile.record_note(69, 450.0, 38.9); // A4 way sharp
+        profile.record_note(70, 470.0, -8.0); // A#4 slightly flat
+
+        let heatmap = profile.heatmap_string();
+        let octave4 = heatmap.split(HEATMAP_SEPARATOR).nth(4).unwrap();
+        assert_eq!(octave4, "=========!~=");
+    }
+
+    #[test]
+    fn test_heatmap_unmeasured() {
+        let heatmap = PianoProfile::new().heatmap_string();
+        assert!(heatmap
+            .chars()
+            .all(|c| c == HEATMAP_MISSING || c == HEATMAP_SEPARATOR));
+    }
+
     #[test]
     fn test_record_note() {
         let mut profile = PianoProfile::new();
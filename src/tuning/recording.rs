@@ -0,0 +1,207 @@
+//! Note segmentation and before/after comparison of recordings.
+
+use crate::audio::gate::{rms, DEFAULT_CLOSE_RMS};
+use crate::audio::{AudioSource, PitchDetector};
+
+use super::temperament::Temperament;
+
+/// Length of each analysis block, in seconds.
+const BLOCK_SECS: f32 = 0.1;
+/// Blocks a note must hold for to count as a segment.
+const MIN_SEGMENT_BLOCKS: usize = 2;
+
+/// One note held in a recording.
+#[derive(Debug, Clone, PartialEq)]
+pub struct NoteSegment {
+    /// Nearest note.
+    pub midi: u8,
+    /// Median detected frequency in Hz.
+    pub frequency: f32,
+    /// Deviation from the note's target in cents.
+    pub cents: f32,
+    /// Analysis blocks in the segment.
+    pub blocks: usize,
+}
+
+/// Split a recording into the notes played, in order.
+///
+/// A segment runs while consecutive blocks detect the same note; silence or
+/// a different note ends it. Segments shorter than two blocks are dropped
+/// as attack transients.
+pub fn segment_notes(source: &mut impl AudioSource, temperament: &Temperament) -> Vec<NoteSegment> {
+    let detector = PitchDetector::new(source.sample_rate());
+    let mut buffer = vec![0.0f32; (source.sample_rate() as f32 * BLOCK_SECS) as usize];
+    let mut segments = Vec::new();
+    let mut current: Option<(u8, Vec<f32>)> = None;
+
+    loop {
+        let read = source.read_samples(&mut buffer);
+        if read == 0 {
+            break;
+        }
+        let block = &buffer[..read];
+
+        let detected = (rms(block) >= DEFAULT_CLOSE_RMS)
+            .then(|| detector.detect(block))
+            .flatten()
+            .map(|result| {
+                (
+                    temperament.nearest_note(result.frequency).0,
+                    result.frequency,
+                )
+            });
+
+        match (&mut current, detected) {
+            (Some((midi, freqs)), Some((m, freq))) if *midi == m => freqs.push(freq),
+            (_, detected) => {
+                if let Some((midi, freqs)) = current.take() {
+                    segments.extend(finish_segment(midi, freqs, temperament));
+                }
+                current = detected.map(|(m, freq)| (m, vec![freq]));
+            }
+        }
+    }
+    if let Some((midi, freqs)) = current {
+        segments.extend(finish_segment(midi, freqs, temperament));
+    }
+
+    segments
+}
+
+/// Turn a run of block frequencies into a segment, if it is long enough.
+fn finish_segment(midi: u8, mut freqs: Vec<f32>, temperament: &Temperament) -> Option<NoteSegment> {
+    if freqs.len() < MIN_SEGMENT_BLOCKS {
+        return None;
+    }
+    freqs.sort_by(|a, b| a.total_cmp(b));
+    let frequency = freqs[freqs.len() / 2];
+
+    Some(NoteSegment {
+        midi,
+        frequency,
+        cents: temperament.cents_from_target(frequency, temperament.frequency(midi)),
+        blocks: freqs.len(),
+    })
+}
+
+/// How one note moved between two recordings.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct NoteChange {
+    /// The note.
+    pub midi: u8,
+    /// Deviation before, in cents.
+    pub before_cents: f32,
+    /// Deviation after, in cents.
+    pub after_cents: f32,
+}
+
+impl NoteChange {
+    /// Cents the note moved (after minus before).
+    pub fn delta(&self) -> f32 {
+        self.after_cents - self.before_cents
+    }
+
+    /// Cents closer to the target the note ended up; negative if worse.
+    pub fn improvement(&self) -> f32 {
+        self.before_cents.abs() - self.after_cents.abs()
+    }
+}
+
+/// Pair up notes from two recordings of the same sequence.
+///
+/// Segments are matched by position; pairs that detected different notes
+/// are left out.
+pub fn compare_segments(before: &[NoteSegment], after: &[NoteSegment]) -> Vec<NoteChange> {
+    before
+        .iter()
+        .zip(after)
+        .filter(|(b, a)| b.midi == a.midi)
+        .map(|(b, a)| NoteChange {
+            midi: b.midi,
+            before_cents: b.cents,
+            after_cents: a.cents,
+        })
+        .collect()
+}
+
+/// Average improvement in cents over all compared notes.
+pub fn average_improvement(changes: &[NoteChange]) -> Option<f32> {
+    if changes.is_empty() {
+        return None;
+    }
+    Some(changes.iter().map(NoteChange::improvement).sum::<f32>() / changes.len() as f32)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::audio::{ReferenceTone, TestAudioSource};
+
+    const SAMPLE_RATE: u32 = 44100;
+
+    /// Notes held for half a second each with a short gap, at `cents` off.
+    fn sweep(notes: &[(u8, f32)]) -> TestAudioSource {
+        let temperament = Temperament::new();
+        let tone = ReferenceTone::new(SAMPLE_RATE);
+        let mut samples = Vec::new();
+        for &(midi, cents) in notes {
+            let freq = temperament.cents_to_frequency(temperament.frequency(midi), cents);
+            samples.extend(tone.generate(freq, 0.5).iter().map(|s| s * 0.5));
+            samples.extend(std::iter::repeat_n(0.0, SAMPLE_RATE as usize / 5));
+        }
+        TestAudioSource::new(samples, SAMPLE_RATE)
+    }
+
+    #[test]
+    fn test_segments_follow_played_notes() {
+        let temperament = Temperament::new();
+        let segments = segment_notes(&mut sweep(&[(57, 12.0), (60, -8.0)]), &temperament);
+
+        let notes: Vec<u8> = segments.iter().map(|s| s.midi).collect();
+        assert_eq!(notes, vec![57, 60]);
+        assert!((segments[0].cents - 12.0).abs() < 1.0, "{:?}", segments[0]);
+        assert!((segments[1].cents + 8.0).abs() < 1.0, "{:?}", segments[1]);
+    }
+
+    #[test]
+    fn test_silence_has_no_segments() {
+        let mut silence = TestAudioSource::new(vec![0.0; SAMPLE_RATE as usize], SAMPLE_RATE);
+        assert!(segment_notes(&mut silence, &Temperament::new()).is_empty());
+    }
+
+    #[test]
+    fn test_compare_before_and_after_sweeps() {
+        let temperament = Temperament::new();
+        let before = segment_notes(
+            &mut sweep(&[(57, 15.0), (60, -10.0), (64, 8.0)]),
+            &temperament,
+        );
+        let after = segment_notes(
+            &mut sweep(&[(57, 2.0), (60, -1.0), (64, 8.0)]),
+            &temperament,
+        );
+
+        let changes = compare_segments(&before, &after);
+        assert_eq!(changes.len(), 3);
+        assert!((changes[0].delta() + 13.0).abs() < 1.0, "{:?}", changes[0]);
+        assert!((changes[1].delta() - 9.0).abs() < 1.0, "{:?}", changes[1]);
+        assert!(changes[2].delta().abs() < 1.0, "{:?}", changes[2]);
+
+        // (13 + 9 + 0) / 3
+        let average = average_improvement(&changes).expect("notes compared");
+        assert!((average - 22.0 / 3.0).abs() < 1.0, "got {}", average);
+    }
+
+    #[test]
+    fn test_compare_skips_mismatched_notes() {
+        let segment = |midi| NoteSegment {
+            midi,
+            frequency: 0.0,
+            cents: 0.0,
+            blocks: 3,
+        };
+        let changes = compare_segments(&[segment(57), segment(60)], &[segment(57), segment(62)]);
+        assert_eq!(changes.len(), 1);
+        assert_eq!(average_improvement(&[]), None);
+    }
+}
@@ -0,0 +1,135 @@
+//! Targets relative to notes already tuned.
+//!
+//! Rather than tuning to an absolute reference, each note is set by its
+//! interval from a note already tuned, so the piano stays consistent with
+//! itself wherever its overall pitch sits.
+
+use std::collections::HashMap;
+
+use super::order::TuningOrder;
+use super::targets::TargetTable;
+
+/// Actual pitches of tuned notes, used to derive the next targets.
+#[derive(Debug, Clone, Default)]
+pub struct RelativeTargets {
+    /// Frequency each note was left at, by MIDI number.
+    pitches: HashMap<u8, f32>,
+}
+
+impl RelativeTargets {
+    /// Create an empty set; the first note tuned is taken where it is.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Rebuild from recorded results, in the order they were recorded, as
+    /// MIDI number and cents off the target each was tuned to.
+    ///
+    /// A note with nothing tuned before it is recorded against its own
+    /// entry in `table`, so its actual pitch can be recovered.
+    pub fn replay(results: impl IntoIterator<Item = (u8, f32)>, table: &TargetTable) -> Self {
+        let mut targets = Self::new();
+        for (midi, cents) in results {
+            let target = targets
+                .target(midi, table)
+                .unwrap_or_else(|| table.target(midi));
+            targets.record(midi, target * 2.0_f32.powf(cents / 1200.0));
+        }
+        targets
+    }
+
+    /// Record the frequency a note was left at.
+    pub fn record(&mut self, midi: u8, frequency: f32) {
+        if frequency.is_finite() && frequency > 0.0 {
+            self.pitches.insert(midi, frequency);
+        }
+    }
+
+    /// Frequency a note was left at, if recorded.
+    pub fn pitch(&self, midi: u8) -> Option<f32> {
+        self.pitches.get(&midi).copied()
+    }
+
+    /// The tuned note `midi` is set against, if any.
+    pub fn reference_for(&self, midi: u8) -> Option<u8> {
+        TuningOrder::reference_for(midi, |m| self.pitches.contains_key(&m))
+    }
+
+    /// Target for `midi`: the interval between the two notes in `table`,
+    /// taken from its reference note's actual pitch, or `None` with nothing
+    /// tuned to set it against.
+    pub fn target(&self, midi: u8, table: &TargetTable) -> Option<f32> {
+        self.reference_for(midi).map(|reference| {
+            self.pitches[&reference] * table.target(midi) / table.target(reference)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tuning::stretch::StretchCurve;
+    use crate::tuning::temperament::Temperament;
+
+    #[test]
+    fn test_first_note_has_no_target() {
+        let targets = RelativeTargets::new();
+        assert_eq!(targets.target(53, &TargetTable::default()), None);
+    }
+
+    #[test]
+    fn test_replay_rebuilds_pitches() {
+        let temperament = Temperament::new();
+
+        // F3 recorded 30 cents flat of the temperament, then F#3 2 cents
+        // sharp of its target a semitone above that
+        let targets = RelativeTargets::replay([(53, -30.0), (54, 2.0)], &TargetTable::default());
+        let f3 = temperament.cents_to_frequency(temperament.frequency(53), -30.0);
+        assert!((targets.pitch(53).unwrap() - f3).abs() < 0.01);
+        let fs3 = temperament.cents_to_frequency(temperament.frequency(54), -28.0);
+        assert!((targets.pitch(54).unwrap() - fs3).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_target_follows_neighbor_actual_pitch() {
+        let temperament = Temperament::new();
+        let table = TargetTable::default();
+        let mut targets = RelativeTargets::new();
+
+        // A4 left 20 cents flat: A5 is an octave above that, not 880 Hz
+        let flat_a4 = temperament.cents_to_frequency(440.0, -20.0);
+        targets.record(69, flat_a4);
+        assert!((targets.target(81, &table).unwrap() - flat_a4 * 2.0).abs() < 0.01);
+
+        // Retuning A4 moves A5's target with it
+        let sharp_a4 = temperament.cents_to_frequency(440.0, 10.0);
+        targets.record(69, sharp_a4);
+        assert!((targets.target(81, &table).unwrap() - sharp_a4 * 2.0).abs() < 0.01);
+
+        // A#4 sits a semitone above the actual A4
+        let expected = sharp_a4 * temperament.frequency(70) / 440.0;
+        assert!((targets.target(70, &table).unwrap() - expected).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_target_keeps_stretched_interval() {
+        let temperament = Temperament::new();
+        let stretch = StretchCurve::new();
+        let table = TargetTable::build(&temperament, Some(&stretch), 0.0);
+        let mut targets = RelativeTargets::new();
+
+        // C7 sits its stretched interval above the actual C6
+        targets.record(84, 1050.0);
+        let expected = 1050.0 * table.target(96) / table.target(84);
+        assert!((targets.target(96, &table).unwrap() - expected).abs() < 0.01);
+        assert!(expected > 2100.0);
+    }
+
+    #[test]
+    fn test_ignores_invalid_pitch() {
+        let mut targets = RelativeTargets::new();
+        targets.record(69, f32::NAN);
+        targets.record(69, 0.0);
+        assert_eq!(targets.pitch(69), None);
+    }
+}
@@ -1,10 +1,22 @@
 //! Session state and persistence.
 
 use chrono::{DateTime, Utc};
-use directories::ProjectDirs;
 use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::Write as _;
 use std::fs;
-use std::path::PathBuf;
+use std::io::{BufReader, Read, Write};
+use std::path::{Path, PathBuf};
+
+use super::atomic::write_atomic;
+use super::id::{file_stem, unique_id};
+use super::notes::{Note, NOTES};
+use super::order::DEFAULT_TEMPERAMENT_START;
+use super::profile::PianoProfile;
+use super::storage::data_dir;
+use super::stretch::StretchCurve;
+use super::temperament::{Temperament, TuningName};
+use super::training::score_for_error;
 
 /// Tuning mode.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
@@ -17,6 +29,63 @@ pub enum TuningMode {
     Concert,
     /// Profile mode: measure all 88 keys to determine tuning priority.
     Profile,
+    /// Relative tune: each note set by interval from notes already tuned.
+    Relative,
+}
+
+/// Octaves spanned by the keyboard (A0-B0 through C8).
+pub const OCTAVE_COUNT: usize = 9;
+
+/// How a recorded note was finished.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NoteStatus {
+    /// Tuned to the target.
+    #[default]
+    Tuned,
+    /// Skipped without tuning.
+    Skipped,
+    /// Judged acceptable and left as it was.
+    LeftAsIs,
+}
+
+impl NoteStatus {
+    /// Short label for reports.
+    pub fn label(self) -> &'static str {
+        match self {
+            NoteStatus::Tuned => "tuned",
+            NoteStatus::Skipped => "skipped",
+            NoteStatus::LeftAsIs => "left as is",
+        }
+    }
+}
+
+/// What happened in a session event.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum SessionEventKind {
+    /// A note came up for tuning.
+    NoteEntered { note: String },
+    /// A note was tuned and confirmed.
+    NoteConfirmed { note: String, cents: f32 },
+    /// A note was judged acceptable and left as it was.
+    NoteLeftAsIs { note: String, cents: f32 },
+    /// A note was skipped without tuning.
+    NoteSkipped { note: String },
+    /// The piano's offset from concert pitch was set.
+    OffsetChanged { cents: f32 },
+    /// The A4 reference changed partway through.
+    A4Changed { a4: f32 },
+}
+
+/// A timestamped entry in a session's event log.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SessionEvent {
+    /// When it happened.
+    pub timestamp: DateTime<Utc>,
+    /// What happened.
+    #[serde(flatten)]
+    pub kind: SessionEventKind,
 }
 
 /// A completed note in a tuning session.
@@ -26,6 +95,15 @@ pub struct CompletedNote {
     pub note: String,
     /// Final cents deviation from target.
     pub final_cents: f32,
+    /// How the note was finished.
+    #[serde(default)]
+    pub status: NoteStatus,
+    /// Tuner's remark, e.g. "sticky key".
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub comment: Option<String>,
+    /// Tuned against an A4 reference that has since changed.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub needs_review: bool,
     /// Timestamp when completed.
     pub timestamp: DateTime<Utc>,
 }
@@ -36,26 +114,59 @@ impl CompletedNote {
         Self {
             note: note.into(),
             final_cents,
+            status: NoteStatus::Tuned,
+            comment: None,
+            needs_review: false,
             timestamp: Utc::now(),
         }
     }
+
+    /// Set how the note was finished.
+    pub fn with_status(mut self, status: NoteStatus) -> Self {
+        self.status = status;
+        self
+    }
+
+    /// Attach a remark to the note.
+    pub fn with_comment(mut self, comment: impl Into<String>) -> Self {
+        self.comment = Some(comment.into());
+        self
+    }
 }
 
 /// A tuning session.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Session {
-    /// Unique session ID (ISO 8601 timestamp).
+    /// Unique session ID (ISO 8601 timestamp with a uniqueness suffix).
     pub id: String,
     /// Tuning mode.
     pub mode: TuningMode,
     /// A4 reference frequency.
     pub a4_reference: f32,
+    /// Tuning system the targets follow.
+    #[serde(default)]
+    pub temperament: TuningName,
+    /// Label for the instrument, e.g. "Studio Steinway".
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub piano: Option<String>,
+    /// Stretch curve the targets follow, including any hand edits.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stretch: Option<StretchCurve>,
+    /// MIDI note the temperament octave started from, if not F3.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub temperament_start: Option<u8>,
     /// Piano's offset from concert pitch in cents (for quick tune).
     pub piano_offset_cents: f32,
     /// Current note index in tuning order.
     pub current_note_index: usize,
     /// Completed notes.
     pub completed_notes: Vec<CompletedNote>,
+    /// Deviations measured before tuning, by note name (from a profile).
+    #[serde(default)]
+    pub starting_cents: HashMap<String, f32>,
+    /// Everything that happened, oldest first, for replaying or auditing.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub events: Vec<SessionEvent>,
     /// Session creation time.
     pub created_at: DateTime<Utc>,
     /// Last update time.
@@ -67,12 +178,18 @@ impl Session {
     pub fn new(mode: TuningMode, a4_reference: f32) -> Self {
         let now = Utc::now();
         Self {
-            id: now.to_rfc3339(),
+            id: unique_id(now),
             mode,
             a4_reference,
+            temperament: TuningName::default(),
+            piano: None,
+            stretch: None,
+            temperament_start: None,
             piano_offset_cents: 0.0,
             current_note_index: 0,
             completed_notes: Vec::new(),
+            starting_cents: HashMap::new(),
+            events: Vec::new(),
             created_at: now,
             updated_at: now,
         }
@@ -90,6 +207,90 @@ impl Session {
         Self::new(TuningMode::Concert, a4_reference)
     }
 
+    /// Create a session seeded with a profile's measured deviations.
+    pub fn from_profile(profile: &PianoProfile, mode: TuningMode, a4_reference: f32) -> Self {
+        let mut session = Self::new(mode, a4_reference);
+        session.starting_cents = profile
+            .notes
+            .iter()
+            .flatten()
+            .filter_map(|p| Note::from_midi(p.midi).map(|n| (n.display_name(), p.cents)))
+            .collect();
+        session.stretch = profile.stretch.clone();
+        session
+    }
+
+    /// Create a concert pitch session that starts each profiled note at its
+    /// distance from concert pitch, so tuning shows how far and which way
+    /// every note must move.
+    ///
+    /// Deviations are measured from each note's recorded frequency, so a
+    /// profile taken against another reference still lands on `a4`.
+    pub fn from_profile_to_concert(profile: &PianoProfile, a4: f32) -> Self {
+        let concert = Temperament::with_a4(a4);
+        let mut session = Self::new(TuningMode::Concert, a4);
+        session.starting_cents = profile
+            .notes
+            .iter()
+            .flatten()
+            .filter_map(|p| {
+                let note = Note::from_midi(p.midi)?;
+                let stretch = profile
+                    .stretch
+                    .as_ref()
+                    .map_or(0.0, |s| s.offset_cents(p.midi));
+                Some((
+                    note.display_name(),
+                    concert.frequency_to_cents(p.frequency, p.midi) - stretch,
+                ))
+            })
+            .collect();
+        session.stretch = profile.stretch.clone();
+        session
+    }
+
+    /// Label the instrument being tuned.
+    pub fn with_piano(mut self, piano: impl Into<String>) -> Self {
+        self.piano = Some(piano.into());
+        self
+    }
+
+    /// Tune to a stretch curve, saved with the session.
+    pub fn with_stretch(mut self, stretch: StretchCurve) -> Self {
+        self.stretch = Some(stretch);
+        self
+    }
+
+    /// Record the note the temperament octave starts from.
+    pub fn with_temperament_start(mut self, midi: u8) -> Self {
+        self.temperament_start = (midi != DEFAULT_TEMPERAMENT_START).then_some(midi);
+        self
+    }
+
+    /// Get the deviation a note started at, if it was profiled.
+    pub fn starting_cents_for(&self, note_name: &str) -> Option<f32> {
+        self.starting_cents.get(note_name).copied()
+    }
+
+    /// Get the latest deviation a note was left at, unless it was skipped.
+    pub fn final_cents_for(&self, note_name: &str) -> Option<f32> {
+        self.completed_notes
+            .iter()
+            .rev()
+            .find(|n| n.note == note_name)
+            .filter(|n| n.status != NoteStatus::Skipped)
+            .map(|n| n.final_cents)
+    }
+
+    /// Get how a note was last finished, if it has been recorded.
+    pub fn status_for(&self, note_name: &str) -> Option<NoteStatus> {
+        self.completed_notes
+            .iter()
+            .rev()
+            .find(|n| n.note == note_name)
+            .map(|n| n.status)
+    }
+
     /// Check if the session is complete.
     pub fn is_complete(&self) -> bool {
         self.current_note_index >= 88
@@ -97,33 +298,210 @@ impl Session {
 
     /// Mark a note as completed.
     pub fn complete_note(&mut self, note_name: impl Into<String>, final_cents: f32) {
+        self.record_note(note_name, final_cents, NoteStatus::Tuned);
+    }
+
+    /// Move a note's latest result into the slot of its first one, so a
+    /// revisit replaces the old result instead of adding another.
+    pub fn fold_latest(&mut self, note_name: &str) {
+        let Some(first) = self
+            .completed_notes
+            .iter()
+            .position(|n| n.note == note_name)
+        else {
+            return;
+        };
+        if first + 1 < self.completed_notes.len()
+            && self
+                .completed_notes
+                .last()
+                .is_some_and(|n| n.note == note_name)
+        {
+            if let Some(latest) = self.completed_notes.pop() {
+                self.completed_notes[first] = latest;
+            }
+        }
+    }
+
+    /// Replace the latest result with a fresh confirm of the same note.
+    ///
+    /// The note's confirm event is updated in place, so the log keeps a
+    /// single confirm for the visit.
+    pub fn reconfirm_last(&mut self, final_cents: f32) {
+        let Some(last) = self.completed_notes.last_mut() else {
+            return;
+        };
+        let now = Utc::now();
+        last.final_cents = final_cents;
+        last.status = NoteStatus::Tuned;
+        last.timestamp = now;
+        let note_name = last.note.clone();
+
+        let confirm = self.events.iter_mut().rev().find(|event| {
+            matches!(&event.kind, SessionEventKind::NoteConfirmed { note, .. } if *note == note_name)
+        });
+        if let Some(event) = confirm {
+            event.kind = SessionEventKind::NoteConfirmed {
+                note: note_name,
+                cents: final_cents,
+            };
+        }
+        self.updated_at = now;
+    }
+
+    /// Record a note with the given status and move to the next one.
+    pub fn record_note(
+        &mut self,
+        note_name: impl Into<String>,
+        final_cents: f32,
+        status: NoteStatus,
+    ) {
+        let note = note_name.into();
+        self.log(match status {
+            NoteStatus::Tuned => SessionEventKind::NoteConfirmed {
+                note: note.clone(),
+                cents: final_cents,
+            },
+            NoteStatus::LeftAsIs => SessionEventKind::NoteLeftAsIs {
+                note: note.clone(),
+                cents: final_cents,
+            },
+            NoteStatus::Skipped => SessionEventKind::NoteSkipped { note: note.clone() },
+        });
         self.completed_notes
-            .push(CompletedNote::new(note_name, final_cents));
+            .push(CompletedNote::new(note, final_cents).with_status(status));
         self.current_note_index += 1;
         self.updated_at = Utc::now();
     }
 
+    /// Log that a note came up for tuning.
+    pub fn enter_note(&mut self, note_name: impl Into<String>) {
+        self.log(SessionEventKind::NoteEntered {
+            note: note_name.into(),
+        });
+    }
+
+    /// Set the piano's offset from concert pitch.
+    pub fn set_offset(&mut self, cents: f32) {
+        self.piano_offset_cents = cents;
+        self.log(SessionEventKind::OffsetChanged { cents });
+        self.updated_at = Utc::now();
+    }
+
+    /// Change the A4 reference partway through. Notes still to come use the
+    /// new reference; notes already tuned or left as is are flagged for
+    /// review, since they were judged against the old one.
+    pub fn set_a4(&mut self, a4: f32) {
+        if a4 == self.a4_reference {
+            return;
+        }
+        self.a4_reference = a4;
+        for note in &mut self.completed_notes {
+            if note.status != NoteStatus::Skipped {
+                note.needs_review = true;
+            }
+        }
+        self.log(SessionEventKind::A4Changed { a4 });
+        self.updated_at = Utc::now();
+    }
+
+    /// Names of recorded notes tuned against an earlier A4, lowest first.
+    pub fn notes_for_review(&self) -> Vec<&str> {
+        self.latest_results()
+            .into_values()
+            .filter(|n| n.needs_review)
+            .map(|n| n.note.as_str())
+            .collect()
+    }
+
+    /// Append an event to the log.
+    fn log(&mut self, kind: SessionEventKind) {
+        self.events.push(SessionEvent {
+            timestamp: Utc::now(),
+            kind,
+        });
+    }
+
+    /// Set or clear the remark on the latest recorded note.
+    pub fn comment_last(&mut self, comment: Option<&str>) {
+        if let Some(note) = self.completed_notes.last_mut() {
+            note.comment = comment.map(str::to_string);
+            self.updated_at = Utc::now();
+        }
+    }
+
+    /// Find the next completed note still outside `tolerance` cents.
+    ///
+    /// Searches `completed_notes` from index `from`, wrapping around. Only the
+    /// latest result for each note counts, so a re-tuned note is not revisited,
+    /// and notes left as-is are accepted.
+    pub fn next_out_of_tolerance(&self, from: usize, tolerance: f32) -> Option<usize> {
+        let len = self.completed_notes.len();
+        (0..len).map(|i| (from + i) % len).find(|&i| {
+            let note = &self.completed_notes[i];
+            let superseded = self.completed_notes[i + 1..]
+                .iter()
+                .any(|later| later.note == note.note);
+            !superseded && note.status != NoteStatus::LeftAsIs && note.final_cents.abs() > tolerance
+        })
+    }
+
     /// Skip to the next note without recording completion.
     pub fn skip_note(&mut self) {
         self.current_note_index += 1;
         self.updated_at = Utc::now();
     }
 
+    /// End the session now, leaving unvisited notes unrecorded.
+    pub fn finish(&mut self) {
+        self.current_note_index = self.current_note_index.max(88);
+        self.updated_at = Utc::now();
+    }
+
+    /// Number of notes with no recorded result.
+    pub fn untuned_count(&self) -> usize {
+        let recorded: std::collections::HashSet<&str> = self
+            .completed_notes
+            .iter()
+            .map(|n| n.note.as_str())
+            .collect();
+        88usize.saturating_sub(recorded.len())
+    }
+
+    /// Completed and total notes per octave, as (octave, completed, total).
+    pub fn progress_by_octave(&self) -> [(i8, usize, usize); OCTAVE_COUNT] {
+        let recorded: std::collections::HashSet<&str> = self
+            .completed_notes
+            .iter()
+            .map(|n| n.note.as_str())
+            .collect();
+
+        let mut progress: [(i8, usize, usize); OCTAVE_COUNT] =
+            std::array::from_fn(|i| (i as i8, 0, 0));
+        for note in NOTES.iter() {
+            let entry = &mut progress[note.octave as usize];
+            entry.2 += 1;
+            if recorded.contains(note.display_name().as_str()) {
+                entry.1 += 1;
+            }
+        }
+        progress
+    }
+
     /// Get the sessions directory path.
     fn sessions_dir() -> Option<PathBuf> {
-        ProjectDirs::from("", "", "pianito").map(|dirs| dirs.data_dir().join("sessions"))
+        data_dir().map(|dir| dir.join("sessions"))
     }
 
     /// Get the path for this session's file.
     fn session_path(&self) -> Option<PathBuf> {
         Self::sessions_dir().map(|dir| {
             // Sanitize the ID for use as filename
-            let safe_id = self.id.replace(':', "-");
-            dir.join(format!("{}.json", safe_id))
+            dir.join(format!("{}.json", file_stem(&self.id)))
         })
     }
 
-    /// Save session to disk.
+    /// Save session to disk, replacing any previous copy atomically.
     pub fn save(&self) -> anyhow::Result<()> {
         let path = self
             .session_path()
@@ -134,81 +512,92 @@ impl Session {
             fs::create_dir_all(parent)?;
         }
 
-        let json = serde_json::to_string_pretty(self)?;
-        fs::write(&path, json)?;
-
-        Ok(())
+        write_atomic(&path, |writer| self.write_to(writer))
     }
 
     /// Load a session from a file path.
     pub fn load(path: impl AsRef<std::path::Path>) -> anyhow::Result<Self> {
-        let content = fs::read_to_string(path)?;
-        let session: Session = serde_json::from_str(&content)?;
-        Ok(session)
+        Self::read_from(BufReader::new(fs::File::open(path)?))
+    }
+
+    /// Write the session as JSON to any writer.
+    pub fn write_to<W: Write>(&self, mut writer: W) -> anyhow::Result<()> {
+        serde_json::to_writer_pretty(&mut writer, self)?;
+        writer.flush()?;
+        Ok(())
+    }
+
+    /// Read a JSON session from any reader.
+    pub fn read_from<R: Read>(reader: R) -> anyhow::Result<Self> {
+        Ok(serde_json::from_reader(reader)?)
     }
 
     /// Load the most recent incomplete session.
     pub fn load_recent() -> anyhow::Result<Option<Self>> {
-        let sessions_dir = match Self::sessions_dir() {
-            Some(dir) => dir,
-            None => return Ok(None),
-        };
+        Ok(Self::list_incomplete()?.into_iter().next())
+    }
 
-        if !sessions_dir.exists() {
-            return Ok(None);
+    /// List incomplete sessions, most recently updated first.
+    pub fn list_incomplete() -> anyhow::Result<Vec<Session>> {
+        match Self::sessions_dir() {
+            Some(dir) => Self::incomplete_in(&dir),
+            None => Ok(Vec::new()),
         }
+    }
 
-        let mut sessions: Vec<(PathBuf, Session)> = Vec::new();
+    /// List all sessions, most recent first.
+    pub fn list_all() -> anyhow::Result<Vec<Session>> {
+        let mut sessions = match Self::sessions_dir() {
+            Some(dir) => Self::load_dir(&dir)?,
+            None => return Ok(Vec::new()),
+        };
 
-        for entry in fs::read_dir(&sessions_dir)? {
-            let entry = entry?;
-            let path = entry.path();
+        // Sort by created_at descending
+        sessions.sort_by_key(|s| std::cmp::Reverse(s.created_at));
 
-            if path.extension().is_some_and(|ext| ext == "json") {
-                if let Ok(session) = Self::load(&path) {
-                    if !session.is_complete() {
-                        sessions.push((path, session));
-                    }
-                }
-            }
-        }
+        Ok(sessions)
+    }
+
+    /// Incomplete sessions saved in `dir`, most recently updated first.
+    fn incomplete_in(dir: &Path) -> anyhow::Result<Vec<Session>> {
+        let mut sessions = Self::load_dir(dir)?;
+        sessions.retain(|s| !s.is_complete());
 
         // Sort by updated_at descending
-        sessions.sort_by(|a, b| b.1.updated_at.cmp(&a.1.updated_at));
+        sessions.sort_by_key(|s| std::cmp::Reverse(s.updated_at));
 
-        Ok(sessions.into_iter().next().map(|(_, s)| s))
+        Ok(sessions)
     }
 
-    /// List all sessions, most recent first.
-    pub fn list_all() -> anyhow::Result<Vec<Session>> {
-        let sessions_dir = match Self::sessions_dir() {
-            Some(dir) => dir,
-            None => return Ok(Vec::new()),
-        };
-
-        if !sessions_dir.exists() {
+    /// Every readable session file in `dir`, in no particular order.
+    fn load_dir(dir: &Path) -> anyhow::Result<Vec<Session>> {
+        if !dir.exists() {
             return Ok(Vec::new());
         }
 
-        let mut sessions: Vec<Session> = Vec::new();
-
-        for entry in fs::read_dir(&sessions_dir)? {
-            let entry = entry?;
-            let path = entry.path();
-
+        let mut sessions = Vec::new();
+        for entry in fs::read_dir(dir)? {
+            let path = entry?.path();
             if path.extension().is_some_and(|ext| ext == "json") {
                 if let Ok(session) = Self::load(&path) {
                     sessions.push(session);
                 }
             }
         }
-
-        // Sort by created_at descending
-        sessions.sort_by(|a, b| b.created_at.cmp(&a.created_at));
-
         Ok(sessions)
     }
 
+    /// One-line summary for choosing between sessions, e.g.
+    /// "Studio Steinway, 2024-01-01 10:00, 35% done".
+    pub fn summary(&self) -> String {
+        format!(
+            "{}, {}, {:.0}% done",
+            self.piano.as_deref().unwrap_or("(unlabelled)"),
+            self.updated_at.format("%Y-%m-%d %H:%M"),
+            self.progress_percent()
+        )
+    }
+
     /// Delete this session file.
     pub fn delete(&self) -> anyhow::Result<()> {
         if let Some(path) = self.session_path() {
@@ -248,6 +637,85 @@ impl Session {
         sum / self.completed_notes.len() as f32
     }
 
+    /// Tuning score (0-100): the average score of the tuned notes' final errors.
+    ///
+    /// Only the latest result for each note counts. `None` if nothing was tuned.
+    pub fn score(&self) -> Option<u32> {
+        let scores: Vec<u32> = self
+            .latest_results()
+            .values()
+            .filter(|n| n.status == NoteStatus::Tuned)
+            .map(|n| score_for_error(n.final_cents))
+            .collect();
+        if scores.is_empty() {
+            return None;
+        }
+        Some((scores.iter().sum::<u32>() as f32 / scores.len() as f32).round() as u32)
+    }
+
+    /// Latest result for each recorded note, keyed by MIDI number.
+    fn latest_results(&self) -> BTreeMap<u8, &CompletedNote> {
+        self.completed_notes
+            .iter()
+            .filter_map(|n| Note::from_name(&n.note).map(|note| (note.midi, n)))
+            .collect()
+    }
+
+    /// Plain-text report of the session, for printing or handing to a client.
+    pub fn report_string(&self) -> String {
+        let mut report = String::new();
+        let piano = self.piano.as_deref().unwrap_or("(unlabelled)");
+        let _ = writeln!(report, "Piano tuning report");
+        let _ = writeln!(report, "{}", "=".repeat(40));
+        let _ = writeln!(report, "Piano:        {}", piano);
+        let _ = writeln!(
+            report,
+            "Date:         {}",
+            self.created_at.format("%Y-%m-%d %H:%M")
+        );
+        let _ = writeln!(report, "Mode:         {:?}", self.mode);
+        let _ = writeln!(report, "A4:           {:.1} Hz", self.a4_reference);
+        let _ = writeln!(report, "Temperament:  {}", self.temperament.display_name());
+
+        let results = self.latest_results();
+        if results.is_empty() {
+            let _ = writeln!(report, "\nNo notes recorded.");
+        }
+        let mut octave = None;
+        for (&midi, result) in &results {
+            let note_octave = Note::from_midi(midi).map(|n| n.octave);
+            if note_octave != octave {
+                octave = note_octave;
+                let _ = writeln!(report, "\nOctave {}", octave.unwrap_or_default());
+            }
+            let mut line = format!(
+                "  {:<4} {:>+6.1} cents  {}",
+                result.note,
+                result.final_cents,
+                result.status.label()
+            );
+            if result.needs_review {
+                line.push_str(" (review)");
+            }
+            if let Some(comment) = &result.comment {
+                let _ = write!(line, " - {}", comment);
+            }
+            let _ = writeln!(report, "{}", line);
+        }
+
+        let _ = writeln!(report);
+        let _ = writeln!(
+            report,
+            "Average deviation: {:.1} cents",
+            self.average_deviation()
+        );
+        let score = self
+            .score()
+            .map_or_else(|| "-".to_string(), |score| format!("{}/100", score));
+        let _ = writeln!(report, "Score: {}", score);
+        report
+    }
+
     /// Get progress as a percentage.
     pub fn progress_percent(&self) -> f32 {
         (self.current_note_index as f32 / 88.0) * 100.0
@@ -273,6 +741,14 @@ mod tests {
         assert!(!session.is_complete());
     }
 
+    #[test]
+    fn test_back_to_back_sessions_are_distinct() {
+        let first = create_test_session();
+        let second = create_test_session();
+        assert_ne!(first.id, second.id);
+        assert_ne!(first.session_path(), second.session_path());
+    }
+
     #[test]
     fn test_quick_tune_session() {
         let session = Session::quick_tune(-15.0);
@@ -287,6 +763,115 @@ mod tests {
         assert_eq!(session.a4_reference, 442.0);
     }
 
+    #[test]
+    fn test_from_profile_seeds_starting_cents() {
+        let mut profile = PianoProfile::new();
+        profile.record_note(69, 442.0, 7.85); // A4
+        profile.record_note(53, 173.0, -12.0); // F3
+
+        let session = Session::from_profile(&profile, TuningMode::Profile, 440.0);
+        assert_eq!(session.mode, TuningMode::Profile);
+        assert_eq!(session.starting_cents.len(), 2);
+        assert_eq!(session.starting_cents_for("A4"), Some(7.85));
+        assert_eq!(session.starting_cents_for("F3"), Some(-12.0));
+        assert_eq!(session.starting_cents_for("C4"), None);
+    }
+
+    #[test]
+    fn test_from_profile_to_concert_seeds_drift() {
+        let temperament = Temperament::new();
+        let mut profile = PianoProfile::new();
+        for (midi, cents) in [(69, 7.85), (53, -12.0), (100, 3.5)] {
+            let freq = temperament.cents_to_frequency(temperament.frequency(midi), cents);
+            profile.record_note(midi, freq, cents);
+        }
+
+        let session = Session::from_profile_to_concert(&profile, 440.0);
+        assert_eq!(session.mode, TuningMode::Concert);
+        assert_eq!(session.a4_reference, 440.0);
+        assert_eq!(session.piano_offset_cents, 0.0);
+        for (name, cents) in [("A4", 7.85), ("F3", -12.0), ("E7", 3.5)] {
+            let start = session.starting_cents_for(name).unwrap();
+            assert!((start - cents).abs() < 0.01, "{name}: {start} != {cents}");
+        }
+        assert_eq!(session.starting_cents_for("C4"), None);
+    }
+
+    #[test]
+    fn test_from_profile_carries_its_stretch() {
+        let temperament = Temperament::new();
+        let mut curve = StretchCurve::new();
+        curve.set_point(100, 15.0);
+        let mut profile = PianoProfile::new();
+        profile.stretch = Some(curve.clone());
+
+        // E7 measured right on its stretched target
+        let freq = temperament.cents_to_frequency(temperament.frequency(100), 15.0);
+        profile.record_note(100, freq, 0.0);
+
+        let session = Session::from_profile(&profile, TuningMode::Profile, 440.0);
+        assert_eq!(session.stretch.as_ref(), Some(&curve));
+        let session = Session::from_profile_to_concert(&profile, 440.0);
+        assert_eq!(session.stretch.as_ref(), Some(&curve));
+        assert!(session.starting_cents_for("E7").unwrap().abs() < 0.01);
+    }
+
+    #[test]
+    fn test_from_profile_to_concert_measures_from_concert_a4() {
+        // Profiled against A4 = 435, a note on its target there is flat of 440
+        let mut profile = PianoProfile::new();
+        profile.record_note(69, 435.0, 0.0);
+
+        let session = Session::from_profile_to_concert(&profile, 440.0);
+        let start = session.starting_cents_for("A4").unwrap();
+        let expected = 1200.0 * (435.0f32 / 440.0).log2();
+        assert!((start - expected).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_starting_cents_optional_in_saved_sessions() {
+        let json = serde_json::to_string(&create_test_session()).unwrap();
+        let mut value: serde_json::Value = serde_json::from_str(&json).unwrap();
+        value.as_object_mut().unwrap().remove("starting_cents");
+
+        let session: Session = serde_json::from_value(value).expect("Should load old sessions");
+        assert!(session.starting_cents.is_empty());
+    }
+
+    #[test]
+    fn test_edited_stretch_round_trips() {
+        let mut curve = StretchCurve::new();
+        curve.set_point(90, 12.0);
+        let session = create_test_session().with_stretch(curve.clone());
+
+        let json = serde_json::to_string(&session).unwrap();
+        let restored: Session = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored.stretch, Some(curve));
+
+        let plain = serde_json::to_string(&create_test_session()).unwrap();
+        assert!(!plain.contains("stretch"));
+    }
+
+    #[test]
+    fn test_progress_by_octave() {
+        let mut session = create_test_session();
+        for name in ["A0", "A#0", "B0", "C1"] {
+            session.complete_note(name, 0.5);
+        }
+
+        let progress = session.progress_by_octave();
+        assert_eq!(progress[0], (0, 3, 3));
+        assert_eq!(progress[1], (1, 1, 12));
+        for &(octave, completed, total) in &progress[2..] {
+            assert_eq!(completed, 0, "octave {octave} should be untouched");
+            assert!(total > 0);
+        }
+        assert_eq!(progress[8], (8, 0, 1));
+
+        let total: usize = progress.iter().map(|p| p.2).sum();
+        assert_eq!(total, 88);
+    }
+
     #[test]
     fn test_complete_note() {
         let mut session = create_test_session();
@@ -298,6 +883,104 @@ mod tests {
         assert_eq!(session.completed_notes[0].final_cents, 1.5);
     }
 
+    #[test]
+    fn test_fold_latest_replaces_first_result() {
+        let mut session = create_test_session();
+        session.complete_note("F3", 12.0);
+        session.complete_note("F#3", 0.0);
+        session.complete_note("F3", 0.5);
+        session.fold_latest("F3");
+
+        let notes: Vec<_> = session
+            .completed_notes
+            .iter()
+            .map(|n| (n.note.as_str(), n.final_cents))
+            .collect();
+        assert_eq!(notes, [("F3", 0.5), ("F#3", 0.0)]);
+
+        // A note recorded once is left alone
+        session.fold_latest("F#3");
+        assert_eq!(session.completed_notes.len(), 2);
+    }
+
+    #[test]
+    fn test_reconfirm_replaces_result_and_event() {
+        let mut session = create_test_session();
+        session.enter_note("F3");
+        session.complete_note("F3", 4.0);
+        session.reconfirm_last(0.5);
+
+        assert_eq!(session.current_note_index, 1);
+        assert_eq!(session.completed_notes.len(), 1);
+        assert_eq!(session.completed_notes[0].final_cents, 0.5);
+        let confirms: Vec<_> = session
+            .events
+            .iter()
+            .filter_map(|e| match &e.kind {
+                SessionEventKind::NoteConfirmed { cents, .. } => Some(*cents),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(confirms, [0.5]);
+    }
+
+    #[test]
+    fn test_note_status_round_trip() {
+        let mut session = create_test_session();
+        session.complete_note("F3", 1.5);
+        session.record_note("F#3", 0.0, NoteStatus::Skipped);
+        session.record_note("G3", 8.0, NoteStatus::LeftAsIs);
+
+        let json = serde_json::to_string(&session).unwrap();
+        let restored: Session = serde_json::from_str(&json).unwrap();
+        let statuses: Vec<NoteStatus> = restored.completed_notes.iter().map(|n| n.status).collect();
+        assert_eq!(
+            statuses,
+            [NoteStatus::Tuned, NoteStatus::Skipped, NoteStatus::LeftAsIs]
+        );
+    }
+
+    #[test]
+    fn test_note_status_defaults_to_tuned() {
+        let json = r#"{"note":"F3","final_cents":1.5,"timestamp":"2024-01-01T00:00:00Z"}"#;
+        let note: CompletedNote = serde_json::from_str(json).expect("Should load old notes");
+        assert_eq!(note.status, NoteStatus::Tuned);
+    }
+
+    #[test]
+    fn test_comment_round_trips_through_save_and_load() {
+        let temp_dir = TempDir::new().expect("Should create temp dir");
+        let path = temp_dir.path().join("session.json");
+
+        let mut session = create_test_session();
+        session.complete_note("F3", 1.5);
+        session.complete_note("F5", 0.5);
+        session.completed_notes[1].comment = Some("false beat on F5".to_string());
+        session
+            .write_to(fs::File::create(&path).expect("Should create file"))
+            .expect("Should write");
+
+        let restored = Session::load(&path).expect("Should load");
+        assert_eq!(restored.completed_notes[0].comment, None);
+        assert_eq!(
+            restored.completed_notes[1].comment.as_deref(),
+            Some("false beat on F5")
+        );
+
+        // Old sessions without comments still load
+        let json = r#"{"note":"F3","final_cents":1.5,"timestamp":"2024-01-01T00:00:00Z"}"#;
+        let note: CompletedNote = serde_json::from_str(json).expect("Should load old notes");
+        assert_eq!(note.comment, None);
+    }
+
+    #[test]
+    fn test_left_as_is_not_out_of_tolerance() {
+        let mut session = create_test_session();
+        session.record_note("F3", 12.0, NoteStatus::LeftAsIs);
+        session.complete_note("F#3", 12.0);
+        assert_eq!(session.next_out_of_tolerance(0, 5.0), Some(1));
+    }
+
     #[test]
     fn test_skip_note() {
         let mut session = create_test_session();
@@ -307,6 +990,18 @@ mod tests {
         assert!(session.completed_notes.is_empty());
     }
 
+    #[test]
+    fn test_finish_marks_complete() {
+        let mut session = create_test_session();
+        session.complete_note("F3", 1.0);
+        session.complete_note("F#3", -1.0);
+        session.finish();
+
+        assert!(session.is_complete());
+        assert_eq!(session.completed_notes.len(), 2);
+        assert_eq!(session.untuned_count(), 86);
+    }
+
     #[test]
     fn test_is_complete() {
         let mut session = create_test_session();
@@ -360,6 +1055,55 @@ mod tests {
         assert_eq!(restored.completed_notes.len(), 2);
     }
 
+    #[test]
+    fn test_next_out_of_tolerance_wraps() {
+        let mut session = create_test_session();
+        session.complete_note("F3", 1.0);
+        session.complete_note("F#3", 12.0);
+        session.complete_note("G3", 0.5);
+        session.complete_note("G#3", -9.0);
+
+        assert_eq!(session.next_out_of_tolerance(0, 5.0), Some(1));
+        assert_eq!(session.next_out_of_tolerance(2, 5.0), Some(3));
+        assert_eq!(session.next_out_of_tolerance(4, 5.0), Some(1));
+    }
+
+    #[test]
+    fn test_next_out_of_tolerance_skips_retuned_notes() {
+        let mut session = create_test_session();
+        session.complete_note("F3", 1.0);
+        session.complete_note("F#3", 12.0);
+        assert_eq!(session.next_out_of_tolerance(0, 5.0), Some(1));
+
+        // Second pass brings it in
+        session.complete_note("F#3", 0.8);
+        assert_eq!(session.next_out_of_tolerance(0, 5.0), None);
+    }
+
+    #[test]
+    fn test_next_out_of_tolerance_empty() {
+        assert_eq!(create_test_session().next_out_of_tolerance(0, 5.0), None);
+    }
+
+    #[test]
+    fn test_write_read_roundtrip_in_memory() {
+        let mut session = create_test_session();
+        session.complete_note("F3", 1.5);
+
+        let mut buffer = Vec::new();
+        session.write_to(&mut buffer).expect("Should write");
+        let restored = Session::read_from(buffer.as_slice()).expect("Should read");
+
+        assert_eq!(restored.id, session.id);
+        assert_eq!(restored.completed_notes.len(), 1);
+        assert_eq!(restored.completed_notes[0].note, "F3");
+    }
+
+    #[test]
+    fn test_read_from_rejects_garbage() {
+        assert!(Session::read_from(&b"not json"[..]).is_err());
+    }
+
     #[test]
     fn test_save_and_load() {
         let temp_dir = TempDir::new().expect("Should create temp dir");
@@ -378,6 +1122,71 @@ mod tests {
         assert_eq!(loaded.completed_notes.len(), 1);
     }
 
+    #[test]
+    fn test_legacy_id_saves_back_to_its_file() {
+        let mut session = create_test_session();
+        session.id = "2024-01-01T10:00:00.123456789+00:00".to_string();
+
+        let mut buffer = Vec::new();
+        session.write_to(&mut buffer).expect("Should write");
+        let loaded = Session::read_from(buffer.as_slice()).expect("Should read");
+        assert_eq!(loaded.id, session.id);
+
+        let path = loaded.session_path().expect("Should have a path");
+        assert_eq!(
+            path.file_name().and_then(|n| n.to_str()),
+            Some("2024-01-01T10-00-00.123456789+00-00.json")
+        );
+    }
+
+    #[test]
+    fn test_list_incomplete_skips_finished_and_sorts_by_recency() {
+        let temp_dir = TempDir::new().expect("Should create temp dir");
+        let save = |session: &Session, name: &str| {
+            session
+                .write_to(fs::File::create(temp_dir.path().join(name)).expect("Should create file"))
+                .expect("Should write");
+        };
+        let at =
+            |hour: u32| chrono::TimeZone::with_ymd_and_hms(&Utc, 2024, 1, 1, hour, 0, 0).unwrap();
+
+        let mut older = create_test_session().with_piano("Upright");
+        older.updated_at = at(9);
+        let mut newer = create_test_session().with_piano("Grand");
+        newer.updated_at = at(11);
+        let mut finished = create_test_session().with_piano("Spinet");
+        finished.current_note_index = 88;
+        finished.updated_at = at(12);
+        save(&older, "older.json");
+        save(&finished, "finished.json");
+        save(&newer, "newer.json");
+        fs::write(temp_dir.path().join("notes.txt"), "not a session").expect("Should write");
+
+        let listed = Session::incomplete_in(temp_dir.path()).expect("Should list");
+        let pianos: Vec<_> = listed.iter().map(|s| s.piano.as_deref()).collect();
+        assert_eq!(pianos, vec![Some("Grand"), Some("Upright")]);
+        assert_eq!(listed[0].summary(), "Grand, 2024-01-01 11:00, 0% done");
+
+        let missing = temp_dir.path().join("missing");
+        assert!(Session::incomplete_in(&missing)
+            .expect("Should list")
+            .is_empty());
+    }
+
+    #[test]
+    fn test_temperament_start_round_trips() {
+        let session = create_test_session().with_temperament_start(57);
+        let json = serde_json::to_string(&session).expect("Should serialize");
+        let restored: Session = serde_json::from_str(&json).expect("Should load");
+        assert_eq!(restored.temperament_start, Some(57));
+
+        // The default start is left out, so older sessions read the same
+        let session = create_test_session().with_temperament_start(DEFAULT_TEMPERAMENT_START);
+        assert_eq!(session.temperament_start, None);
+        let json = serde_json::to_string(&session).expect("Should serialize");
+        assert!(!json.contains("temperament_start"));
+    }
+
     #[test]
     fn test_tuning_mode_serialization() {
         // Test that modes serialize to expected strings
@@ -407,6 +1216,143 @@ mod tests {
         assert!(session.updated_at > original_updated);
     }
 
+    #[test]
+    fn test_report_lists_header_and_notes() {
+        let mut session = create_test_session().with_piano("Studio Steinway");
+        session.complete_note("A4", 0.0);
+        session.complete_note("F3", 3.0);
+        session.comment_last(Some("sticky key"));
+        session.record_note("C8", 12.0, NoteStatus::LeftAsIs);
+
+        let report = session.report_string();
+        assert!(report.contains("Piano:        Studio Steinway"));
+        assert!(report.contains(&session.created_at.format("%Y-%m-%d").to_string()));
+        assert!(report.contains("Mode:         Concert"));
+        assert!(report.contains("A4:           440.0 Hz"));
+        assert!(report.contains("Temperament:  Equal temperament"));
+        assert!(report.contains("  F3     +3.0 cents  tuned - sticky key"));
+        assert!(report.contains("  C8    +12.0 cents  left as is"));
+        assert!(report.contains("Average deviation: 5.0 cents"));
+
+        // Octaves run low to high, whatever order notes were tuned in
+        let octave_3 = report.find("Octave 3").expect("Octave 3 heading");
+        let octave_4 = report.find("Octave 4").expect("Octave 4 heading");
+        assert!(octave_3 < octave_4);
+    }
+
+    #[test]
+    fn test_set_a4_flags_recorded_notes_for_review() {
+        let mut session = create_test_session();
+        session.complete_note("F3", 0.4);
+        session.complete_note("F#3", -0.2);
+        session.record_note("G3", 3.0, NoteStatus::LeftAsIs);
+        session.record_note("G#3", 0.0, NoteStatus::Skipped);
+
+        session.set_a4(442.0);
+        session.complete_note("A3", 0.1);
+
+        assert_eq!(session.a4_reference, 442.0);
+        // A skipped note was never tuned against the old reference
+        assert_eq!(session.notes_for_review(), ["F3", "F#3", "G3"]);
+        assert!(!session.completed_notes[3].needs_review);
+        assert!(!session.completed_notes[4].needs_review);
+        assert!(session
+            .events
+            .iter()
+            .any(|e| e.kind == SessionEventKind::A4Changed { a4: 442.0 }));
+        assert!(session
+            .report_string()
+            .contains("  F3     +0.4 cents  tuned (review)"));
+
+        // Setting the same reference again changes nothing
+        let events = session.events.len();
+        session.set_a4(442.0);
+        assert_eq!(session.events.len(), events);
+        assert!(!session.completed_notes[4].needs_review);
+    }
+
+    #[test]
+    fn test_events_logged_in_order() {
+        let mut session = create_test_session();
+        session.set_offset(-12.0);
+        session.enter_note("F3");
+        session.complete_note("F3", 0.4);
+        session.enter_note("F#3");
+        session.record_note("F#3", 0.0, NoteStatus::Skipped);
+        session.enter_note("G3");
+        session.record_note("G3", 3.0, NoteStatus::LeftAsIs);
+
+        let kinds: Vec<_> = session.events.iter().map(|e| e.kind.clone()).collect();
+        let note = |n: &str| n.to_string();
+        assert_eq!(
+            kinds,
+            [
+                SessionEventKind::OffsetChanged { cents: -12.0 },
+                SessionEventKind::NoteEntered { note: note("F3") },
+                SessionEventKind::NoteConfirmed {
+                    note: note("F3"),
+                    cents: 0.4
+                },
+                SessionEventKind::NoteEntered { note: note("F#3") },
+                SessionEventKind::NoteSkipped { note: note("F#3") },
+                SessionEventKind::NoteEntered { note: note("G3") },
+                SessionEventKind::NoteLeftAsIs {
+                    note: note("G3"),
+                    cents: 3.0
+                },
+            ]
+        );
+        assert_eq!(session.piano_offset_cents, -12.0);
+        assert!(session
+            .events
+            .windows(2)
+            .all(|w| w[0].timestamp <= w[1].timestamp));
+    }
+
+    #[test]
+    fn test_events_round_trip_and_default_for_old_files() {
+        let mut session = create_test_session();
+        session.enter_note("A4");
+        let json = serde_json::to_string(&session).expect("serialize");
+        assert!(json.contains(r#""event":"note_entered","note":"A4""#));
+        let loaded: Session = serde_json::from_str(&json).expect("deserialize");
+        assert_eq!(loaded.events, session.events);
+
+        // Files written before the log existed load with an empty one
+        let mut value: serde_json::Value = serde_json::from_str(&json).unwrap();
+        value.as_object_mut().unwrap().remove("events");
+        let old: Session = serde_json::from_value(value).expect("old file");
+        assert!(old.events.is_empty());
+    }
+
+    #[test]
+    fn test_final_cents_uses_latest_result() {
+        let mut session = create_test_session();
+        assert_eq!(session.final_cents_for("A5"), None);
+
+        session.complete_note("A5", 3.0);
+        session.complete_note("A5", 1.0);
+        assert_eq!(session.final_cents_for("A5"), Some(1.0));
+
+        session.skip_note();
+        session.record_note("A5", 0.0, NoteStatus::Skipped);
+        assert_eq!(session.final_cents_for("A5"), None);
+    }
+
+    #[test]
+    fn test_score_counts_latest_tuned_results() {
+        let mut session = create_test_session();
+        assert_eq!(session.score(), None);
+
+        session.complete_note("A4", 10.0);
+        session.record_note("A3", 20.0, NoteStatus::Skipped);
+        assert_eq!(session.score(), Some(score_for_error(10.0)));
+
+        // Re-tuning a note replaces its earlier result
+        session.complete_note("A4", 0.0);
+        assert_eq!(session.score(), Some(100));
+    }
+
     #[test]
     fn test_completed_note_creation() {
         let note = CompletedNote::new("A4", -2.5);
@@ -0,0 +1,42 @@
+//! Where saved profiles and sessions live.
+
+use std::ffi::OsString;
+use std::path::PathBuf;
+
+use directories::ProjectDirs;
+
+/// Environment variable that overrides the data directory, so tests and
+/// sandboxed runs can keep away from the user's real files.
+pub const DATA_DIR_ENV: &str = "ONKEY_DATA_DIR";
+
+/// Get the data directory: `ONKEY_DATA_DIR` if set, otherwise the
+/// platform data directory for pianito.
+pub fn data_dir() -> Option<PathBuf> {
+    data_dir_from(std::env::var_os(DATA_DIR_ENV))
+}
+
+/// Resolve the data directory from the override's value, if any.
+fn data_dir_from(override_dir: Option<OsString>) -> Option<PathBuf> {
+    match override_dir {
+        Some(dir) if !dir.is_empty() => Some(PathBuf::from(dir)),
+        _ => ProjectDirs::from("", "", "pianito").map(|dirs| dirs.data_dir().to_path_buf()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_override_redirects_data_dir() {
+        let dir = data_dir_from(Some(OsString::from("/tmp/pianito-test")));
+        assert_eq!(dir, Some(PathBuf::from("/tmp/pianito-test")));
+    }
+
+    #[test]
+    fn test_unset_or_empty_override_uses_platform_dir() {
+        let platform = data_dir_from(None);
+        assert_ne!(platform, Some(PathBuf::from("")));
+        assert_eq!(data_dir_from(Some(OsString::new())), platform);
+    }
+}
@@ -5,23 +5,26 @@
 //! compensates with "stretch tuning" where bass notes are tuned slightly flat
 //! and treble notes slightly sharp.
 
+use serde::{Deserialize, Serialize};
+
 /// Stretch tuning curve based on the Railsback curve.
 ///
 /// The Railsback curve is an empirical curve showing how piano tuners
 /// deviate from equal temperament to achieve the most pleasing sound.
-#[derive(Debug, Clone)]
+/// Every key follows the curve unless hand-edited: an edit sets a control
+/// point, and the change from the curve is interpolated linearly out to
+/// the neighboring control points, so it reshapes only its neighborhood.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
 pub struct StretchCurve {
-    /// Stretch values in cents for each of the 88 keys.
-    /// Index 0 = A0 (MIDI 21), Index 87 = C8 (MIDI 108)
-    offsets: [f32; 88],
+    /// Hand-edited control points as (MIDI note, cents), sorted by note.
+    #[serde(default)]
+    overrides: Vec<(u8, f32)>,
 }
 
 impl StretchCurve {
     /// Create a new stretch curve with default Railsback-inspired values.
     pub fn new() -> Self {
-        Self {
-            offsets: Self::generate_railsback_curve(),
-        }
+        Self::default()
     }
 
     /// Get the stretch offset in cents for a given MIDI note.
@@ -30,33 +33,82 @@ impl StretchCurve {
         if !(21..=108).contains(&midi_note) {
             return 0.0;
         }
-        self.offsets[(midi_note - 21) as usize]
+        // An edited note is exactly where it was set
+        if let Ok(i) = self
+            .overrides
+            .binary_search_by_key(&midi_note, |&(midi, _)| midi)
+        {
+            return self.overrides[i].1;
+        }
+        let edits = self.edits();
+        let after = edits.partition_point(|&(midi, _)| midi < midi_note);
+        let edit = match (after.checked_sub(1).map(|i| edits[i]), edits.get(after)) {
+            (_, Some(&(midi, edit))) if midi == midi_note => edit,
+            (Some((lo_midi, lo)), Some(&(hi_midi, hi))) => {
+                let t = (midi_note - lo_midi) as f32 / (hi_midi - lo_midi) as f32;
+                lo + (hi - lo) * t
+            }
+            _ => 0.0,
+        };
+        Self::calculate_stretch(midi_note) + edit
     }
 
     /// Get the stretch offset for a note by index (0-87).
     pub fn offset_cents_by_index(&self, index: usize) -> f32 {
-        self.offsets.get(index).copied().unwrap_or(0.0)
+        if index >= 88 {
+            return 0.0;
+        }
+        self.offset_cents((index + 21) as u8)
     }
 
-    /// Generate a simplified Railsback-style stretch curve.
-    ///
-    /// This is a simplified model based on typical Railsback curves:
-    /// - Bass notes (A0-C3): progressively flat, up to -20 cents at A0
-    /// - Middle octaves (C3-F5): close to 0, the "temperament zone"
-    /// - Treble notes (F5-C8): progressively sharp, up to +20 cents at C8
-    fn generate_railsback_curve() -> [f32; 88] {
-        let mut offsets = [0.0_f32; 88];
+    /// Control points as (MIDI note, cents), lowest note first: A0, every
+    /// C, and any hand-edited notes.
+    pub fn as_points(&self) -> Vec<(u8, f32)> {
+        self.edits()
+            .into_iter()
+            .map(|(midi, _)| (midi, self.offset_cents(midi)))
+            .collect()
+    }
 
-        for (i, offset) in offsets.iter_mut().enumerate() {
-            let midi = (i + 21) as u8;
-            *offset = Self::calculate_stretch(midi);
+    /// Add or move the control point for a note. Notes off the keyboard
+    /// are ignored.
+    pub fn set_point(&mut self, midi_note: u8, cents: f32) {
+        if !(21..=108).contains(&midi_note) {
+            return;
+        }
+        match self
+            .overrides
+            .binary_search_by_key(&midi_note, |&(midi, _)| midi)
+        {
+            Ok(i) => self.overrides[i].1 = cents,
+            Err(i) => self.overrides.insert(i, (midi_note, cents)),
         }
+    }
 
-        offsets
+    /// Control points with how far each sits from the default curve, in
+    /// cents. Unedited points at A0 and every C sit on the curve.
+    fn edits(&self) -> Vec<(u8, f32)> {
+        let mut edits: Vec<(u8, f32)> = std::iter::once(21)
+            .chain((24..=108).step_by(12))
+            .map(|midi| (midi, 0.0))
+            .collect();
+        for &(midi, cents) in &self.overrides {
+            let edit = cents - Self::calculate_stretch(midi);
+            match edits.binary_search_by_key(&midi, |&(m, _)| m) {
+                Ok(i) => edits[i].1 = edit,
+                Err(i) => edits.insert(i, (midi, edit)),
+            }
+        }
+        edits
     }
 
     /// Calculate stretch for a single note.
     ///
+    /// This is a simplified model based on typical Railsback curves:
+    /// - Bass notes (A0-C3): progressively flat, up to -20 cents at A0
+    /// - Middle octaves (C3-F5): close to 0, the "temperament zone"
+    /// - Treble notes (F5-C8): progressively sharp, up to +20 cents at C8
+    ///
     /// Uses a smooth cubic curve across the entire range:
     /// - A0 (21): approximately -20 cents
     /// - C4 (60): approximately 0 cents
@@ -84,12 +136,6 @@ impl StretchCurve {
     }
 }
 
-impl Default for StretchCurve {
-    fn default() -> Self {
-        Self::new()
-    }
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -196,6 +242,68 @@ mod tests {
         assert_eq!(curve.offset_cents(109), 0.0);
     }
 
+    #[test]
+    fn test_set_point_reshapes_neighborhood() {
+        let mut curve = StretchCurve::new();
+        let before = curve.clone();
+
+        // Raise C5 (72) by 4 cents
+        let c5 = curve.offset_cents(72);
+        curve.set_point(72, c5 + 4.0);
+
+        assert!((curve.offset_cents(72) - (c5 + 4.0)).abs() < 1e-4);
+        // Halfway to the neighboring points, half the change
+        assert!((curve.offset_cents(66) - before.offset_cents(66) - 2.0).abs() < 1e-4);
+        assert!((curve.offset_cents(78) - before.offset_cents(78) - 2.0).abs() < 1e-4);
+        // Beyond the neighboring points, unchanged
+        assert_eq!(curve.offset_cents(60), before.offset_cents(60));
+        assert_eq!(curve.offset_cents(84), before.offset_cents(84));
+    }
+
+    #[test]
+    fn test_default_curve_is_per_key() {
+        let curve = StretchCurve::new();
+        for midi in 21..=108 {
+            assert_eq!(
+                curve.offset_cents(midi),
+                StretchCurve::calculate_stretch(midi)
+            );
+        }
+
+        // An edit leaves keys beyond its neighboring points on the curve
+        let mut edited = curve.clone();
+        edited.set_point(72, 5.0);
+        assert_eq!(edited.offset_cents(59), StretchCurve::calculate_stretch(59));
+        assert_eq!(edited.offset_cents(85), StretchCurve::calculate_stretch(85));
+    }
+
+    #[test]
+    fn test_set_point_inserts_in_order() {
+        let mut curve = StretchCurve::new();
+        let count = curve.as_points().len();
+
+        curve.set_point(69, 1.5);
+        curve.set_point(200, 5.0);
+
+        let points = curve.as_points();
+        assert_eq!(points.len(), count + 1);
+        assert!(points.windows(2).all(|pair| pair[0].0 < pair[1].0));
+        assert_eq!(curve.offset_cents(69), 1.5);
+    }
+
+    #[test]
+    fn test_edited_curve_round_trips_through_serde() {
+        let mut curve = StretchCurve::new();
+        curve.set_point(69, 1.5);
+        curve.set_point(100, 18.0);
+
+        let json = serde_json::to_string(&curve).unwrap();
+        let restored: StretchCurve = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(restored, curve);
+        assert_eq!(restored.offset_cents(70), curve.offset_cents(70));
+    }
+
     #[test]
     fn test_stretch_magnitudes() {
         let curve = StretchCurve::new();
@@ -203,14 +311,14 @@ mod tests {
         // Verify approximate magnitudes match Railsback expectations
         let a0 = curve.offset_cents(21);
         assert!(
-            a0 >= -25.0 && a0 <= -10.0,
+            (-25.0..=-10.0).contains(&a0),
             "A0 stretch {} out of expected range",
             a0
         );
 
         let c8 = curve.offset_cents(108);
         assert!(
-            c8 >= 10.0 && c8 <= 25.0,
+            (10.0..=25.0).contains(&c8),
             "C8 stretch {} out of expected range",
             c8
         );
@@ -0,0 +1,87 @@
+//! Precomputed target frequencies for all 88 keys.
+//!
+//! A session's targets only change when its temperament, stretch, or offset
+//! does, so they are worked out once and looked up from then on.
+
+use super::notes::NOTE_COUNT;
+use super::stretch::StretchCurve;
+use super::temperament::Temperament;
+
+/// MIDI number of the lowest key (A0).
+const LOWEST_MIDI: u8 = 21;
+
+/// Target frequency of every key, built once per session.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TargetTable {
+    /// Target in Hz by key index (0 = A0).
+    targets: [f32; NOTE_COUNT],
+}
+
+impl TargetTable {
+    /// Work out every key's target from the temperament, an optional stretch
+    /// curve, and an overall offset in cents.
+    pub fn build(temperament: &Temperament, stretch: Option<&StretchCurve>, offset: f32) -> Self {
+        let mut targets = [0.0; NOTE_COUNT];
+        for (i, target) in targets.iter_mut().enumerate() {
+            let midi = LOWEST_MIDI + i as u8;
+            let base = temperament.frequency(midi);
+            let stretched = match stretch {
+                Some(stretch) => stretch.apply(base, midi),
+                None => base,
+            };
+            *target = temperament.cents_to_frequency(stretched, offset);
+        }
+        Self { targets }
+    }
+
+    /// Target frequency for a key. Keys off the keyboard return 0.0.
+    pub fn target(&self, midi: u8) -> f32 {
+        midi.checked_sub(LOWEST_MIDI)
+            .and_then(|i| self.targets.get(i as usize))
+            .copied()
+            .unwrap_or(0.0)
+    }
+}
+
+impl Default for TargetTable {
+    fn default() -> Self {
+        Self::build(&Temperament::new(), None, 0.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+
+    const SAMPLE: [u8; 6] = [21, 45, 60, 69, 93, 108];
+
+    #[test]
+    fn test_matches_temperament() {
+        let temperament = Temperament::with_a4(442.0);
+        let table = TargetTable::build(&temperament, None, 0.0);
+        for midi in SAMPLE {
+            assert_relative_eq!(table.target(midi), temperament.frequency(midi));
+        }
+    }
+
+    #[test]
+    fn test_matches_stretch_and_offset() {
+        let temperament = Temperament::new();
+        let stretch = StretchCurve::new();
+        let table = TargetTable::build(&temperament, Some(&stretch), -15.0);
+        for midi in SAMPLE {
+            let expected = temperament
+                .cents_to_frequency(stretch.apply(temperament.frequency(midi), midi), -15.0);
+            assert_relative_eq!(table.target(midi), expected, max_relative = 1e-6);
+        }
+    }
+
+    #[test]
+    fn test_keys_off_the_keyboard() {
+        let table = TargetTable::default();
+        assert_eq!(table.target(20), 0.0);
+        assert_eq!(table.target(109), 0.0);
+        assert_eq!(table.target(0), 0.0);
+    }
+}
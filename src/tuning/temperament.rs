@@ -1,9 +1,133 @@
 //! Equal temperament calculations.
 
+use std::fmt;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
 use super::notes::Note;
 
+/// A tuning system, by its short name.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TuningName {
+    /// Twelve-tone equal temperament.
+    #[default]
+    Equal,
+}
+
+/// Every available tuning with its display name.
+const AVAILABLE: &[(TuningName, &str)] = &[(TuningName::Equal, "Equal temperament")];
+
+impl TuningName {
+    /// Short name used on the command line and in files.
+    pub fn key(self) -> &'static str {
+        match self {
+            TuningName::Equal => "equal",
+        }
+    }
+
+    /// Human-readable name.
+    pub fn display_name(self) -> &'static str {
+        AVAILABLE
+            .iter()
+            .find(|(name, _)| *name == self)
+            .map(|(_, display)| *display)
+            .expect("every tuning is listed")
+    }
+}
+
+impl fmt::Display for TuningName {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.key())
+    }
+}
+
+/// Error for an unrecognised tuning name.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("Unknown temperament: {0}")]
+pub struct UnknownTuning(pub String);
+
+impl FromStr for TuningName {
+    type Err = UnknownTuning;
+
+    /// Parse a short name (`equal`) or display name, ignoring case.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        AVAILABLE
+            .iter()
+            .find(|(name, display)| {
+                name.key().eq_ignore_ascii_case(s) || display.eq_ignore_ascii_case(s)
+            })
+            .map(|(name, _)| *name)
+            .ok_or_else(|| UnknownTuning(s.to_string()))
+    }
+}
+
+/// A common A4 reference pitch.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize, clap::ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum A4Preset {
+    /// Modern standard pitch, 440 Hz.
+    #[default]
+    #[value(alias = "440")]
+    Standard,
+    /// Many orchestras, 442 Hz.
+    #[value(alias = "442")]
+    Orchestral,
+    /// Some European orchestras, 443 Hz.
+    #[value(alias = "443")]
+    European,
+    /// Baroque pitch, a semitone below standard (415.3 Hz).
+    #[value(alias = "415")]
+    Baroque,
+    /// "Verdi" pitch, 432 Hz.
+    #[value(alias = "432")]
+    Verdi,
+}
+
+impl A4Preset {
+    /// All presets, in menu order.
+    pub const ALL: [A4Preset; 5] = [
+        A4Preset::Standard,
+        A4Preset::Orchestral,
+        A4Preset::European,
+        A4Preset::Baroque,
+        A4Preset::Verdi,
+    ];
+
+    /// A4 frequency in Hz.
+    pub fn frequency(self) -> f32 {
+        match self {
+            A4Preset::Standard => 440.0,
+            A4Preset::Orchestral => 442.0,
+            A4Preset::European => 443.0,
+            A4Preset::Baroque => 415.3,
+            A4Preset::Verdi => 432.0,
+        }
+    }
+
+    /// Short display name.
+    pub fn label(self) -> &'static str {
+        match self {
+            A4Preset::Standard => "Standard",
+            A4Preset::Orchestral => "Orchestral",
+            A4Preset::European => "European",
+            A4Preset::Baroque => "Baroque",
+            A4Preset::Verdi => "Verdi",
+        }
+    }
+
+    /// The preset at `a4`, if it is one.
+    pub fn from_frequency(a4: f32) -> Option<A4Preset> {
+        Self::ALL
+            .into_iter()
+            .find(|preset| (preset.frequency() - a4).abs() < 0.05)
+    }
+}
+
 /// Equal temperament calculator.
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub struct Temperament {
     /// Reference frequency for A4.
     a4_freq: f32,
@@ -20,6 +144,16 @@ impl Temperament {
         Self { a4_freq }
     }
 
+    /// List every available tuning with its display name.
+    pub fn available() -> &'static [(TuningName, &'static str)] {
+        AVAILABLE
+    }
+
+    /// Get the tuning this temperament uses.
+    pub fn name(&self) -> TuningName {
+        TuningName::Equal
+    }
+
     /// Get the A4 reference frequency.
     pub fn a4(&self) -> f32 {
         self.a4_freq
@@ -178,6 +312,42 @@ pub const REFERENCE_FREQUENCIES: [(u8, f32); 88] = [
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_a4_presets() {
+        let frequencies: Vec<f32> = A4Preset::ALL.iter().map(|p| p.frequency()).collect();
+        assert_eq!(frequencies, [440.0, 442.0, 443.0, 415.3, 432.0]);
+        assert_eq!(A4Preset::default().frequency(), 440.0);
+
+        assert_eq!(A4Preset::from_frequency(415.3), Some(A4Preset::Baroque));
+        assert_eq!(A4Preset::from_frequency(441.0), None);
+    }
+
+    #[test]
+    fn test_available_names_round_trip() {
+        assert!(!Temperament::available().is_empty());
+        for &(name, display) in Temperament::available() {
+            assert_eq!(name.to_string().parse::<TuningName>(), Ok(name));
+            assert_eq!(display.parse::<TuningName>(), Ok(name));
+            assert_eq!(name.display_name(), display);
+        }
+    }
+
+    #[test]
+    fn test_unknown_tuning_name() {
+        assert_eq!("EQUAL".parse::<TuningName>(), Ok(TuningName::Equal));
+        assert_eq!(
+            "meantone".parse::<TuningName>(),
+            Err(UnknownTuning("meantone".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_temperament_equality() {
+        assert_eq!(Temperament::new(), Temperament::with_a4(440.0));
+        assert_ne!(Temperament::new(), Temperament::with_a4(442.0));
+        assert_eq!(Temperament::new().name(), TuningName::Equal);
+    }
+
     #[test]
     fn test_a4_default() {
         let temp = Temperament::new();
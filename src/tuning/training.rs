@@ -0,0 +1,311 @@
+//! Ear training: match a detuned string to the reference by ear.
+//!
+//! Each round starts a simulated string somewhere off pitch. The student
+//! nudges it up or down while comparing against the reference tone, then
+//! reveals how many cents off they ended up.
+
+use super::notes::Note;
+use super::temperament::Temperament;
+
+/// Largest initial detune for a training round, in cents.
+pub const MAX_DETUNE_CENTS: f32 = 30.0;
+/// Smallest initial detune, so rounds never start already in tune.
+pub const MIN_DETUNE_CENTS: f32 = 5.0;
+/// Error at or beyond which a round scores zero.
+pub const ZERO_SCORE_CENTS: f32 = 25.0;
+/// Furthest the simulated string can be pushed from the target.
+const MAX_ADJUST_CENTS: f32 = 100.0;
+
+/// Score (0-100) for a final error in cents.
+///
+/// Exact matches score 100, falling linearly to 0 at `ZERO_SCORE_CENTS`.
+pub fn score_for_error(error_cents: f32) -> u32 {
+    let ratio = (error_cents.abs() / ZERO_SCORE_CENTS).min(1.0);
+    ((1.0 - ratio) * 100.0).round() as u32
+}
+
+/// Outcome of a revealed training round.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TrainingResult {
+    /// Final deviation of the simulated string in cents.
+    pub error_cents: f32,
+    /// Score from 0 to 100.
+    pub score: u32,
+}
+
+impl TrainingResult {
+    /// Build a result from a final error.
+    pub fn from_error(error_cents: f32) -> Self {
+        Self {
+            error_cents,
+            score: score_for_error(error_cents),
+        }
+    }
+}
+
+/// A single ear-training round.
+#[derive(Debug, Clone)]
+pub struct TrainingRound {
+    /// MIDI note being matched.
+    midi: u8,
+    /// Current offset of the simulated string from the target.
+    offset_cents: f32,
+    /// Result, once revealed.
+    result: Option<TrainingResult>,
+}
+
+impl TrainingRound {
+    /// Create a round with the simulated string at `initial_offset_cents`.
+    pub fn new(midi: u8, initial_offset_cents: f32) -> Self {
+        Self {
+            midi,
+            offset_cents: initial_offset_cents.clamp(-MAX_ADJUST_CENTS, MAX_ADJUST_CENTS),
+            result: None,
+        }
+    }
+
+    /// Get the MIDI note being matched.
+    pub fn midi(&self) -> u8 {
+        self.midi
+    }
+
+    /// Get the note being matched.
+    pub fn note(&self) -> Option<&'static Note> {
+        Note::from_midi(self.midi)
+    }
+
+    /// Get the simulated string's current offset in cents.
+    pub fn offset_cents(&self) -> f32 {
+        self.offset_cents
+    }
+
+    /// Nudge the simulated string. Ignored once revealed.
+    pub fn adjust(&mut self, delta_cents: f32) {
+        if self.result.is_none() {
+            self.offset_cents =
+                (self.offset_cents + delta_cents).clamp(-MAX_ADJUST_CENTS, MAX_ADJUST_CENTS);
+        }
+    }
+
+    /// Reveal the result. Repeated calls return the same result.
+    pub fn reveal(&mut self) -> TrainingResult {
+        *self
+            .result
+            .get_or_insert_with(|| TrainingResult::from_error(self.offset_cents))
+    }
+
+    /// Check if the round has been revealed.
+    pub fn is_revealed(&self) -> bool {
+        self.result.is_some()
+    }
+
+    /// Get the result, if revealed.
+    pub fn result(&self) -> Option<TrainingResult> {
+        self.result
+    }
+
+    /// Reference (target) frequency for this round.
+    pub fn target_frequency(&self, temperament: &Temperament) -> f32 {
+        temperament.frequency(self.midi)
+    }
+
+    /// Current frequency of the simulated string.
+    pub fn simulated_frequency(&self, temperament: &Temperament) -> f32 {
+        temperament.cents_to_frequency(self.target_frequency(temperament), self.offset_cents)
+    }
+}
+
+/// Running accuracy across training rounds.
+#[derive(Debug, Clone, Default)]
+pub struct TrainingStats {
+    rounds: usize,
+    total_abs_error: f32,
+    total_score: u32,
+}
+
+impl TrainingStats {
+    /// Create empty stats.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record a revealed round.
+    pub fn record(&mut self, result: &TrainingResult) {
+        self.rounds += 1;
+        self.total_abs_error += result.error_cents.abs();
+        self.total_score += result.score;
+    }
+
+    /// Number of rounds recorded.
+    pub fn rounds(&self) -> usize {
+        self.rounds
+    }
+
+    /// Average absolute error in cents.
+    pub fn average_error(&self) -> f32 {
+        if self.rounds == 0 {
+            0.0
+        } else {
+            self.total_abs_error / self.rounds as f32
+        }
+    }
+
+    /// Running accuracy as the average score (0-100).
+    pub fn accuracy(&self) -> f32 {
+        if self.rounds == 0 {
+            0.0
+        } else {
+            self.total_score as f32 / self.rounds as f32
+        }
+    }
+}
+
+/// Picks notes and starting detunes for training rounds.
+///
+/// Uses a small xorshift generator so rounds are reproducible from a seed.
+#[derive(Debug, Clone)]
+pub struct RoundGenerator {
+    state: u64,
+}
+
+impl RoundGenerator {
+    /// Temperament octave (F3-F4), where ear training is most useful.
+    const MIDI_RANGE: (u8, u8) = (53, 65);
+
+    /// Create a generator from a seed.
+    pub fn new(seed: u64) -> Self {
+        Self {
+            // xorshift must not start at zero
+            state: seed.max(1),
+        }
+    }
+
+    /// Create a generator seeded from the current time.
+    pub fn from_time() -> Self {
+        let nanos = chrono::Utc::now().timestamp_nanos_opt().unwrap_or(1);
+        Self::new(nanos as u64)
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.state ^= self.state << 13;
+        self.state ^= self.state >> 7;
+        self.state ^= self.state << 17;
+        self.state
+    }
+
+    fn next_unit(&mut self) -> f32 {
+        (self.next_u64() % 10_000) as f32 / 10_000.0
+    }
+
+    /// Generate the next round.
+    pub fn next_round(&mut self) -> TrainingRound {
+        let (low, high) = Self::MIDI_RANGE;
+        let midi = low + (self.next_u64() % (high - low + 1) as u64) as u8;
+
+        let magnitude = MIN_DETUNE_CENTS + self.next_unit() * (MAX_DETUNE_CENTS - MIN_DETUNE_CENTS);
+        let sign = if self.next_u64() & 1 == 0 { 1.0 } else { -1.0 };
+
+        TrainingRound::new(midi, sign * magnitude)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_score_exact_match() {
+        assert_eq!(score_for_error(0.0), 100);
+    }
+
+    #[test]
+    fn test_score_known_error() {
+        // 5 cents of 25 is 20% off → 80
+        assert_eq!(score_for_error(5.0), 80);
+        assert_eq!(score_for_error(-5.0), 80);
+        assert_eq!(score_for_error(12.5), 50);
+    }
+
+    #[test]
+    fn test_score_floor_at_zero() {
+        assert_eq!(score_for_error(ZERO_SCORE_CENTS), 0);
+        assert_eq!(score_for_error(80.0), 0);
+    }
+
+    #[test]
+    fn test_adjust_moves_offset() {
+        let mut round = TrainingRound::new(69, 12.0);
+        round.adjust(-5.0);
+        round.adjust(-5.0);
+        assert!((round.offset_cents() - 2.0).abs() < 0.001);
+    }
+
+    #[test]
+    fn test_reveal_reports_final_error() {
+        let mut round = TrainingRound::new(69, 12.0);
+        round.adjust(-9.0);
+        assert!(!round.is_revealed());
+
+        let result = round.reveal();
+        assert!(round.is_revealed());
+        assert!((result.error_cents - 3.0).abs() < 0.001);
+        assert_eq!(result.score, score_for_error(3.0));
+    }
+
+    #[test]
+    fn test_reveal_locks_adjustment() {
+        let mut round = TrainingRound::new(69, 10.0);
+        let first = round.reveal();
+        round.adjust(-10.0);
+        let second = round.reveal();
+
+        assert_eq!(first, second);
+        assert!((round.offset_cents() - 10.0).abs() < 0.001);
+    }
+
+    #[test]
+    fn test_simulated_frequency_follows_offset() {
+        let temperament = Temperament::new();
+        let mut round = TrainingRound::new(69, 0.0);
+        assert!((round.simulated_frequency(&temperament) - 440.0).abs() < 0.01);
+
+        round.adjust(1200.0); // clamped to +100
+        let freq = round.simulated_frequency(&temperament);
+        assert!((freq - temperament.frequency(70)).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_stats_running_accuracy() {
+        let mut stats = TrainingStats::new();
+        assert_eq!(stats.accuracy(), 0.0);
+
+        stats.record(&TrainingResult::from_error(0.0));
+        stats.record(&TrainingResult::from_error(-12.5));
+
+        assert_eq!(stats.rounds(), 2);
+        assert!((stats.accuracy() - 75.0).abs() < 0.01);
+        assert!((stats.average_error() - 6.25).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_generator_rounds_in_range() {
+        let mut gen = RoundGenerator::new(42);
+        for _ in 0..50 {
+            let round = gen.next_round();
+            assert!((53..=65).contains(&round.midi()));
+            let offset = round.offset_cents().abs();
+            assert!((MIN_DETUNE_CENTS..=MAX_DETUNE_CENTS).contains(&offset));
+        }
+    }
+
+    #[test]
+    fn test_generator_is_reproducible() {
+        let mut a = RoundGenerator::new(7);
+        let mut b = RoundGenerator::new(7);
+        for _ in 0..5 {
+            let (ra, rb) = (a.next_round(), b.next_round());
+            assert_eq!(ra.midi(), rb.midi());
+            assert_eq!(ra.offset_cents(), rb.offset_cents());
+        }
+    }
+}
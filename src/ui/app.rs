@@ -5,16 +5,61 @@ use std::collections::HashSet;
 use crossterm::event::KeyCode;
 use ratatui::Frame;
 
+use crate::audio::{
+    AttackTracker, Cue, FalseBeatWindow, PitchSmoother, ReferenceTone, ResponsePreset,
+    SilenceWatchdog,
+};
+use crate::tuning::intervals::{
+    OCTAVE_BEATS_LOW_MIDI, TEMPERAMENT_HIGH_MIDI, TEMPERAMENT_LOW_MIDI,
+};
+use crate::tuning::notes::{Note, NoteSpelling, StringLayout};
 use crate::tuning::order::TuningOrder;
+use crate::tuning::practice::TemperamentPractice;
 use crate::tuning::profile::PianoProfile;
-use crate::tuning::session::{Session, TuningMode};
+use crate::tuning::relative::RelativeTargets;
+use crate::tuning::session::{NoteStatus, Session, TuningMode};
+use crate::tuning::targets::TargetTable;
 use crate::tuning::temperament::Temperament;
 
+use super::components::{KeyColors, MeterCurve};
+use super::format::DEFAULT_CENTS_PRECISION;
+use super::language::Language;
+use super::screens::profiling::EmptyConfirm;
+use super::screens::tuning::{KeyboardView, DEFAULT_TOLERANCE_CENTS};
 use super::screens::{
-    mode_select::SelectedMode, CalibrationScreen, CompleteScreen, ModeSelectScreen,
-    ProfilingScreen, TuningScreen,
+    mode_select::SelectedMode, CalibrationScreen, CompleteScreen, IntervalCheckScreen,
+    ModeSelectScreen, ProfilingScreen, TrainingScreen, TuningScreen,
 };
 
+/// Length of each tone played in ear training, in seconds.
+const TRAINING_TONE_SECS: f32 = 1.5;
+
+/// Beep played when the reading locks in tune: A6 for 80ms.
+const LOCK_BEEP: ToneRequest = ToneRequest {
+    frequency: 1760.0,
+    duration: 0.08,
+};
+
+/// Rising C major arpeggio played when a session completes.
+const FANFARE: [ToneRequest; 4] = [
+    ToneRequest {
+        frequency: 523.25,
+        duration: 0.15,
+    },
+    ToneRequest {
+        frequency: 659.26,
+        duration: 0.15,
+    },
+    ToneRequest {
+        frequency: 783.99,
+        duration: 0.15,
+    },
+    ToneRequest {
+        frequency: 1046.5,
+        duration: 0.4,
+    },
+];
+
 /// Application screen state.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum AppState {
@@ -26,8 +71,52 @@ pub enum AppState {
     Profiling,
     /// Main tuning screen.
     Tuning,
+    /// Interval checks after the temperament octave.
+    IntervalCheck,
     /// Session complete.
     Complete,
+    /// Ear training practice.
+    Training,
+}
+
+impl AppState {
+    /// Check if this state listens to or plays audio.
+    pub fn needs_audio(&self) -> bool {
+        !matches!(
+            self,
+            AppState::ModeSelect | AppState::IntervalCheck | AppState::Complete
+        )
+    }
+}
+
+/// A tone the app wants played, drained by the audio loop.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ToneRequest {
+    /// Frequency in Hz.
+    pub frequency: f32,
+    /// Duration in seconds.
+    pub duration: f32,
+}
+
+/// What confirming the last step of a note does.
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    Default,
+    serde::Serialize,
+    serde::Deserialize,
+    clap::ValueEnum,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum ConfirmAction {
+    /// Record the note and move to the next one.
+    #[default]
+    Advance,
+    /// Record the note and stay on it until Enter moves on.
+    Stay,
 }
 
 /// Main application.
@@ -50,12 +139,104 @@ pub struct App {
     tuning: Option<TuningScreen>,
     /// Complete screen (created when session ends).
     complete: Option<CompleteScreen>,
+    /// Ear training screen (created when training starts).
+    training: Option<TrainingScreen>,
+    /// Temperament check screen (created when the octave is set).
+    interval_check: Option<IntervalCheckScreen>,
+    /// Whether the temperament check was offered this session.
+    temperament_checked: bool,
+    /// Tones waiting to be played.
+    pending_tones: Vec<ToneRequest>,
     /// Tuning order.
     tuning_order: TuningOrder,
     /// Temperament calculator.
     temperament: Temperament,
+    /// Target of every key under the temperament.
+    targets: TargetTable,
     /// Current note index in tuning order.
     current_note_idx: usize,
+    /// Effective string counts for this instrument.
+    string_layout: StringLayout,
+    /// Note name spelling for display.
+    spelling: NoteSpelling,
+    /// Language for coaching instructions.
+    language: Language,
+    /// Smooths detected pitch for the current note.
+    smoother: PitchSmoother,
+    /// Detector confidence a reading needs to be shown.
+    min_confidence: f32,
+    /// Seconds of dropout to ride out before clearing the display.
+    hold_secs: f32,
+    /// Seconds of input since the last accepted reading.
+    missed_secs: f32,
+    /// Meter in-tune tolerance and completion tolerance, in cents.
+    tolerances: (f32, f32),
+    /// Save the session after this many notes (0 = only on quit).
+    autosave_every: usize,
+    /// Label for the piano, recorded on new sessions.
+    piano: Option<String>,
+    /// Notes advanced since the last save.
+    unsaved_notes: usize,
+    /// Number of session saves attempted.
+    save_count: usize,
+    /// The only note to tune, when touching up a single note.
+    single_note: Option<&'static Note>,
+    /// Temperament practice results, when looping the temperament octave.
+    practice: Option<TemperamentPractice>,
+    /// Pitches notes were left at, when targets follow tuned neighbors.
+    relative: Option<RelativeTargets>,
+    /// The current note has nothing tuned to set it against, so its first
+    /// reading becomes its target.
+    relative_anchor: bool,
+    /// Completed-note index to search from for the next out-of-tolerance note.
+    review_from: usize,
+    /// Position and state to go back to once a revisited note is finished.
+    review_return: Option<(usize, AppState)>,
+    /// Tuning order position the session last logged entering, so
+    /// rebuilding the screen in place doesn't log the note again.
+    entered_position: Option<usize>,
+    /// Beep when the reading locks in tune.
+    beep: bool,
+    /// Suppress all tone playback.
+    quiet: bool,
+    /// Whether the last reading was in tune.
+    locked: bool,
+    /// What confirming a note does.
+    confirm_action: ConfirmAction,
+    /// Whether the current note was recorded during this visit.
+    recorded_here: bool,
+    /// How the keyboard shows progress.
+    keyboard_view: KeyboardView,
+    /// How the tuning meter maps cents to positions.
+    meter_curve: MeterCurve,
+    /// How keys on deviation keyboards are colored.
+    key_colors: KeyColors,
+    /// Decimal places on cents readouts.
+    cents_precision: u8,
+    /// Whether the A/B key is held down, playing the reference.
+    holding_reference: bool,
+    /// Whether key releases are reported; without them A/B toggles.
+    key_release: bool,
+    /// Auto-confirm steady readings while profiling.
+    auto_profile: bool,
+    /// What confirming a profiled key with no reading does.
+    empty_confirm: EmptyConfirm,
+    /// Coach high treble notes by their octave beats.
+    octave_beats: bool,
+    /// Keep the listening indicator still.
+    reduced_motion: bool,
+    /// Main loop iterations so far, driving the listening animation.
+    frame: u64,
+    /// Time the tuning screen has heard only dead silence.
+    silence: SilenceWatchdog,
+    /// Age of the audio behind the latest detection, in milliseconds.
+    latency_ms: Option<f32>,
+    /// Confirm with the pitch read during the strike's attack.
+    attack_confirm: bool,
+    /// Readings from the latest strike's attack.
+    attack: AttackTracker,
+    /// Latest input for the current strike, checked for false beats.
+    false_beat_window: FalseBeatWindow,
 }
 
 impl App {
@@ -71,23 +252,338 @@ impl App {
             profile: None,
             tuning: None,
             complete: None,
+            training: None,
+            interval_check: None,
+            temperament_checked: false,
+            pending_tones: Vec::new(),
+            smoother: PitchSmoother::new(),
+            min_confidence: ResponsePreset::default().min_confidence(),
+            hold_secs: ResponsePreset::default().hold_secs(),
+            missed_secs: 0.0,
+            tolerances: (DEFAULT_TOLERANCE_CENTS, DEFAULT_TOLERANCE_CENTS),
+            autosave_every: 1,
+            piano: None,
+            unsaved_notes: 0,
+            save_count: 0,
+            single_note: None,
+            practice: None,
+            relative: None,
+            relative_anchor: false,
+            review_from: 0,
+            review_return: None,
+            entered_position: None,
+            beep: false,
+            quiet: false,
+            locked: false,
+            confirm_action: ConfirmAction::default(),
+            recorded_here: false,
+            keyboard_view: KeyboardView::default(),
+            meter_curve: MeterCurve::default(),
+            key_colors: KeyColors::default(),
+            cents_precision: DEFAULT_CENTS_PRECISION,
+            holding_reference: false,
+            key_release: true,
+            auto_profile: false,
+            empty_confirm: EmptyConfirm::default(),
+            octave_beats: false,
+            reduced_motion: false,
+            frame: 0,
+            silence: SilenceWatchdog::new(),
+            latency_ms: None,
+            attack_confirm: false,
+            attack: AttackTracker::new(),
+            false_beat_window: FalseBeatWindow::new(),
             tuning_order: TuningOrder::new(),
             temperament: Temperament::new(),
+            targets: TargetTable::default(),
             current_note_idx: 0,
+            string_layout: StringLayout::new(),
+            spelling: NoteSpelling::default(),
+            language: Language::default(),
+        }
+    }
+
+    /// Use a temperament, rebuilding the note targets from it.
+    fn set_temperament(&mut self, temperament: Temperament) {
+        self.temperament = temperament;
+        self.rebuild_targets();
+    }
+
+    /// Rebuild the note targets from the temperament and the session's
+    /// stretch curve and piano offset.
+    fn rebuild_targets(&mut self) {
+        let stretch = self.session.as_ref().and_then(|s| s.stretch.as_ref());
+        let offset = self.session.as_ref().map_or(0.0, |s| s.piano_offset_cents);
+        self.targets = TargetTable::build(&self.temperament, stretch, offset);
+    }
+
+    /// Change the A4 reference partway through a session. Targets for the
+    /// current and remaining notes follow the new reference, and notes
+    /// already recorded are flagged for review.
+    pub fn set_a4(&mut self, a4: f32) {
+        self.mode_select.set_a4(a4);
+        self.set_temperament(Temperament::with_a4(a4));
+        if let Some(session) = &mut self.session {
+            session.set_a4(a4);
+        }
+        if self.state == AppState::Tuning {
+            self.setup_current_note();
+        }
+    }
+
+    /// Start concert pitch at the given A4 reference.
+    pub fn with_a4(mut self, a4: f32) -> Self {
+        self.mode_select = ModeSelectScreen::new().with_a4(a4);
+        self.set_temperament(Temperament::with_a4(a4));
+        self
+    }
+
+    /// Use the given note name spelling for display.
+    pub fn with_spelling(mut self, spelling: NoteSpelling) -> Self {
+        self.spelling = spelling;
+        if self.state == AppState::Tuning {
+            self.setup_current_note();
+        }
+        self
+    }
+
+    /// Set how quickly readings respond versus how steady they are.
+    pub fn with_response(mut self, preset: ResponsePreset) -> Self {
+        self.smoother = PitchSmoother::with_window(preset.smoothing_window());
+        self.min_confidence = preset.min_confidence();
+        self.hold_secs = preset.hold_secs();
+        self
+    }
+
+    /// Get the smoothing window length, in readings.
+    pub fn smoothing_window(&self) -> usize {
+        self.smoother.window()
+    }
+
+    /// Beep when the reading locks in tune.
+    pub fn with_beep(mut self, beep: bool) -> Self {
+        self.beep = beep;
+        self
+    }
+
+    /// Choose whether confirming a note advances or stays on it.
+    pub fn with_confirm_action(mut self, action: ConfirmAction) -> Self {
+        self.confirm_action = action;
+        self
+    }
+
+    /// Set how the keyboard shows progress while tuning.
+    pub fn with_keyboard_view(mut self, view: KeyboardView) -> Self {
+        self.keyboard_view = view;
+        if let Some(tuning) = &mut self.tuning {
+            tuning.set_keyboard_view(view);
+        }
+        self
+    }
+
+    /// Show cents readouts with this many decimal places (0 to 2).
+    pub fn with_cents_precision(mut self, precision: u8) -> Self {
+        self.cents_precision = precision;
+        if let Some(tuning) = &mut self.tuning {
+            tuning.set_cents_precision(precision);
+        }
+        self
+    }
+
+    /// Start the temperament octave from `midi` instead of F3.
+    ///
+    /// Ignored outside the allowed range, when practising, and for a
+    /// resumed session, which keeps the order it was started with.
+    pub fn with_temperament_start(mut self, midi: u8) -> Self {
+        if self.session.is_none() && self.practice.is_none() {
+            if let Some(order) = TuningOrder::from_midi(midi) {
+                self.tuning_order = order;
+            }
+        }
+        self
+    }
+
+    /// Set how the tuning meter maps cents to positions.
+    pub fn with_meter_curve(mut self, curve: MeterCurve) -> Self {
+        self.meter_curve = curve;
+        if let Some(tuning) = &mut self.tuning {
+            tuning.set_meter_curve(curve);
+        }
+        self
+    }
+
+    /// Set how keys on deviation keyboards are colored.
+    pub fn with_key_colors(mut self, key_colors: KeyColors) -> Self {
+        self.key_colors = key_colors;
+        if let Some(tuning) = &mut self.tuning {
+            tuning.set_key_colors(key_colors);
+        }
+        self
+    }
+
+    /// Set whether key releases are reported. Without them, the A/B key
+    /// toggles the reference on and off instead of playing while held.
+    pub fn with_key_release(mut self, reported: bool) -> Self {
+        self.key_release = reported;
+        self
+    }
+
+    /// Choose what confirming a profiled key with no reading does.
+    pub fn with_empty_confirm(mut self, empty_confirm: EmptyConfirm) -> Self {
+        self.empty_confirm = empty_confirm;
+        self
+    }
+
+    /// Record profiled notes automatically once the reading holds steady.
+    pub fn with_auto_profile(mut self, auto: bool) -> Self {
+        self.auto_profile = auto;
+        self
+    }
+
+    /// Coach high treble notes by their beats against the octave below.
+    pub fn with_octave_beats(mut self, enabled: bool) -> Self {
+        self.octave_beats = enabled;
+        self
+    }
+
+    /// Keep the listening indicator still, for reduced-motion preferences.
+    pub fn with_reduced_motion(mut self, reduced: bool) -> Self {
+        self.reduced_motion = reduced;
+        self
+    }
+
+    /// Confirm notes with the pitch read just after the strike, rather than
+    /// the latest reading.
+    pub fn with_attack_confirm(mut self, enabled: bool) -> Self {
+        self.attack_confirm = enabled;
+        self
+    }
+
+    /// Advance the listening animation by one frame.
+    pub fn tick(&mut self) {
+        self.frame = self.frame.wrapping_add(1);
+        let frame = (!self.reduced_motion).then_some(self.frame);
+        if let Some(profiling) = &mut self.profiling {
+            profiling.set_animation_frame(frame);
+        }
+        if let Some(tuning) = &mut self.tuning {
+            tuning.set_animation_frame(frame);
+        }
+        self.release_reference_off_tuning();
+    }
+
+    /// Never queue any tones.
+    pub fn with_quiet(mut self, quiet: bool) -> Self {
+        self.quiet = quiet;
+        self
+    }
+
+    /// Use the given language for coaching instructions.
+    pub fn with_language(mut self, language: Language) -> Self {
+        self.language = language;
+        if let Some(tuning) = &mut self.tuning {
+            tuning.set_language(language);
+        }
+        self
+    }
+
+    /// Set the meter's in-tune tolerance and the completion tolerance, in cents.
+    pub fn with_tolerances(mut self, display: f32, confirm: f32) -> Self {
+        self.tolerances = (display, confirm);
+        if let Some(tuning) = &mut self.tuning {
+            tuning.set_tolerances(display, confirm);
+        }
+        self
+    }
+
+    /// Auto-save the session every `notes` notes (0 saves only on quit).
+    pub fn with_autosave_every(mut self, notes: usize) -> Self {
+        self.autosave_every = notes;
+        self
+    }
+
+    /// Label new sessions with the piano being tuned.
+    pub fn with_piano(mut self, piano: Option<String>) -> Self {
+        self.piano = piano;
+        self
+    }
+
+    /// Number of times the session has been saved.
+    pub fn save_count(&self) -> usize {
+        self.save_count
+    }
+
+    /// Use per-instrument string counts for coaching.
+    pub fn with_string_layout(mut self, layout: StringLayout) -> Self {
+        self.string_layout = layout;
+        if self.state == AppState::Tuning {
+            self.setup_current_note();
         }
+        self
     }
 
     /// Create app with an existing session (for resume).
     pub fn with_session(session: Session) -> Self {
         let mut app = Self::new();
         app.current_note_idx = session.current_note_index;
-        app.temperament = Temperament::with_a4(session.a4_reference);
+        if let Some(order) = session.temperament_start.and_then(TuningOrder::from_midi) {
+            app.tuning_order = order;
+        }
+        app.set_temperament(Temperament::with_a4(session.a4_reference));
         app.session = Some(session);
+        app.rebuild_targets();
+        if let Some(session) = app
+            .session
+            .as_ref()
+            .filter(|s| s.mode == TuningMode::Relative)
+        {
+            let results = session
+                .completed_notes
+                .iter()
+                .filter(|n| n.status != NoteStatus::Skipped)
+                .filter_map(|n| Note::from_name(&n.note).map(|note| (note.midi, n.final_cents)));
+            app.relative = Some(RelativeTargets::replay(results, &app.targets));
+        }
+        app.state = AppState::Tuning;
+        app.setup_current_note();
+        app
+    }
+
+    /// Create an app that tunes one note and quits once it is confirmed.
+    ///
+    /// No session is recorded.
+    pub fn single_note(note: &'static Note, a4: f32) -> Self {
+        let mut app = Self::new();
+        app.single_note = Some(note);
+        app.set_temperament(Temperament::with_a4(a4));
+        app.state = AppState::Tuning;
+        app.setup_current_note();
+        app
+    }
+
+    /// Create an app that loops the temperament octave, scoring each pass.
+    ///
+    /// No session is recorded.
+    pub fn temperament_practice(a4: f32) -> Self {
+        let mut app = Self::new();
+        app.set_temperament(Temperament::with_a4(a4));
+        app.tuning_order = TuningOrder::only_temperament();
+        app.practice = Some(TemperamentPractice::new(app.temperament));
         app.state = AppState::Tuning;
         app.setup_current_note();
         app
     }
 
+    /// Get the temperament practice results, if practising.
+    pub fn practice(&self) -> Option<&TemperamentPractice> {
+        self.practice.as_ref()
+    }
+
+    /// Get the profiling screen, if profiling.
+    pub fn profiling(&self) -> Option<&ProfilingScreen> {
+        self.profiling.as_ref()
+    }
+
     /// Get current state.
     pub fn state(&self) -> AppState {
         self.state
@@ -118,6 +614,31 @@ impl App {
         self.tuning.as_ref().map(|t| t.target_freq())
     }
 
+    /// Take the tones queued for playback since the last call.
+    pub fn take_tone_requests(&mut self) -> Vec<ToneRequest> {
+        std::mem::take(&mut self.pending_tones)
+    }
+
+    /// Get the tuning screen, if tuning.
+    pub fn tuning(&self) -> Option<&TuningScreen> {
+        self.tuning.as_ref()
+    }
+
+    /// Get the complete screen, once the session has ended.
+    pub fn complete(&self) -> Option<&CompleteScreen> {
+        self.complete.as_ref()
+    }
+
+    /// Get the training screen, if training.
+    pub fn training(&self) -> Option<&TrainingScreen> {
+        self.training.as_ref()
+    }
+
+    /// Get the temperament check screen, if checking.
+    pub fn interval_check(&self) -> Option<&IntervalCheckScreen> {
+        self.interval_check.as_ref()
+    }
+
     /// Handle key press event.
     pub fn handle_key(&mut self, key: KeyCode) {
         match self.state {
@@ -125,7 +646,26 @@ impl App {
             AppState::Calibration => self.handle_calibration_key(key),
             AppState::Profiling => self.handle_profiling_key(key),
             AppState::Tuning => self.handle_tuning_key(key),
+            AppState::IntervalCheck => self.handle_interval_check_key(key),
             AppState::Complete => self.handle_complete_key(key),
+            AppState::Training => self.handle_training_key(key),
+        }
+        self.release_reference_off_tuning();
+    }
+
+    /// Stop a held reference once tuning is left, so it doesn't resume
+    /// on return after a release was missed.
+    fn release_reference_off_tuning(&mut self) {
+        if self.state != AppState::Tuning {
+            self.holding_reference = false;
+        }
+    }
+
+    /// Handle key release event.
+    pub fn handle_key_release(&mut self, key: KeyCode) {
+        if matches!(key, KeyCode::Char('a') | KeyCode::Char('A')) && self.key_release {
+            // Let the string ring on its own again
+            self.holding_reference = false;
         }
     }
 
@@ -134,6 +674,12 @@ impl App {
             KeyCode::Up | KeyCode::Down | KeyCode::Tab => {
                 self.mode_select.next();
             }
+            KeyCode::Left if self.mode_select.selected() == SelectedMode::ConcertPitch => {
+                self.mode_select.prev_pitch();
+            }
+            KeyCode::Right if self.mode_select.selected() == SelectedMode::ConcertPitch => {
+                self.mode_select.next_pitch();
+            }
             KeyCode::Enter => {
                 self.start_session();
             }
@@ -148,7 +694,7 @@ impl App {
         match key {
             KeyCode::Char('s') | KeyCode::Char('S') => {
                 // Skip calibration, use 440 Hz
-                self.temperament = Temperament::new();
+                self.set_temperament(Temperament::new());
                 self.start_tuning();
             }
             KeyCode::Char('q') | KeyCode::Char('Q') | KeyCode::Esc => {
@@ -159,11 +705,20 @@ impl App {
     }
 
     fn handle_tuning_key(&mut self, key: KeyCode) {
+        if self.tuning.as_ref().is_some_and(|t| t.is_editing_comment()) {
+            self.handle_comment_key(key);
+            return;
+        }
+
         match key {
             KeyCode::Char(' ') => {
                 // Confirm current note/step
                 self.confirm_note();
             }
+            KeyCode::Enter if self.confirm_action == ConfirmAction::Stay => {
+                // Move on from a note confirmed in place
+                self.advance_to_next_note();
+            }
             KeyCode::Char('b') | KeyCode::Char('B') => {
                 // Go back to previous step or note
                 self.go_back();
@@ -172,21 +727,84 @@ impl App {
                 // Toggle piano progress display
                 self.toggle_piano_progress();
             }
+            KeyCode::Char('r') | KeyCode::Char('R') => {
+                // Toggle reference comparison
+                if let Some(tuning) = &mut self.tuning {
+                    tuning.toggle_compare();
+                }
+            }
+            KeyCode::Char('a') | KeyCode::Char('A') => {
+                // Play the reference while held, to A/B against the string
+                self.holding_reference = self.key_release || !self.holding_reference;
+            }
+            KeyCode::Char('h') | KeyCode::Char('H') => {
+                // Toggle holding the last reading through silence
+                if let Some(tuning) = &mut self.tuning {
+                    tuning.toggle_hold();
+                }
+            }
+            KeyCode::Char('c') | KeyCode::Char('C') => {
+                // Write a remark on this note
+                if let Some(tuning) = &mut self.tuning {
+                    tuning.start_comment();
+                }
+            }
+            KeyCode::Char('n') | KeyCode::Char('N') => {
+                // Revisit a note that ended outside tolerance
+                self.jump_to_out_of_tolerance();
+            }
             KeyCode::Char('s') | KeyCode::Char('S') => {
                 // Skip current note
                 self.skip_note();
             }
+            KeyCode::Char('l') | KeyCode::Char('L') => {
+                // Accept the current note without tuning it
+                self.leave_note_as_is();
+            }
+            KeyCode::Char('o') | KeyCode::Char('O') => {
+                // Accept the rest of this octave without visiting each note
+                self.leave_octave_as_is();
+            }
+            KeyCode::Char('f') | KeyCode::Char('F') => {
+                // Skip remaining notes and finish
+                self.finish_early();
+            }
             KeyCode::Char('q') | KeyCode::Char('Q') | KeyCode::Esc => {
                 // Save session before quitting
-                if let Some(session) = &self.session {
-                    let _ = session.save();
-                }
+                self.save_session();
                 self.quit();
             }
             _ => {}
         }
     }
 
+    /// Type into the remark on the current note.
+    fn handle_comment_key(&mut self, key: KeyCode) {
+        let Some(tuning) = &mut self.tuning else {
+            return;
+        };
+        match key {
+            KeyCode::Char(c) => tuning.type_comment(c),
+            KeyCode::Backspace => tuning.backspace_comment(),
+            KeyCode::Enter => {
+                tuning.commit_comment();
+                // A note already recorded on this visit takes the new remark
+                if self.recorded_here {
+                    self.attach_comment();
+                }
+            }
+            KeyCode::Esc => tuning.cancel_comment(),
+            _ => {}
+        }
+    }
+
+    /// Copy the current note's remark onto its latest recorded result.
+    fn attach_comment(&mut self) {
+        if let (Some(session), Some(tuning)) = (&mut self.session, &self.tuning) {
+            session.comment_last(tuning.comment());
+        }
+    }
+
     /// Toggle piano progress display.
     fn toggle_piano_progress(&mut self) {
         if let Some(tuning) = &mut self.tuning {
@@ -200,6 +818,70 @@ impl App {
                 // Start new session
                 self.reset();
             }
+            KeyCode::Char('n') | KeyCode::Char('N') => {
+                self.jump_to_out_of_tolerance();
+            }
+            KeyCode::Char('q') | KeyCode::Char('Q') | KeyCode::Esc => {
+                self.quit();
+            }
+            _ => {}
+        }
+    }
+
+    fn handle_interval_check_key(&mut self, key: KeyCode) {
+        match key {
+            KeyCode::Up => {
+                if let Some(check) = &mut self.interval_check {
+                    check.previous();
+                }
+            }
+            KeyCode::Down => {
+                if let Some(check) = &mut self.interval_check {
+                    check.next();
+                }
+            }
+            KeyCode::Enter | KeyCode::Char('s') | KeyCode::Char('S') => {
+                // Resume the order where it left off
+                self.interval_check = None;
+                self.state = AppState::Tuning;
+            }
+            KeyCode::Char('q') | KeyCode::Char('Q') | KeyCode::Esc => {
+                self.save_session();
+                self.quit();
+            }
+            _ => {}
+        }
+    }
+
+    fn handle_training_key(&mut self, key: KeyCode) {
+        match key {
+            KeyCode::Up => {
+                if let Some(training) = &mut self.training {
+                    training.raise();
+                }
+            }
+            KeyCode::Down => {
+                if let Some(training) = &mut self.training {
+                    training.lower();
+                }
+            }
+            KeyCode::Char('r') | KeyCode::Char('R') => {
+                self.queue_training_tones();
+            }
+            KeyCode::Char(' ') => {
+                if let Some(training) = &mut self.training {
+                    training.reveal();
+                }
+            }
+            KeyCode::Enter => {
+                let advanced = self
+                    .training
+                    .as_mut()
+                    .is_some_and(|training| training.next_round());
+                if advanced {
+                    self.queue_training_tones();
+                }
+            }
             KeyCode::Char('q') | KeyCode::Char('Q') | KeyCode::Esc => {
                 self.quit();
             }
@@ -207,12 +889,67 @@ impl App {
         }
     }
 
+    /// Queue a tone for playback unless in quiet mode.
+    fn queue_tone(&mut self, tone: ToneRequest) {
+        if !self.quiet {
+            self.pending_tones.push(tone);
+        }
+    }
+
+    /// Queue a progress cue when beeping is on.
+    fn queue_cue(&mut self, cue: Cue) {
+        if self.beep {
+            for &(frequency, duration) in ReferenceTone::cue_tones(cue) {
+                self.queue_tone(ToneRequest {
+                    frequency,
+                    duration,
+                });
+            }
+        }
+    }
+
+    /// Queue the reference tone followed by the simulated string.
+    fn queue_training_tones(&mut self) {
+        if let Some(training) = &self.training {
+            let round = training.round();
+            let target = round.target_frequency(&self.temperament);
+            let simulated = round.simulated_frequency(&self.temperament);
+            for frequency in [target, simulated] {
+                self.queue_tone(ToneRequest {
+                    frequency,
+                    duration: TRAINING_TONE_SECS,
+                });
+            }
+        }
+    }
+
+    /// Track the in-tune state, beeping when the reading first locks.
+    fn update_lock(&mut self, in_tune: bool) {
+        if self.beep && in_tune && !self.locked {
+            self.queue_tone(LOCK_BEEP);
+        }
+        self.locked = in_tune;
+    }
+
+    /// Start ear training.
+    fn start_training(&mut self) {
+        self.set_temperament(Temperament::new());
+        self.training = Some(TrainingScreen::new());
+        self.state = AppState::Training;
+        self.queue_training_tones();
+    }
+
     /// Start a new tuning session based on selected mode.
     fn start_session(&mut self) {
         let mode = match self.mode_select.selected() {
             SelectedMode::QuickTune => TuningMode::Quick,
             SelectedMode::ConcertPitch => TuningMode::Concert,
             SelectedMode::Profile => TuningMode::Profile,
+            SelectedMode::RelativeTune => TuningMode::Relative,
+            SelectedMode::Training => {
+                self.start_training();
+                return;
+            }
         };
 
         match mode {
@@ -220,8 +957,8 @@ impl App {
                 self.state = AppState::Calibration;
                 self.calibration.reset();
             }
-            TuningMode::Concert => {
-                self.temperament = Temperament::new();
+            TuningMode::Concert | TuningMode::Relative => {
+                self.set_temperament(Temperament::with_a4(self.mode_select.a4()));
                 self.start_tuning();
             }
             TuningMode::Profile => {
@@ -232,13 +969,22 @@ impl App {
 
     /// Start the profiling phase.
     fn start_profiling(&mut self) {
-        self.profiling = Some(ProfilingScreen::new());
-        self.temperament = Temperament::new();
+        self.smoother.reset();
+        self.set_temperament(Temperament::with_a4(self.mode_select.a4()));
+        self.profiling = Some(
+            ProfilingScreen::new()
+                .with_spelling(self.spelling)
+                .with_temperament(self.temperament)
+                .with_auto_confirm(self.auto_profile)
+                .with_empty_confirm(self.empty_confirm)
+                .with_key_colors(self.key_colors, self.tolerances.0),
+        );
         self.state = AppState::Profiling;
     }
 
     /// Handle key press in profiling state.
     fn handle_profiling_key(&mut self, key: KeyCode) {
+        let previous_idx = self.profiling.as_ref().map(|p| p.current_note_idx());
         match key {
             KeyCode::Char(' ') => {
                 // Confirm current note
@@ -254,6 +1000,17 @@ impl App {
                     profiling.go_back();
                 }
             }
+            KeyCode::Left | KeyCode::Right | KeyCode::Up | KeyCode::Down => {
+                // Move around the keyboard by key or by octave
+                if let Some(profiling) = &mut self.profiling {
+                    match key {
+                        KeyCode::Left => profiling.move_by(-1),
+                        KeyCode::Right => profiling.move_by(1),
+                        KeyCode::Up => profiling.move_octaves(1),
+                        _ => profiling.move_octaves(-1),
+                    }
+                }
+            }
             KeyCode::Char('s') | KeyCode::Char('S') => {
                 // Skip current note
                 if let Some(profiling) = &mut self.profiling {
@@ -267,6 +1024,11 @@ impl App {
             }
             _ => {}
         }
+
+        // Readings from the previous note must not bleed into the next
+        if self.profiling.as_ref().map(|p| p.current_note_idx()) != previous_idx {
+            self.smoother.reset();
+        }
     }
 
     /// Finish profiling and transition to tuning.
@@ -281,7 +1043,9 @@ impl App {
             }
 
             // Create tuning order based on profile deviations
-            self.tuning_order = TuningOrder::from_profile(&profile);
+            self.tuning_order =
+                TuningOrder::from_profile_at(&profile, self.tuning_order.temperament_start())
+                    .unwrap_or_else(|| TuningOrder::from_profile(&profile));
             self.profile = Some(profile);
 
             // Start tuning with the profile-based order
@@ -293,25 +1057,75 @@ impl App {
     fn start_tuning(&mut self) {
         let mode = match self.mode_select.selected() {
             SelectedMode::QuickTune => TuningMode::Quick,
-            SelectedMode::ConcertPitch => TuningMode::Concert,
+            // Training never starts a tuning session
+            SelectedMode::ConcertPitch | SelectedMode::Training => TuningMode::Concert,
             SelectedMode::Profile => TuningMode::Profile,
+            SelectedMode::RelativeTune => TuningMode::Relative,
         };
+        self.relative = (mode == TuningMode::Relative).then(RelativeTargets::new);
+
+        // Calibration found where the piano's A4 sits; the session tunes to
+        // concert pitch moved by that offset
+        let offset = (mode == TuningMode::Quick).then(|| {
+            let a4 = self.temperament.a4();
+            self.temperament.cents_from_target(a4, 440.0)
+        });
+        if offset.is_some() {
+            self.temperament = Temperament::new();
+        }
 
-        self.session = Some(Session::new(mode, self.temperament.a4()));
+        let mut session = match &self.profile {
+            Some(profile) if mode == TuningMode::Concert => {
+                Session::from_profile_to_concert(profile, self.temperament.a4())
+            }
+            Some(profile) => Session::from_profile(profile, mode, self.temperament.a4()),
+            None => Session::new(mode, self.temperament.a4()),
+        }
+        .with_temperament_start(self.tuning_order.temperament_start());
+        session.piano = self.piano.clone();
+        if let Some(offset) = offset {
+            session.set_offset(offset);
+        }
+        self.session = Some(session);
+        self.rebuild_targets();
+        self.tuning = None;
         self.current_note_idx = 0;
+        self.review_from = 0;
+        self.review_return = None;
+        self.entered_position = None;
         self.state = AppState::Tuning;
         self.setup_current_note();
     }
 
     /// Set up the tuning screen for the current note.
     fn setup_current_note(&mut self) {
-        if self.current_note_idx >= 88 {
+        self.smoother.reset();
+        // A reference held on the last note would play the wrong pitch
+        self.holding_reference = false;
+        self.missed_secs = 0.0;
+        self.locked = false;
+        self.recorded_here = false;
+        self.attack.reset();
+        self.false_beat_window.reset();
+
+        if self.current_note_idx >= self.tuning_order.len() {
             self.finish_session();
             return;
         }
 
-        if let Some(note) = self.tuning_order.note_at(self.current_note_idx) {
-            let target_freq = self.temperament.frequency(note.midi);
+        let note = match self.single_note {
+            Some(note) => Some(note),
+            None => self.tuning_order.note_at(self.current_note_idx),
+        };
+        if let Some(note) = note {
+            let relative_target = self
+                .relative
+                .as_ref()
+                .map(|relative| relative.target(note.midi, &self.targets));
+            self.relative_anchor = relative_target == Some(None);
+            let target_freq = relative_target
+                .flatten()
+                .unwrap_or_else(|| self.targets.target(note.midi));
 
             // Collect completed chromatic indices from session (midi - 21)
             let completed_notes: HashSet<usize> = if let Some(session) = &self.session {
@@ -328,15 +1142,62 @@ impl App {
                 HashSet::new()
             };
 
-            let mut tuning = TuningScreen::new(
-                note.display_name(),
-                self.current_note_idx,
-                88,
-                target_freq,
-                note.strings,
-                note.midi,
-            );
+            let note_name = note.display_name_with(self.spelling);
+            let strings = self.string_layout.strings_for(note);
+            let mut tuning = match self.tuning.take() {
+                Some(mut tuning) => {
+                    tuning.load_note(
+                        note_name,
+                        self.current_note_idx,
+                        target_freq,
+                        strings,
+                        note.midi,
+                    );
+                    tuning
+                }
+                None => {
+                    let mut tuning = TuningScreen::new(
+                        note_name,
+                        self.current_note_idx,
+                        self.tuning_order.len(),
+                        target_freq,
+                        strings,
+                        note.midi,
+                    );
+                    tuning.set_tolerances(self.tolerances.0, self.tolerances.1);
+                    tuning.set_language(self.language);
+                    tuning
+                }
+            };
             tuning.set_completed_notes(completed_notes);
+            tuning.set_keyboard_view(self.keyboard_view);
+            tuning.set_meter_curve(self.meter_curve);
+            tuning.set_key_colors(self.key_colors);
+            tuning.set_cents_precision(self.cents_precision);
+            let next = match self.single_note {
+                Some(_) => None,
+                None => self.tuning_order.note_at(self.current_note_idx + 1),
+            };
+            tuning.set_next_key(next.map(|n| (n.midi - 21) as usize));
+            tuning.set_octave_reference(self.octave_reference_for(note));
+            if let Some(session) = &mut self.session {
+                if self.entered_position != Some(self.current_note_idx) {
+                    session.enter_note(note.display_name());
+                    self.entered_position = Some(self.current_note_idx);
+                }
+            }
+            if let Some(session) = &self.session {
+                tuning.set_octave_progress(session.progress_by_octave());
+                tuning.set_starting_deviations(
+                    session
+                        .starting_cents
+                        .iter()
+                        .filter_map(|(name, &cents)| {
+                            Note::from_name(name).map(|n| ((n.midi - 21) as usize, cents))
+                        })
+                        .collect(),
+                );
+            }
             self.tuning = Some(tuning);
         }
     }
@@ -344,46 +1205,192 @@ impl App {
     /// Update with detected pitch.
     pub fn update_pitch(&mut self, freq: f32, confidence: f32) {
         match self.state {
-            AppState::Calibration => {
-                if confidence > 0.8 {
-                    self.calibration.update(freq);
-                    if self.calibration.is_complete() {
-                        if let Some(a4) = self.calibration.result() {
-                            self.temperament = Temperament::with_a4(a4);
-                        }
-                        self.start_tuning();
+            AppState::Calibration if confidence > 0.8 => {
+                self.calibration.update(freq);
+                if self.calibration.is_complete() {
+                    if let Some(a4) = self.calibration.result() {
+                        self.set_temperament(Temperament::with_a4(a4));
                     }
+                    self.start_tuning();
+                }
+            }
+            AppState::Profiling | AppState::Tuning if confidence <= self.min_confidence => {
+                self.clear_pitch();
+                // Show the unsure guess, tagged, rather than nothing
+                if let (AppState::Tuning, Some(tuning)) = (self.state, &mut self.tuning) {
+                    tuning.set_raw_estimate(Some(freq));
                 }
             }
             AppState::Profiling => {
+                self.missed_secs = 0.0;
                 if let Some(profiling) = &mut self.profiling {
-                    if confidence > 0.6 {
-                        let note = profiling.current_note();
-                        let target = self.temperament.frequency(note.midi);
-                        let cents = self.temperament.cents_from_target(freq, target);
-                        profiling.update(freq, cents);
-                    } else {
-                        profiling.clear();
+                    match profiling.target_freq() {
+                        Some(target) => {
+                            let freq = self.smoother.push(freq);
+                            let cents = self.temperament.cents_from_target(freq, target);
+                            profiling.update(freq, cents);
+                            profiling.set_confidence(confidence);
+                        }
+                        None => profiling.clear(),
                     }
                 }
+                self.auto_confirm_profiling();
             }
             AppState::Tuning => {
+                self.missed_secs = 0.0;
                 if let Some(tuning) = &mut self.tuning {
-                    if confidence > 0.6 {
-                        let target = tuning.target_freq();
-                        let cents = self.temperament.cents_from_target(freq, target);
-                        tuning.update(freq, cents);
-                    } else {
-                        tuning.clear();
+                    // The first note tuned relatively is taken where it is
+                    if self.relative_anchor {
+                        tuning.set_target_freq(freq);
+                        self.relative_anchor = false;
+                    }
+                    let target = tuning.target_freq();
+                    // The attack is judged on raw readings, not smoothed ones
+                    self.attack
+                        .record(self.temperament.cents_from_target(freq, target));
+                    let freq = self.smoother.push(freq);
+                    let cents = self.temperament.cents_from_target(freq, target);
+                    tuning.update(freq, cents);
+                    tuning.set_confidence(confidence);
+
+                    let (heard, _) = self.temperament.nearest_note(freq);
+                    if let Some(note) = Note::from_midi(heard) {
+                        tuning.set_heard_note(note.display_name_with(self.spelling));
                     }
                 }
+                let in_tune = self.tuning.as_ref().is_some_and(|t| t.shows_in_tune());
+                self.update_lock(in_tune);
             }
             _ => {}
         }
     }
 
+    /// Frequency of the reference tone to keep looping, if any.
+    pub fn looping_tone(&self) -> Option<f32> {
+        if self.quiet {
+            return None;
+        }
+        match (self.state, &self.tuning) {
+            (AppState::Tuning, Some(tuning)) if tuning.is_comparing() || self.holding_reference => {
+                Some(tuning.target_freq())
+            }
+            _ => None,
+        }
+    }
+
+    /// Record the profiled note once its reading has held steady.
+    fn auto_confirm_profiling(&mut self) {
+        let Some(profiling) = &mut self.profiling else {
+            return;
+        };
+        if !profiling.should_auto_confirm() {
+            return;
+        }
+
+        self.smoother.reset();
+        if profiling.confirm_note() {
+            self.finish_profiling();
+        }
+    }
+
+    /// Check if a screen that shows the octave warning is up, so callers
+    /// only run the octave check when its result is used.
+    pub fn shows_octave_warning(&self) -> bool {
+        self.profiling.is_some() || self.tuning.is_some()
+    }
+
+    /// Flag that the detected note's octave is sounding too.
+    pub fn set_octave_warning(&mut self, ambiguous: bool) {
+        if let Some(profiling) = &mut self.profiling {
+            profiling.set_octave_warning(ambiguous);
+        }
+        if let Some(tuning) = &mut self.tuning {
+            tuning.set_octave_warning(ambiguous);
+        }
+    }
+
+    /// Set whether the microphone input is clipping.
+    pub fn set_clipping(&mut self, clipping: bool) {
+        if let Some(tuning) = &mut self.tuning {
+            tuning.set_clipping(clipping);
+        }
+    }
+
+    /// Feed the input level over the last `elapsed_secs`, so a long dead
+    /// silence while tuning can prompt a microphone check and dropouts are
+    /// held for the response preset's duration.
+    pub fn update_input_level(&mut self, level: f32, elapsed_secs: f32) {
+        // Dropouts are held by time, whatever the buffer size
+        self.missed_secs += elapsed_secs.max(0.0);
+        if self.state != AppState::Tuning {
+            self.silence.reset();
+            self.attack.reset();
+            self.false_beat_window.reset();
+            return;
+        }
+        // A new strike starts a fresh false beat window
+        if self.attack.update_level(level, elapsed_secs) {
+            self.false_beat_window.reset();
+        }
+        let silent = self.silence.update(level, elapsed_secs);
+        if let Some(tuning) = &mut self.tuning {
+            tuning.set_no_audio(silent);
+        }
+    }
+
+    /// Feed the latest input buffer, of which only the last `fresh` samples
+    /// are new, checking the note for false beats once enough of it has
+    /// been heard. Unsure readings are collected but never raise the warning.
+    pub fn update_false_beats(
+        &mut self,
+        samples: &[f32],
+        fresh: usize,
+        sample_rate: u32,
+        confident: bool,
+    ) {
+        // Overlapping reads would repeat audio and fake a longer window
+        let fresh = fresh.min(samples.len());
+        self.false_beat_window
+            .push(&samples[samples.len() - fresh..], sample_rate);
+        let Some(tuning) = &mut self.tuning else {
+            return;
+        };
+        let false_beats = self
+            .false_beat_window
+            .check(sample_rate, tuning.target_freq())
+            .unwrap_or(false);
+        tuning.set_false_beats(confident && false_beats);
+    }
+
+    /// Set whether the microphone buffer is still filling after a start or flush.
+    pub fn set_warming_up(&mut self, warming_up: bool) {
+        if let Some(profiling) = &mut self.profiling {
+            profiling.set_warming_up(warming_up);
+        }
+        if let Some(tuning) = &mut self.tuning {
+            tuning.set_warming_up(warming_up);
+        }
+    }
+
+    /// Record how old the audio behind the latest detection was.
+    pub fn set_latency_ms(&mut self, latency_ms: Option<f32>) {
+        self.latency_ms = latency_ms;
+    }
+
+    /// Age of the audio behind the latest detection, in milliseconds.
+    pub fn latency_ms(&self) -> Option<f32> {
+        self.latency_ms
+    }
+
     /// Clear pitch detection (silence).
     pub fn clear_pitch(&mut self) {
+        // Ride out brief dropouts so the reading doesn't flicker
+        if matches!(self.state, AppState::Profiling | AppState::Tuning)
+            && self.missed_secs < self.hold_secs
+        {
+            return;
+        }
+
         match self.state {
             AppState::Calibration => {
                 self.calibration.clear();
@@ -397,6 +1404,7 @@ impl App {
                 if let Some(tuning) = &mut self.tuning {
                     tuning.clear();
                 }
+                self.locked = false;
             }
             _ => {}
         }
@@ -404,20 +1412,52 @@ impl App {
 
     /// Confirm current note is tuned.
     fn confirm_note(&mut self) {
-        if let Some(tuning) = &mut self.tuning {
-            // For multi-string notes (bichord/trichord), advance through steps
-            if tuning.is_multi_string() && tuning.next_step() {
-                return;
-            }
+        let Some(tuning) = &mut self.tuning else {
+            return;
+        };
+
+        // For multi-string notes (bichord/trichord), advance through steps
+        if tuning.is_multi_string() && tuning.next_step() {
+            self.queue_cue(Cue::StringDone);
+            return;
+        }
+        self.queue_cue(Cue::NoteDone);
+
+        if let Some(tuning) = &self.tuning {
+            // The strike's attack reads truer than its decayed tail
+            let cents = self
+                .attack_confirm
+                .then(|| self.attack.attack_cents())
+                .flatten()
+                .unwrap_or(tuning.cents());
+            let recorded = self.session_cents(cents);
 
             // Record completion
-            if let Some(session) = &mut self.session {
-                if let Some(note) = self.tuning_order.note_at(self.current_note_idx) {
-                    session.complete_note(note.display_name(), tuning.cents());
+            if let Some(note) = self.tuning_order.note_at(self.current_note_idx) {
+                if let Some(session) = &mut self.session {
+                    // Re-confirming while staying replaces this visit's result
+                    if self.recorded_here {
+                        session.reconfirm_last(recorded);
+                    } else {
+                        session.complete_note(note.display_name(), recorded);
+                    }
+                    session.comment_last(tuning.comment());
+                }
+                if let Some(practice) = &mut self.practice {
+                    practice.record(note.midi, cents);
                 }
             }
+            self.record_relative_pitch(cents);
 
-            self.advance_to_next_note();
+            match self.confirm_action {
+                ConfirmAction::Advance => self.advance_to_next_note(),
+                ConfirmAction::Stay => {
+                    self.recorded_here = true;
+                    if let Some(session) = &mut self.session {
+                        session.current_note_index = self.current_note_idx;
+                    }
+                }
+            }
         }
     }
 
@@ -449,21 +1489,172 @@ impl App {
 
     /// Skip current note.
     fn skip_note(&mut self) {
+        // A note confirmed in place keeps its result
+        if self.recorded_here {
+            self.advance_to_next_note();
+            return;
+        }
+
         // Record as skipped (0 cents)
         if let Some(session) = &mut self.session {
             if let Some(note) = self.tuning_order.note_at(self.current_note_idx) {
-                session.complete_note(note.display_name(), 0.0);
+                session.record_note(note.display_name(), 0.0, NoteStatus::Skipped);
+                session.comment_last(self.tuning.as_ref().and_then(|t| t.comment()));
+            }
+        }
+
+        self.advance_to_next_note();
+    }
+
+    /// Leave the current note as it is, recording its current deviation.
+    fn leave_note_as_is(&mut self) {
+        let Some(tuning) = &self.tuning else {
+            return;
+        };
+        let cents = tuning.cents();
+        let recorded = self.session_cents(cents);
+        if let Some(session) = &mut self.session {
+            if let Some(note) = self.tuning_order.note_at(self.current_note_idx) {
+                session.record_note(note.display_name(), recorded, NoteStatus::LeftAsIs);
+                session.comment_last(tuning.comment());
             }
         }
+        self.record_relative_pitch(cents);
+
+        self.advance_to_next_note();
+    }
+
+    /// Leave the current note and the rest of its octave as they are.
+    ///
+    /// Marks every not-yet-recorded note of the current note's octave, wherever
+    /// it falls in the tuning order, so a touch-up session can pass over a
+    /// register that is already acceptable. Notes other than the current one
+    /// record their profiled deviation, or 0 cents when none was measured,
+    /// and are passed over when they come up.
+    fn leave_octave_as_is(&mut self) {
+        if self.practice.is_some() || self.single_note.is_some() {
+            return;
+        }
+        let Some(cents) = self.tuning.as_ref().map(|t| t.cents()) else {
+            return;
+        };
+        let recorded = self.session_cents(cents);
+        let (Some(tuning), Some(session)) = (&self.tuning, &mut self.session) else {
+            return;
+        };
+        let Some(octave) = self
+            .tuning_order
+            .note_at(self.current_note_idx)
+            .map(|n| n.octave)
+        else {
+            return;
+        };
+
+        let current = self.tuning_order.note_at(self.current_note_idx);
+        let rest: Vec<&Note> = (0..self.tuning_order.len())
+            .filter_map(|position| self.tuning_order.note_at(position))
+            .filter(|note| note.octave == octave && Some(*note) != current)
+            .filter(|note| session.status_for(&note.display_name()).is_none())
+            .collect();
+
+        if let Some(note) = current {
+            session.record_note(note.display_name(), recorded, NoteStatus::LeftAsIs);
+            session.comment_last(tuning.comment());
+        }
+        for note in rest {
+            let name = note.display_name();
+            let profiled = session.starting_cents_for(&name).unwrap_or(0.0);
+            session.record_note(name, profiled, NoteStatus::LeftAsIs);
+        }
+        self.record_relative_pitch(cents);
 
         self.advance_to_next_note();
     }
 
+    /// The tuned note an octave below `note` and the pitch it was left at,
+    /// when `note` is tuned by octave beats.
+    fn octave_reference_for(&self, note: &Note) -> Option<(String, f32)> {
+        if !self.octave_beats || note.midi < OCTAVE_BEATS_LOW_MIDI {
+            return None;
+        }
+        let lower = Note::from_midi(note.midi - 12)?;
+        let pitch = match self.relative.as_ref().and_then(|r| r.pitch(lower.midi)) {
+            Some(pitch) => pitch,
+            None => {
+                let cents = self
+                    .session
+                    .as_ref()?
+                    .final_cents_for(&lower.display_name())?;
+                self.temperament
+                    .cents_to_frequency(self.targets.target(lower.midi), cents)
+            }
+        };
+        Some((lower.display_name_with(self.spelling), pitch))
+    }
+
+    /// Cents to record for the current note, `cents` off its shown target.
+    ///
+    /// A relative note with nothing tuned to set it against is targeted at
+    /// its own first reading, so it is recorded against the temperament to
+    /// keep its actual pitch for a resumed session.
+    fn session_cents(&self, cents: f32) -> f32 {
+        let (Some(relative), Some(tuning)) = (&self.relative, &self.tuning) else {
+            return cents;
+        };
+        match self.tuning_order.note_at(self.current_note_idx) {
+            Some(note) if relative.reference_for(note.midi).is_none() => {
+                let pitch = self
+                    .temperament
+                    .cents_to_frequency(tuning.target_freq(), cents);
+                self.temperament
+                    .cents_from_target(pitch, self.targets.target(note.midi))
+            }
+            _ => cents,
+        }
+    }
+
+    /// Remember the pitch the current note was left at, `cents` off its
+    /// target, so later notes can be tuned against it.
+    fn record_relative_pitch(&mut self, cents: f32) {
+        let (Some(relative), Some(tuning)) = (&mut self.relative, &self.tuning) else {
+            return;
+        };
+        if let Some(note) = self.tuning_order.note_at(self.current_note_idx) {
+            let pitch = self
+                .temperament
+                .cents_to_frequency(tuning.target_freq(), cents);
+            relative.record(note.midi, pitch);
+        }
+    }
+
     /// Advance to the next note.
     fn advance_to_next_note(&mut self) {
+        if self.single_note.is_some() {
+            self.quit();
+            return;
+        }
+
+        if let Some((position, state)) = self.review_return.take() {
+            self.return_from_review(position, state);
+            return;
+        }
+
         self.current_note_idx += 1;
 
-        if self.current_note_idx >= 88 {
+        // Practice loops back to the start of the octave
+        if let Some(practice) = &mut self.practice {
+            if self.current_note_idx >= self.tuning_order.len() {
+                practice.next_pass();
+                self.current_note_idx = 0;
+            }
+        }
+
+        // Notes left with the rest of their octave don't come up again
+        while self.practice.is_none() && self.already_left_as_is(self.current_note_idx) {
+            self.current_note_idx += 1;
+        }
+
+        if self.current_note_idx >= self.tuning_order.len() {
             self.finish_session();
         } else {
             self.setup_current_note();
@@ -471,20 +1662,141 @@ impl App {
             // Update session progress
             if let Some(session) = &mut self.session {
                 session.current_note_index = self.current_note_idx;
-                let _ = session.save();
             }
+
+            self.unsaved_notes += 1;
+            if self.autosave_every > 0 && self.unsaved_notes >= self.autosave_every {
+                self.save_session();
+            }
+
+            if self.temperament_just_set() {
+                self.temperament_checked = true;
+                self.interval_check = Some(IntervalCheckScreen::new(
+                    &self.temperament,
+                    self.tuning_order.temperament_start(),
+                ));
+                self.state = AppState::IntervalCheck;
+            }
+        }
+    }
+
+    /// Whether the note at `position` was already left as is.
+    fn already_left_as_is(&self, position: usize) -> bool {
+        let (Some(note), Some(session)) = (self.tuning_order.note_at(position), &self.session)
+        else {
+            return false;
+        };
+        session.status_for(&note.display_name()) == Some(NoteStatus::LeftAsIs)
+    }
+
+    /// Whether the order just left the temperament octave with some of it
+    /// tuned, for the first time this session.
+    fn temperament_just_set(&self) -> bool {
+        let position = self.current_note_idx;
+        let left_octave = position > 0
+            && self.tuning_order.is_temperament_phase(position - 1)
+            && !self.tuning_order.is_temperament_phase(position);
+        let low = self.tuning_order.temperament_start();
+        let octave = low..=low + (TEMPERAMENT_HIGH_MIDI - TEMPERAMENT_LOW_MIDI);
+        let octave_tuned = self.session.as_ref().is_some_and(|session| {
+            session.completed_notes.iter().any(|n| {
+                n.status == NoteStatus::Tuned
+                    && Note::from_name(&n.note).is_some_and(|note| octave.contains(&note.midi))
+            })
+        });
+        left_octave && octave_tuned && !self.temperament_checked
+    }
+
+    /// Jump to the next recorded note still outside the completion tolerance.
+    ///
+    /// Works mid-session and from the complete screen. Returns false when
+    /// every recorded note is within tolerance.
+    pub fn jump_to_out_of_tolerance(&mut self) -> bool {
+        let Some(session) = &self.session else {
+            return false;
+        };
+        let Some(idx) = session.next_out_of_tolerance(self.review_from, self.tolerances.1) else {
+            return false;
+        };
+        let Some(position) = Note::from_name(&session.completed_notes[idx].note)
+            .and_then(|note| self.tuning_order.position_of(note.midi))
+        else {
+            return false;
+        };
+
+        self.review_from = idx + 1;
+        // Jumping on from a revisit keeps the original place to come back to
+        if self.review_return.is_none() {
+            self.review_return = Some((self.current_note_idx, self.state));
+        }
+        self.current_note_idx = position;
+        self.state = AppState::Tuning;
+        self.setup_current_note();
+        true
+    }
+
+    /// Go back to where tuning was before jumping to a revisited note.
+    fn return_from_review(&mut self, position: usize, state: AppState) {
+        if let (Some(note), Some(session)) = (
+            self.tuning_order.note_at(self.current_note_idx),
+            &mut self.session,
+        ) {
+            session.fold_latest(&note.display_name());
         }
+
+        self.current_note_idx = position;
+        if state == AppState::Complete {
+            self.finish_session();
+            return;
+        }
+        self.state = AppState::Tuning;
+        self.setup_current_note();
+        if let Some(session) = &mut self.session {
+            session.current_note_index = position;
+        }
+    }
+
+    /// Save the session to disk, ignoring errors.
+    fn save_session(&mut self) {
+        if let Some(session) = &self.session {
+            let _ = session.save();
+            self.save_count += 1;
+            self.unsaved_notes = 0;
+        }
+    }
+
+    /// End the session now, leaving unvisited notes untuned.
+    pub fn finish_early(&mut self) {
+        if self.state != AppState::Tuning {
+            return;
+        }
+        if self.single_note.is_some() || self.practice.is_some() {
+            self.quit();
+            return;
+        }
+        if let Some(session) = &mut self.session {
+            session.finish();
+        }
+        self.save_session();
+        self.finish_session();
     }
 
     /// Finish the tuning session.
     fn finish_session(&mut self) {
-        if let Some(session) = self.session.take() {
+        // The session is kept so out-of-tolerance notes can be revisited
+        if let Some(session) = &self.session {
             let completed_notes = session.completed_notes.clone();
-            self.complete = Some(CompleteScreen::new(completed_notes));
+            self.complete = Some(
+                CompleteScreen::new(completed_notes).with_starting_cents(&session.starting_cents),
+            );
         } else {
             self.complete = Some(CompleteScreen::new(Vec::new()));
         }
         self.state = AppState::Complete;
+
+        for tone in FANFARE {
+            self.queue_tone(tone);
+        }
     }
 
     /// Reset to start a new session.
@@ -495,9 +1807,17 @@ impl App {
         self.profile = None;
         self.tuning = None;
         self.complete = None;
+        self.training = None;
+        self.interval_check = None;
+        self.temperament_checked = false;
+        self.pending_tones.clear();
         self.current_note_idx = 0;
-        self.tuning_order = TuningOrder::new();
-        self.mode_select = ModeSelectScreen::new();
+        self.review_from = 0;
+        self.review_return = None;
+        self.entered_position = None;
+        self.tuning_order =
+            TuningOrder::from_midi(self.tuning_order.temperament_start()).unwrap_or_default();
+        self.mode_select = ModeSelectScreen::new().with_a4(self.mode_select.a4());
         self.calibration = CalibrationScreen::new();
     }
 
@@ -522,11 +1842,21 @@ impl App {
                     frame.render_widget(tuning, area);
                 }
             }
+            AppState::IntervalCheck => {
+                if let Some(check) = &self.interval_check {
+                    frame.render_widget(check, area);
+                }
+            }
             AppState::Complete => {
                 if let Some(complete) = &self.complete {
                     frame.render_widget(complete, area);
                 }
             }
+            AppState::Training => {
+                if let Some(training) = &self.training {
+                    frame.render_widget(training, area);
+                }
+            }
         }
     }
 }
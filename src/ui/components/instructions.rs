@@ -7,7 +7,8 @@ use ratatui::{
     widgets::{Block, Borders, Widget},
 };
 
-use crate::ui::theme::Theme;
+use crate::ui::language::Language;
+use crate::ui::theme::{BoxChars, Theme};
 
 /// Step in the tuning process for multi-string notes.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -66,26 +67,22 @@ impl TuningStep {
 
     /// Get the step title.
     pub fn title(&self) -> &'static str {
-        match self {
-            Self::MuteBichord => "Mute right string",
-            Self::TuneBichord => "Tune left string",
-            Self::MuteOuter => "Mute outer strings",
-            Self::TuneCenter => "Tune center string",
-            Self::TuneLeft => "Tune left string",
-            Self::TuneRight => "Tune right string",
-        }
+        self.title_in(Language::English)
     }
 
     /// Get instruction text.
     pub fn instruction(&self) -> &'static str {
-        match self {
-            Self::MuteBichord => "Use a felt wedge or rubber mute to mute the right string. Only the left string should sound.",
-            Self::TuneBichord => "Tune the left string to pitch. Then remove the mute and tune the right string to match.",
-            Self::MuteOuter => "Use felt strip or rubber mutes to mute the outer strings. Only the center string should sound.",
-            Self::TuneCenter => "Tune the center string to the target pitch using the meter.",
-            Self::TuneLeft => "Unmute the left string. Tune it to match the center string until you hear no beats.",
-            Self::TuneRight => "Unmute the right string. Tune it to match the center string until you hear no beats.",
-        }
+        self.instruction_in(Language::English)
+    }
+
+    /// Get the step title in the given language.
+    pub fn title_in(&self, language: Language) -> &'static str {
+        language.step_text(*self).title
+    }
+
+    /// Get instruction text in the given language.
+    pub fn instruction_in(&self, language: Language) -> &'static str {
+        language.step_text(*self).instruction
     }
 
     /// Get the next step.
@@ -113,10 +110,62 @@ impl TuningStep {
     }
 }
 
+/// Row of dots showing progress through a note's steps, e.g. `●●○○`.
+///
+/// Monochord notes have no steps and render nothing.
+pub struct StepDots {
+    step: Option<TuningStep>,
+}
+
+impl StepDots {
+    /// Create dots for the current step, if any.
+    pub fn new(step: Option<TuningStep>) -> Self {
+        Self { step }
+    }
+
+    /// The dots as text: filled up to and including the current step.
+    pub fn pattern(&self) -> String {
+        let Some(step) = self.step else {
+            return String::new();
+        };
+        (1..=step.total_steps())
+            .map(|n| {
+                if n <= step.number() {
+                    BoxChars::DOT_FILLED
+                } else {
+                    BoxChars::DOT_EMPTY
+                }
+            })
+            .collect()
+    }
+}
+
+impl Widget for StepDots {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        if area.height == 0 {
+            return;
+        }
+        for (i, dot) in self.pattern().chars().enumerate() {
+            let x = area.x + i as u16;
+            if x >= area.x + area.width {
+                break;
+            }
+            let style = if dot == BoxChars::DOT_FILLED {
+                Theme::accent()
+            } else {
+                Theme::muted()
+            };
+            buf.set_string(x, area.y, dot.to_string(), style);
+        }
+    }
+}
+
 /// Instructions panel for coaching the user.
 pub struct Instructions {
     step: Option<TuningStep>,
-    direction_hint: Option<String>,
+    /// Cents deviation to hint a pin direction for.
+    direction_hint: Option<f32>,
+    language: Language,
 }
 
 impl Instructions {
@@ -125,6 +174,7 @@ impl Instructions {
         Self {
             step: Some(step),
             direction_hint: None,
+            language: Language::default(),
         }
     }
 
@@ -133,21 +183,23 @@ impl Instructions {
         Self {
             step: None,
             direction_hint: None,
+            language: Language::default(),
         }
     }
 
     /// Set a direction hint based on cents deviation.
     pub fn with_direction_hint(mut self, cents: f32) -> Self {
         if cents.abs() > 5.0 {
-            let hint = if cents < 0.0 {
-                "Turn tuning pin CLOCKWISE (tighten) slightly"
-            } else {
-                "Turn tuning pin COUNTER-CLOCKWISE (loosen) slightly"
-            };
-            self.direction_hint = Some(hint.to_string());
+            self.direction_hint = Some(cents);
         }
         self
     }
+
+    /// Render in the given language.
+    pub fn with_language(mut self, language: Language) -> Self {
+        self.language = language;
+        self
+    }
 }
 
 impl Widget for Instructions {
@@ -164,24 +216,34 @@ impl Widget for Instructions {
             return;
         }
 
+        let phrases = self.language.phrases();
         let mut y = inner.y;
 
         if let Some(step) = &self.step {
             // Multi-string note with steps (bichord or trichord)
             // Step indicator
             let step_text = format!(
-                "Step {} of {}: {}",
+                "{} {} {} {}: {}",
+                phrases.step,
                 step.number(),
+                phrases.of,
                 step.total_steps(),
-                step.title()
+                step.title_in(self.language)
             );
             let step_style = Theme::accent();
             buf.set_string(inner.x + 1, y, &step_text, step_style);
-            y += 2;
+            y += 1;
+
+            // Step dots on the line under the step indicator
+            if y < inner.y + inner.height {
+                let dots = Rect::new(inner.x + 1, y, inner.width.saturating_sub(2), 1);
+                StepDots::new(Some(*step)).render(dots, buf);
+            }
+            y += 1;
 
             // Instruction text
             if y < inner.y + inner.height {
-                let instruction = step.instruction();
+                let instruction = step.instruction_in(self.language);
                 let available_width = inner.width.saturating_sub(2) as usize;
 
                 // Word wrap
@@ -195,13 +257,17 @@ impl Widget for Instructions {
             }
         } else {
             // Monochord note - simple instruction
-            let text = "Tune this string to the target pitch using the meter.";
-            buf.set_string(inner.x + 1, y, text, Style::default());
+            buf.set_string(inner.x + 1, y, phrases.simple, Style::default());
             y += 2;
         }
 
         // Direction hint (not shown during muting steps)
-        if let Some(hint) = &self.direction_hint {
+        if let Some(cents) = self.direction_hint {
+            let hint = if cents < 0.0 {
+                phrases.tighten
+            } else {
+                phrases.loosen
+            };
             if y < inner.y + inner.height {
                 y += 1;
                 buf.set_string(inner.x + 1, y, hint, Theme::warning());
@@ -210,11 +276,10 @@ impl Widget for Instructions {
 
         // Press SPACE prompt
         if y + 1 < inner.y + inner.height {
-            let prompt = "Press SPACE to continue";
             buf.set_string(
                 inner.x + 1,
                 inner.y + inner.height - 1,
-                prompt,
+                phrases.press_space,
                 Theme::muted(),
             );
         }
@@ -244,3 +309,68 @@ fn textwrap(text: &str, max_width: usize) -> Vec<String> {
 
     lines
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn render_text(instructions: Instructions) -> String {
+        let area = Rect::new(0, 0, 80, 10);
+        let mut buf = Buffer::empty(area);
+        instructions.render(area, &mut buf);
+        (0..area.height)
+            .map(|y| {
+                (0..area.width)
+                    .map(|x| buf[(x, y)].symbol())
+                    .collect::<String>()
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    #[test]
+    fn test_language_changes_rendered_title() {
+        let english = render_text(Instructions::for_step(TuningStep::TuneCenter, 3));
+        let spanish = render_text(
+            Instructions::for_step(TuningStep::TuneCenter, 3).with_language(Language::Spanish),
+        );
+
+        assert!(english.contains("Step 2 of 4: Tune center string"));
+        assert!(spanish.contains("Paso 2 de 4: Afinar cuerda central"));
+    }
+
+    #[test]
+    fn test_direction_hint_translated() {
+        let text = render_text(
+            Instructions::simple()
+                .with_direction_hint(-12.0)
+                .with_language(Language::Spanish),
+        );
+        assert!(text.contains("HORARIO (apretar)"));
+    }
+
+    #[test]
+    fn test_step_dots_show_progress() {
+        assert_eq!(
+            StepDots::new(Some(TuningStep::TuneCenter)).pattern(),
+            "●●○○"
+        );
+        assert_eq!(StepDots::new(Some(TuningStep::MuteBichord)).pattern(), "●○");
+        assert_eq!(StepDots::new(Some(TuningStep::TuneRight)).pattern(), "●●●●");
+        assert_eq!(StepDots::new(None).pattern(), "");
+
+        let text = render_text(Instructions::for_step(TuningStep::TuneCenter, 3));
+        let lines: Vec<&str> = text.lines().collect();
+        assert!(lines[1].contains("Step 2 of 4"));
+        assert_eq!(lines[2].trim_matches(|c| c == '│' || c == ' '), "●●○○");
+
+        let simple = render_text(Instructions::simple());
+        assert!(!simple.contains(BoxChars::DOT_FILLED));
+        assert!(!simple.contains(BoxChars::DOT_EMPTY));
+    }
+
+    #[test]
+    fn test_default_title_is_english() {
+        assert_eq!(TuningStep::TuneCenter.title(), "Tune center string");
+    }
+}
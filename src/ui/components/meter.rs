@@ -1,18 +1,72 @@
 //! Cents deviation meter component.
 
-use ratatui::{buffer::Buffer, layout::Rect, widgets::Widget};
+use ratatui::{buffer::Buffer, layout::Rect, style::Modifier, widgets::Widget};
 
+use crate::ui::format::{self, DEFAULT_CENTS_PRECISION};
 use crate::ui::theme::{BoxChars, Theme};
 
+/// Default full-scale deflection in cents.
+pub const DEFAULT_RANGE_CENTS: f32 = 500.0;
+
+/// Detection confidence at or above which the indicator is drawn solid.
+pub const SOLID_CONFIDENCE: f32 = 0.9;
+/// Detection confidence below which the indicator is drawn faintest.
+pub const FAINT_CONFIDENCE: f32 = 0.75;
+
+/// Animation frames each spinner glyph is shown for.
+pub const FRAMES_PER_GLYPH: u64 = 4;
+
+/// Cents either side of center that the expanded curve stretches.
+pub const EXPANDED_ZONE_CENTS: f32 = 10.0;
+/// Share of the half width the expanded zone takes up.
+const EXPANDED_ZONE_SHARE: f32 = 0.5;
+
+/// How readings map to positions on the meter.
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    Default,
+    serde::Serialize,
+    serde::Deserialize,
+    clap::ValueEnum,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum MeterCurve {
+    /// The in-tune zone holds still at center; readings outside it spread
+    /// evenly on the log scale.
+    #[default]
+    Linear,
+    /// The ±10 cent region spans half the meter so small errors visibly
+    /// move the indicator; larger errors are compressed toward the edges.
+    Expanded,
+}
+
 /// Cents deviation meter for visualizing pitch accuracy.
-/// Uses logarithmic scale for ±500 cents with a fixed "in-tune" zone at center.
+/// Uses logarithmic scale up to the display range with a fixed "in-tune" zone at center.
 pub struct Meter {
-    /// Current cents deviation from target (±500 cents range, logarithmic scale).
+    /// Current cents deviation from target (logarithmic scale).
     cents: f32,
     /// Whether we're currently detecting a pitch.
     detecting: bool,
     /// Tolerance threshold in cents.
     tolerance: f32,
+    /// Full-scale deflection in cents; readings beyond it are pegged.
+    range: f32,
+    /// Message shown while not detecting.
+    idle_message: &'static str,
+    /// Mapping from cents to position.
+    curve: MeterCurve,
+    /// Detection confidence of the reading (0.0 to 1.0).
+    confidence: f32,
+    /// Animation frame for the idle spinner; `None` keeps it still.
+    frame: Option<u64>,
+    /// Where the note sat before, e.g. in a baseline profile.
+    previous: Option<f32>,
+    /// Decimal places on the cents readout.
+    precision: u8,
 }
 
 impl Meter {
@@ -22,6 +76,13 @@ impl Meter {
             cents,
             detecting: true,
             tolerance: 5.0,
+            range: DEFAULT_RANGE_CENTS,
+            idle_message: "Listening...",
+            curve: MeterCurve::default(),
+            confidence: 1.0,
+            frame: None,
+            previous: None,
+            precision: DEFAULT_CENTS_PRECISION,
         }
     }
 
@@ -31,6 +92,125 @@ impl Meter {
             cents: 0.0,
             detecting: false,
             tolerance: 5.0,
+            range: DEFAULT_RANGE_CENTS,
+            idle_message: "Listening...",
+            curve: MeterCurve::default(),
+            confidence: 1.0,
+            frame: None,
+            previous: None,
+            precision: DEFAULT_CENTS_PRECISION,
+        }
+    }
+
+    /// Create a meter shown while the microphone buffer is still filling.
+    pub fn warming_up() -> Self {
+        Self {
+            idle_message: "Warming up...",
+            ..Self::listening()
+        }
+    }
+
+    /// Set the full-scale deflection in cents (e.g. 25, 50, 100).
+    pub fn with_range(mut self, cents: f32) -> Self {
+        // Keep the range outside the in-tune zone so the log scale is defined
+        self.range = cents.abs().max(self.tolerance * 2.0);
+        self
+    }
+
+    /// Set how readings map to positions.
+    pub fn with_curve(mut self, curve: MeterCurve) -> Self {
+        self.curve = curve;
+        self
+    }
+
+    /// Set the detection confidence; weaker readings draw a fainter indicator.
+    pub fn with_confidence(mut self, confidence: f32) -> Self {
+        self.confidence = confidence.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Animate the idle message with a spinner at `frame`; `None` disables it.
+    pub fn with_frame(mut self, frame: Option<u64>) -> Self {
+        self.frame = frame;
+        self
+    }
+
+    /// Set the decimal places on the cents readout (0 to 2).
+    pub fn with_precision(mut self, precision: u8) -> Self {
+        self.precision = precision;
+        self
+    }
+
+    /// Mark where the note sat before, so its drift stays in view.
+    pub fn with_previous(mut self, cents: f32) -> Self {
+        self.previous = Some(cents).filter(|c| c.is_finite());
+        self
+    }
+
+    /// Column offset of the previous-reading marker from center, if any.
+    pub fn previous_offset(&self, half_width: f32) -> Option<f32> {
+        self.previous.map(|cents| self.position(cents, half_width))
+    }
+
+    /// Spinner glyph shown beside the idle message, if animating.
+    pub fn spinner_glyph(&self) -> Option<char> {
+        let spinner = BoxChars::SPINNER;
+        self.frame
+            .map(|frame| spinner[(frame / FRAMES_PER_GLYPH) as usize % spinner.len()])
+    }
+
+    /// Indicator glyph for the reading's confidence.
+    pub fn indicator_glyph(&self) -> char {
+        let [faint, medium, solid] = BoxChars::SHADES;
+        if self.confidence >= SOLID_CONFIDENCE {
+            solid
+        } else if self.confidence >= FAINT_CONFIDENCE {
+            medium
+        } else {
+            faint
+        }
+    }
+
+    /// Get the full-scale deflection in cents.
+    pub fn range(&self) -> f32 {
+        self.range
+    }
+
+    /// Check if the reading is beyond the display range.
+    pub fn is_pegged(&self) -> bool {
+        self.detecting && self.cents.abs() > self.range
+    }
+
+    /// Indicator offset from center, clamped to the display range.
+    pub fn indicator_offset(&self, half_width: f32) -> f32 {
+        let clamped = self.cents.clamp(-self.range, self.range);
+        self.position(clamped, half_width)
+    }
+
+    /// Position of a cents value under this meter's curve.
+    fn position(&self, cents: f32, half_width: f32) -> f32 {
+        match self.curve {
+            MeterCurve::Linear => Self::log_position(cents, self.range, half_width, self.tolerance),
+            MeterCurve::Expanded => Self::expanded_position(cents, self.range, half_width),
+        }
+    }
+
+    /// Whether in-tune readings are drawn as a fixed zone at center.
+    fn holds_in_tune_zone(&self) -> bool {
+        self.curve == MeterCurve::Linear && self.cents.abs() <= self.tolerance
+    }
+
+    /// Scale label for a cents value: hundreds for wide ranges, cents otherwise.
+    fn scale_label(&self, cents: i32) -> String {
+        let value = if self.range >= 100.0 {
+            cents / 100
+        } else {
+            cents
+        };
+        match cents.signum() {
+            -1 => format!("{} {}", BoxChars::FLAT, value),
+            1 => format!("{:+} {}", value, BoxChars::SHARP),
+            _ => "0".to_string(),
         }
     }
 
@@ -65,6 +245,26 @@ impl Meter {
 
         sign * normalized.clamp(0.0, 1.0) * half_width
     }
+
+    /// Convert cents to screen position, stretching the ±10 cent region.
+    /// That region maps evenly onto the inner half of the meter; the rest
+    /// of the range is log-compressed into the outer half.
+    pub fn expanded_position(cents: f32, max_cents: f32, half_width: f32) -> f32 {
+        let sign = cents.signum();
+        let abs_cents = cents.abs().min(max_cents);
+
+        let normalized = if max_cents <= EXPANDED_ZONE_CENTS {
+            abs_cents / max_cents
+        } else if abs_cents <= EXPANDED_ZONE_CENTS {
+            abs_cents / EXPANDED_ZONE_CENTS * EXPANDED_ZONE_SHARE
+        } else {
+            let outer =
+                (abs_cents / EXPANDED_ZONE_CENTS).ln() / (max_cents / EXPANDED_ZONE_CENTS).ln();
+            EXPANDED_ZONE_SHARE + (1.0 - EXPANDED_ZONE_SHARE) * outer
+        };
+
+        sign * normalized * half_width
+    }
 }
 
 impl Widget for Meter {
@@ -75,25 +275,24 @@ impl Widget for Meter {
 
         let center_x = area.x + area.width / 2;
         let half_width = (area.width / 2 - 1) as f32;
-        let max_cents = 500.0;
+        let max_cents = self.range;
+        let edge = max_cents.round() as i32;
 
-        // Draw scale labels (logarithmically spaced)
+        // Draw scale labels (logarithmically spaced): edges, center, and ±100 if inside
         let label_y = area.y;
-        let labels: [(i32, String); 7] = [
-            (-500, format!("{} -5", BoxChars::FLAT)),
-            (-100, "-1".to_string()),
-            (-50, "".to_string()),
+        let mut labels = vec![
+            (-edge, self.scale_label(-edge)),
             (0, "0".to_string()),
-            (50, "".to_string()),
-            (100, "+1".to_string()),
-            (500, format!("+5 {}", BoxChars::SHARP)),
+            (edge, self.scale_label(edge)),
         ];
+        if edge > 100 {
+            // Wide ranges are labelled in hundreds of cents
+            labels.push((-100, "-1".to_string()));
+            labels.push((100, "+1".to_string()));
+        }
 
         for (cents, label) in labels {
-            if label.is_empty() {
-                continue;
-            }
-            let x_offset = Self::log_position(cents as f32, max_cents, half_width, self.tolerance);
+            let x_offset = self.position(cents as f32, half_width);
             let x = (center_x as f32 + x_offset) as u16;
             if x >= area.x && x + label.len() as u16 <= area.x + area.width {
                 let style = if cents == 0 {
@@ -115,13 +314,21 @@ impl Widget for Meter {
         let meter_height = area.height.saturating_sub(4).min(5);
 
         // Draw tick marks at logarithmic positions
-        let tick_values = [-500, -100, -50, -15, 0, 15, 50, 100, 500];
+        let ticks: &[i32] = match self.curve {
+            MeterCurve::Linear => &[-100, -50, -15, 0, 15, 50, 100],
+            MeterCurve::Expanded => &[-100, -10, -5, 0, 5, 10, 100],
+        };
+        let tick_values: Vec<i32> = ticks
+            .iter()
+            .copied()
+            .filter(|t: &i32| t.abs() < edge)
+            .chain([-edge, edge])
+            .collect();
         for row in 0..meter_height {
             let y = meter_y_start + row;
 
             for &tick_cents in &tick_values {
-                let x_offset =
-                    Self::log_position(tick_cents as f32, max_cents, half_width, self.tolerance);
+                let x_offset = self.position(tick_cents as f32, half_width);
                 let x = (center_x as f32 + x_offset) as u16;
                 if x >= area.x && x < area.x + area.width {
                     let char = if tick_cents == 0 {
@@ -139,14 +346,32 @@ impl Widget for Meter {
             }
         }
 
+        // Faint marker where the note sat before; the live indicator wins
+        if let Some(x_offset) = self.previous_offset(half_width) {
+            let x = (center_x as f32 + x_offset) as u16;
+            if x >= area.x && x < area.x + area.width {
+                let style = Theme::muted().add_modifier(Modifier::DIM);
+                for row in 0..meter_height {
+                    buf.set_string(x, meter_y_start + row, BoxChars::GHOST.to_string(), style);
+                }
+            }
+        }
+
         // Fixed in-tune zone width (in characters)
         let in_tune_zone_width: u16 = 7;
 
         // Draw the indicator if detecting
         if self.detecting {
             let style = Theme::style_for_cents(self.cents);
+            // Shade and dim the indicator for less certain readings
+            let glyph = self.indicator_glyph().to_string();
+            let indicator_style = if self.confidence < SOLID_CONFIDENCE {
+                style.add_modifier(Modifier::DIM)
+            } else {
+                style
+            };
 
-            if self.cents.abs() <= self.tolerance {
+            if self.holds_in_tune_zone() {
                 // Within tolerance: draw fixed, wide green zone at center (no movement)
                 let half_zone = in_tune_zone_width / 2;
                 let start_x = center_x.saturating_sub(half_zone).max(area.x);
@@ -155,27 +380,34 @@ impl Widget for Meter {
                 for row in 0..meter_height {
                     let y = meter_y_start + row;
                     for x in start_x..end_x {
-                        buf.set_string(x, y, "█", style);
+                        buf.set_string(x, y, &glyph, indicator_style);
                     }
                 }
             } else {
                 // Outside tolerance: narrow indicator at logarithmic position
-                let clamped_cents = self.cents.clamp(-max_cents, max_cents);
-                let x_offset =
-                    Self::log_position(clamped_cents, max_cents, half_width, self.tolerance);
+                let x_offset = self.indicator_offset(half_width);
                 let indicator_x = (center_x as f32 + x_offset) as u16;
 
+                // Pegged readings get an arrow at the edge instead of a bar
+                let glyph = if !self.is_pegged() {
+                    glyph
+                } else if self.cents < 0.0 {
+                    BoxChars::LEFT_ARROW.to_string()
+                } else {
+                    BoxChars::RIGHT_ARROW.to_string()
+                };
+
                 // Narrow indicator (1-2 chars) when out of tune
                 for row in 0..meter_height {
                     let y = meter_y_start + row;
                     if indicator_x >= area.x && indicator_x < area.x + area.width {
-                        buf.set_string(indicator_x, y, "█", style);
+                        buf.set_string(indicator_x, y, &glyph, indicator_style);
                     }
                 }
             }
 
             // Draw cents value below meter
-            let cents_text = format!("{:+.1} cents", self.cents);
+            let cents_text = format::signed_cents(self.cents, self.precision);
             let cents_x = center_x.saturating_sub(cents_text.len() as u16 / 2);
             let cents_y = meter_y_start + meter_height;
             buf.set_string(cents_x, cents_y, &cents_text, style);
@@ -194,10 +426,15 @@ impl Widget for Meter {
                 }
             }
         } else {
-            // Show "Listening..." message
-            let msg = "Listening...";
-            let msg_x = center_x.saturating_sub(msg.len() as u16 / 2);
+            // Show the idle message, led by the spinner when animating
+            let msg = self.idle_message;
+            let width = msg.len() as u16 + if self.frame.is_some() { 2 } else { 0 };
+            let mut msg_x = center_x.saturating_sub(width / 2);
             let msg_y = meter_y_start + meter_height / 2;
+            if let Some(glyph) = self.spinner_glyph() {
+                buf.set_string(msg_x, msg_y, glyph.to_string(), Theme::accent());
+                msg_x += 2;
+            }
             buf.set_string(msg_x, msg_y, msg, Theme::muted());
         }
     }
@@ -342,6 +579,41 @@ mod tests {
         assert_eq!(meter.tolerance, 5.0);
     }
 
+    #[test]
+    fn test_meter_warming_up() {
+        let meter = Meter::warming_up();
+        assert!(!meter.detecting);
+        assert_eq!(meter.idle_message, "Warming up...");
+    }
+
+    #[test]
+    fn test_listening_spinner_turns_with_frames() {
+        let render = |meter: Meter| {
+            let area = Rect::new(0, 0, 60, 10);
+            let mut buf = Buffer::empty(area);
+            meter.render(area, &mut buf);
+            (0..area.width)
+                .map(|x| buf[(x, 4)].symbol().to_string())
+                .collect::<String>()
+        };
+
+        let first = render(Meter::listening().with_frame(Some(0)));
+        let next = render(Meter::listening().with_frame(Some(FRAMES_PER_GLYPH)));
+        assert!(first.contains("◐ Listening..."), "{first}");
+        assert!(next.contains("◓ Listening..."), "{next}");
+        assert_eq!(
+            Meter::listening()
+                .with_frame(Some(FRAMES_PER_GLYPH * 4))
+                .spinner_glyph(),
+            Some('◐')
+        );
+
+        // Reduced motion: no spinner at all
+        let still = render(Meter::listening().with_frame(None));
+        assert!(still.contains("Listening..."));
+        assert!(!BoxChars::SPINNER.iter().any(|&g| still.contains(g)));
+    }
+
     #[test]
     fn test_meter_with_custom_tolerance() {
         let meter = Meter::new(0.0).tolerance(10.0);
@@ -357,6 +629,124 @@ mod tests {
         assert!(meter.detecting);
     }
 
+    #[test]
+    fn test_meter_default_range() {
+        assert_eq!(Meter::new(0.0).range(), DEFAULT_RANGE_CENTS);
+    }
+
+    #[test]
+    fn test_meter_pegs_beyond_range() {
+        let meter = Meter::new(60.0).with_range(50.0);
+        assert!(meter.is_pegged());
+        assert!((meter.indicator_offset(50.0) - 50.0).abs() < 0.01);
+
+        let meter = Meter::new(-60.0).with_range(50.0);
+        assert!(meter.is_pegged());
+        assert!((meter.indicator_offset(50.0) + 50.0).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_meter_within_wider_range() {
+        let meter = Meter::new(60.0).with_range(100.0);
+        assert!(!meter.is_pegged());
+        let offset = meter.indicator_offset(50.0);
+        assert!(offset > 0.0 && offset < 50.0, "got {}", offset);
+    }
+
+    #[test]
+    fn test_listening_meter_never_pegged() {
+        assert!(!Meter::listening().with_range(25.0).is_pegged());
+    }
+
+    #[test]
+    fn test_expanded_curve_moves_for_small_errors() {
+        let linear = Meter::new(2.0).indicator_offset(50.0);
+        let expanded = Meter::new(2.0)
+            .with_curve(MeterCurve::Expanded)
+            .indicator_offset(50.0);
+
+        assert_eq!(linear, 0.0, "linear holds in-tune readings at center");
+        assert!((expanded - 5.0).abs() < 0.01, "got {}", expanded);
+
+        let flat = Meter::new(-2.0)
+            .with_curve(MeterCurve::Expanded)
+            .indicator_offset(50.0);
+        assert!((flat + expanded).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_expanded_position_compresses_extremes() {
+        let at_zone = Meter::expanded_position(EXPANDED_ZONE_CENTS, 500.0, 50.0);
+        assert!((at_zone - 25.0).abs() < 0.01);
+        let at_edge = Meter::expanded_position(500.0, 500.0, 50.0);
+        assert!((at_edge - 50.0).abs() < 0.01);
+        assert_eq!(Meter::expanded_position(900.0, 500.0, 50.0), at_edge);
+
+        // Ten cents near center take more room than a hundred near the edge
+        let inner = at_zone - Meter::expanded_position(0.0, 500.0, 50.0);
+        let outer = at_edge - Meter::expanded_position(400.0, 500.0, 50.0);
+        assert!(inner > outer * 5.0);
+    }
+
+    #[test]
+    fn test_expanded_position_narrow_range() {
+        let pos = Meter::expanded_position(5.0, 10.0, 50.0);
+        assert!((pos - 25.0).abs() < 0.01);
+    }
+
+    fn render_indicator(confidence: f32) -> ratatui::buffer::Cell {
+        let area = Rect::new(0, 0, 60, 10);
+        let mut buf = Buffer::empty(area);
+        let meter = Meter::new(40.0).with_confidence(confidence);
+        let x = (area.width / 2) as f32 + meter.indicator_offset((area.width / 2 - 1) as f32);
+        meter.render(area, &mut buf);
+        buf[(x as u16, 3)].clone()
+    }
+
+    #[test]
+    fn test_previous_marker_renders_apart_from_live_indicator() {
+        let area = Rect::new(0, 0, 60, 10);
+        let half_width = (area.width / 2 - 1) as f32;
+        let mut buf = Buffer::empty(area);
+        let meter = Meter::new(-40.0).with_previous(-150.0);
+        let live = (area.width / 2) as f32 + meter.indicator_offset(half_width);
+        let previous = (area.width / 2) as f32 + meter.previous_offset(half_width).unwrap();
+        meter.render(area, &mut buf);
+
+        assert_ne!(live as u16, previous as u16);
+        assert_eq!(buf[(previous as u16, 3)].symbol(), "░");
+        assert_eq!(buf[(live as u16, 3)].symbol(), "█");
+        // The note has moved toward the target since
+        assert!(previous < live);
+    }
+
+    #[test]
+    fn test_no_previous_marker_by_default() {
+        let area = Rect::new(0, 0, 60, 10);
+        let mut buf = Buffer::empty(area);
+        Meter::new(-40.0).render(area, &mut buf);
+        let marks = (0..area.width).filter(|&x| buf[(x, 3)].symbol() == "░");
+        assert_eq!(marks.count(), 0);
+    }
+
+    #[test]
+    fn test_low_confidence_renders_fainter() {
+        let strong = render_indicator(0.97);
+        let weak = render_indicator(0.6);
+
+        assert_eq!(strong.symbol(), "█");
+        assert!(!strong.modifier.contains(Modifier::DIM));
+        assert_eq!(weak.symbol(), "▒");
+        assert!(weak.modifier.contains(Modifier::DIM));
+    }
+
+    #[test]
+    fn test_indicator_glyph_by_confidence() {
+        assert_eq!(Meter::new(0.0).indicator_glyph(), '█');
+        assert_eq!(Meter::new(0.0).with_confidence(0.8).indicator_glyph(), '▓');
+        assert_eq!(Meter::new(0.0).with_confidence(0.5).indicator_glyph(), '▒');
+    }
+
     #[test]
     fn test_compact_meter_new() {
         let meter = CompactMeter::new(25.0, 80);
@@ -5,7 +5,7 @@ pub mod meter;
 pub mod piano;
 pub mod progress;
 
-pub use instructions::Instructions;
-pub use meter::Meter;
-pub use piano::Piano;
-pub use progress::Progress;
+pub use instructions::{Instructions, StepDots};
+pub use meter::{Meter, MeterCurve};
+pub use piano::{GlyphSet, KeyColors, Piano};
+pub use progress::{OctaveProgress, Progress};
@@ -31,12 +31,105 @@ pub mod chars {
     pub const WHITE_ON: char = '▓';
     pub const BLACK_OFF: char = '▒';
     pub const BLACK_ON: char = '█';
+    pub const NEXT: char = '░';
     pub const BORDER_WHITE: char = '═';
     pub const BORDER_BLACK: char = '╩';
     pub const CORNER_LEFT: char = '╚';
     pub const CORNER_RIGHT: char = '╝';
 }
 
+/// Glyphs used to draw the keyboard.
+///
+/// Every glyph must be a single-width character so columns stay aligned.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct GlyphSet {
+    pub edge: char,
+    pub white_off: char,
+    pub white_on: char,
+    pub black_off: char,
+    pub black_on: char,
+    pub next: char,
+    pub border_white: char,
+    pub border_black: char,
+    pub corner_left: char,
+    pub corner_right: char,
+}
+
+impl GlyphSet {
+    /// Unicode block and box-drawing glyphs.
+    pub const UNICODE: Self = Self {
+        edge: chars::EDGE,
+        white_off: chars::WHITE_OFF,
+        white_on: chars::WHITE_ON,
+        black_off: chars::BLACK_OFF,
+        black_on: chars::BLACK_ON,
+        next: chars::NEXT,
+        border_white: chars::BORDER_WHITE,
+        border_black: chars::BORDER_BLACK,
+        corner_left: chars::CORNER_LEFT,
+        corner_right: chars::CORNER_RIGHT,
+    };
+
+    /// Plain ASCII glyphs for fonts without block characters.
+    pub const ASCII: Self = Self {
+        edge: '|',
+        white_off: ' ',
+        white_on: '#',
+        black_off: ':',
+        black_on: '@',
+        next: '.',
+        border_white: '=',
+        border_black: '+',
+        corner_left: '\\',
+        corner_right: '/',
+    };
+}
+
+impl Default for GlyphSet {
+    fn default() -> Self {
+        Self::UNICODE
+    }
+}
+
+/// Multiple of the tolerance up to which `KeyColors::Bands` shows a warning.
+pub const WARNING_BAND: f32 = 3.0;
+
+/// How keys with a deviation are colored.
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    Default,
+    serde::Serialize,
+    serde::Deserialize,
+    clap::ValueEnum,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum KeyColors {
+    /// Green to 5 cents, yellow to 15, red beyond, whatever the tolerance.
+    #[default]
+    Fixed,
+    /// Green within tolerance, red outside it.
+    Pass,
+    /// Green within tolerance, yellow to three times it, red beyond.
+    Bands,
+}
+
+impl KeyColors {
+    /// Color for a key `cents` off, under `tolerance` cents.
+    pub fn color(self, cents: f32, tolerance: f32) -> Color {
+        let abs_cents = cents.abs();
+        match self {
+            KeyColors::Fixed => Theme::color_for_cents(cents),
+            _ if abs_cents <= tolerance => Theme::IN_TUNE,
+            KeyColors::Bands if abs_cents <= tolerance * WARNING_BAND => Theme::WARNING,
+            _ => Theme::OUT_OF_TUNE,
+        }
+    }
+}
+
 /// A cell in the piano layout.
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum Cell {
@@ -51,7 +144,7 @@ pub enum Cell {
 /// Piano keyboard visualization widget.
 ///
 /// Generates a piano of `n` keys starting at any MIDI note.
-/// Supports highlighting completed keys and marking the current key.
+/// Supports highlighting completed keys and marking the current and next keys.
 ///
 /// # Example
 ///
@@ -74,12 +167,20 @@ pub struct Piano {
     deviations: HashMap<usize, f32>,
     /// Currently active key (shown with accent color).
     current: Option<usize>,
+    /// Key coming up next (shown with a light marker).
+    next: Option<usize>,
     /// Color for highlighted keys.
     on_color: Color,
     /// Color for current key.
     current_color: Color,
     /// Whether this is a continuing segment (no right corner).
     continuing: bool,
+    /// Glyphs used for drawing.
+    glyphs: GlyphSet,
+    /// How deviations are colored.
+    key_colors: KeyColors,
+    /// Tolerance in cents the deviation colors are judged against.
+    tolerance: f32,
 }
 
 impl Piano {
@@ -91,9 +192,13 @@ impl Piano {
             highlighted: HashSet::new(),
             deviations: HashMap::new(),
             current: None,
+            next: None,
             on_color: Color::Green,
             current_color: Color::Cyan,
             continuing: false,
+            glyphs: GlyphSet::default(),
+            key_colors: KeyColors::default(),
+            tolerance: 5.0,
         }
     }
 
@@ -119,6 +224,12 @@ impl Piano {
         self
     }
 
+    /// Set the key coming up next.
+    pub fn next(mut self, key: Option<usize>) -> Self {
+        self.next = key;
+        self
+    }
+
     /// Set color for highlighted keys.
     pub fn on_color(mut self, color: Color) -> Self {
         self.on_color = color;
@@ -137,6 +248,12 @@ impl Piano {
         self
     }
 
+    /// Set the glyphs used for drawing.
+    pub fn with_glyphs(mut self, glyphs: GlyphSet) -> Self {
+        self.glyphs = glyphs;
+        self
+    }
+
     /// Set per-key deviations for deviation-based coloring.
     /// Keys with deviations will be colored green/yellow/red based on cents.
     pub fn with_deviations(mut self, deviations: HashMap<usize, f32>) -> Self {
@@ -144,6 +261,69 @@ impl Piano {
         self
     }
 
+    /// Color deviations by `key_colors`, judged against `tolerance` cents.
+    pub fn with_key_colors(mut self, key_colors: KeyColors, tolerance: f32) -> Self {
+        self.key_colors = key_colors;
+        self.tolerance = tolerance;
+        self
+    }
+
+    /// Narrow to about `visible_keys` keys centered on `current_midi`,
+    /// clamped to this keyboard's ends. The window starts and ends on white
+    /// keys, and key indices shift to stay on the same notes.
+    pub fn auto_center(mut self, current_midi: u8, visible_keys: usize) -> Self {
+        let (start, count) = self.window_around(current_midi, visible_keys);
+        let shift = (start - self.start_midi) as usize;
+        let keep = |i: usize| (shift..shift + count).contains(&i).then(|| i - shift);
+
+        self.highlighted = self.highlighted.iter().filter_map(|&i| keep(i)).collect();
+        self.deviations = self
+            .deviations
+            .iter()
+            .filter_map(|(&i, &cents)| keep(i).map(|i| (i, cents)))
+            .collect();
+        self.current = self.current.and_then(keep);
+        self.next = self.next.and_then(keep);
+        self.start_midi = start;
+        self.num_keys = count;
+        self
+    }
+
+    /// Center on `current_midi` when the keyboard is wider than `width`
+    /// columns, so the current note stays in view on narrow terminals.
+    pub fn fit_around(self, current_midi: Option<u8>, width: usize) -> Self {
+        match current_midi {
+            Some(midi) if width < self.width() => {
+                self.auto_center(midi, Self::keys_for_width(width))
+            }
+            _ => self,
+        }
+    }
+
+    /// Roughly how many keys fit in `width` columns (14 columns per octave).
+    pub fn keys_for_width(width: usize) -> usize {
+        width.saturating_sub(1) * 12 / 14
+    }
+
+    /// First MIDI note and key count of the window around `current_midi`.
+    fn window_around(&self, current_midi: u8, visible_keys: usize) -> (u8, usize) {
+        let count = visible_keys.clamp(1, self.num_keys);
+        let last_start = self.start_midi + (self.num_keys - count) as u8;
+        let mut start = current_midi
+            .saturating_sub((count / 2) as u8)
+            .clamp(self.start_midi, last_start);
+        let mut end = start + count as u8 - 1;
+
+        let last = self.start_midi + self.num_keys as u8 - 1;
+        if Self::is_black(start % 12) && start > self.start_midi {
+            start -= 1;
+        }
+        if Self::is_black(end % 12) && end < last {
+            end += 1;
+        }
+        (start, (end - start) as usize + 1)
+    }
+
     /// Check if semitone (0-11, where 0=C) is a black key.
     #[inline]
     fn is_black(semitone: u8) -> bool {
@@ -220,62 +400,81 @@ impl Piano {
 
     /// Render a single row to a string.
     fn render_row_to_string(&self, cells: &[Cell], row: usize) -> String {
-        let mut result = String::with_capacity(cells.len());
-
-        for (col, cell) in cells.iter().enumerate() {
-            let ch = match (cell, row) {
-                // Rows 0-1: Top rows
-                (Cell::Edge, 0..=1) => chars::EDGE,
-                (Cell::Black(i), 0..=1) => {
-                    if self.is_on(*i) {
-                        chars::BLACK_ON
-                    } else {
-                        chars::BLACK_OFF
-                    }
+        cells
+            .iter()
+            .enumerate()
+            .map(|(col, cell)| self.glyph(*cell, row, col, cells.len(), self.continuing))
+            .collect()
+    }
+
+    /// Number of cells to draw in `width` columns.
+    ///
+    /// When the keyboard doesn't fit, it is cut after the last edge that
+    /// fits so no key is split and the segment closes on an edge.
+    pub fn visible_cells(cells: &[Cell], width: usize) -> usize {
+        if cells.len() <= width {
+            return cells.len();
+        }
+
+        cells[..width]
+            .iter()
+            .rposition(|c| *c == Cell::Edge)
+            .filter(|&last| last > 0)
+            .map(|last| last + 1)
+            .unwrap_or(width)
+    }
+
+    /// Pick the glyph for a cell in a row of `width` columns.
+    ///
+    /// `continues` closes the border with a continuation glyph instead of a corner.
+    fn glyph(&self, cell: Cell, row: usize, col: usize, width: usize, continues: bool) -> char {
+        let g = &self.glyphs;
+        match (cell, row) {
+            // Rows 0-1: Top rows (all keys visible)
+            (Cell::Edge, 0..=1) => g.edge,
+            (Cell::Black(i) | Cell::White(i), 0..=2) if self.is_next(i) => match cell {
+                Cell::White(_) => g.next,
+                _ if row < 2 => g.next,
+                _ => g.edge,
+            },
+            (Cell::Black(i), 0..=1) => {
+                if self.is_on(i) {
+                    g.black_on
+                } else {
+                    g.black_off
                 }
-                (Cell::White(i), 0..=1) => {
-                    if self.is_on(*i) {
-                        chars::WHITE_ON
-                    } else {
-                        chars::WHITE_OFF
-                    }
+            }
+            (Cell::White(i), 0..=2) => {
+                if self.is_on(i) {
+                    g.white_on
+                } else {
+                    g.white_off
                 }
+            }
 
-                // Row 2: Bottom row
-                (Cell::Edge, 2) => chars::EDGE,
-                (Cell::Black(_), 2) => chars::EDGE,
-                (Cell::White(i), 2) => {
-                    if self.is_on(*i) {
-                        chars::WHITE_ON
-                    } else {
-                        chars::WHITE_OFF
-                    }
-                }
+            // Row 2: Bottom row (black keys become edges)
+            (Cell::Edge | Cell::Black(_), 2) => g.edge,
 
-                // Row 3: Border
-                (cell, 3) => {
-                    if col == 0 {
-                        chars::CORNER_LEFT
-                    } else if col == cells.len() - 1 {
-                        if self.continuing {
-                            chars::BORDER_BLACK
-                        } else {
-                            chars::CORNER_RIGHT
-                        }
+            // Row 3: Border
+            (cell, 3) => {
+                if col == 0 {
+                    g.corner_left
+                } else if col == width - 1 {
+                    if continues {
+                        g.border_black // ╩ for continuation
                     } else {
-                        match cell {
-                            Cell::Edge | Cell::Black(_) => chars::BORDER_BLACK,
-                            Cell::White(_) => chars::BORDER_WHITE,
-                        }
+                        g.corner_right // ╝ for end
+                    }
+                } else {
+                    match cell {
+                        Cell::Edge | Cell::Black(_) => g.border_black,
+                        Cell::White(_) => g.border_white,
                     }
                 }
+            }
 
-                _ => ' ',
-            };
-            result.push(ch);
+            _ => ' ',
         }
-
-        result
     }
 
     /// Check if a key is "on" (highlighted, has deviation, or current).
@@ -285,13 +484,18 @@ impl Piano {
             || self.current == Some(index)
     }
 
+    /// Check if a key is marked as next (the current marker wins).
+    fn is_next(&self, index: usize) -> bool {
+        self.next == Some(index) && self.current != Some(index)
+    }
+
     /// Get style for a key.
     fn key_style(&self, index: usize) -> Style {
-        if self.current == Some(index) {
+        if self.current == Some(index) || self.is_next(index) {
             Style::default().fg(self.current_color)
         } else if let Some(&cents) = self.deviations.get(&index) {
             // Color by deviation: green (in-tune), yellow (warning), red (out-of-tune)
-            Style::default().fg(Theme::color_for_cents(cents))
+            Style::default().fg(self.key_colors.color(cents, self.tolerance))
         } else if self.highlighted.contains(&index) {
             Style::default().fg(self.on_color)
         } else {
@@ -306,63 +510,16 @@ impl Piano {
             return;
         }
 
-        let display_width = cells.len().min(area.width as usize);
+        let display_width = Self::visible_cells(cells, area.width as usize);
+        let continues = self.continuing || display_width < cells.len();
 
         for (col, cell) in cells.iter().take(display_width).enumerate() {
             let x = area.x + col as u16;
 
-            let (ch, style) = match (cell, row) {
-                // Rows 0-1: Top rows (all keys visible)
-                (Cell::Edge, 0..=1) => (chars::EDGE, Style::default()),
-                (Cell::Black(i), 0..=1) => {
-                    let ch = if self.is_on(*i) {
-                        chars::BLACK_ON
-                    } else {
-                        chars::BLACK_OFF
-                    };
-                    (ch, self.key_style(*i))
-                }
-                (Cell::White(i), 0..=1) => {
-                    let ch = if self.is_on(*i) {
-                        chars::WHITE_ON
-                    } else {
-                        chars::WHITE_OFF
-                    };
-                    (ch, self.key_style(*i))
-                }
-
-                // Row 2: Bottom row (black keys become edges)
-                (Cell::Edge, 2) => (chars::EDGE, Style::default()),
-                (Cell::Black(_), 2) => (chars::EDGE, Style::default()),
-                (Cell::White(i), 2) => {
-                    let ch = if self.is_on(*i) {
-                        chars::WHITE_ON
-                    } else {
-                        chars::WHITE_OFF
-                    };
-                    (ch, self.key_style(*i))
-                }
-
-                // Row 3: Border
-                (cell, 3) => {
-                    let ch = if col == 0 {
-                        chars::CORNER_LEFT
-                    } else if col == display_width - 1 {
-                        if self.continuing {
-                            chars::BORDER_BLACK // ╩ for continuation
-                        } else {
-                            chars::CORNER_RIGHT // ╝ for end
-                        }
-                    } else {
-                        match cell {
-                            Cell::Edge | Cell::Black(_) => chars::BORDER_BLACK,
-                            Cell::White(_) => chars::BORDER_WHITE,
-                        }
-                    };
-                    (ch, Style::default())
-                }
-
-                _ => (' ', Style::default()),
+            let ch = self.glyph(*cell, row, col, display_width, continues);
+            let style = match (cell, row) {
+                (Cell::Black(i), 0..=1) | (Cell::White(i), 0..=2) => self.key_style(*i),
+                _ => Style::default(),
             };
 
             buf.set_string(x, y, ch.to_string(), style);
@@ -430,6 +587,24 @@ mod tests {
         assert_eq!(rows[2], "║▓║ ║▓║ ║ ║ ║ ║");
     }
 
+    #[test]
+    fn test_render_current_and_next_distinct() {
+        // Current on C (0), next on D# (3), both black and white
+        let piano = Piano::new(60, 12).current(Some(0)).next(Some(3));
+        let rows = piano.render_to_strings();
+        assert_eq!(rows[0], "║▓▒ ░ ║ ▒ ▒ ▒ ║");
+        assert_eq!(rows[2], "║▓║ ║ ║ ║ ║ ║ ║");
+
+        let piano = Piano::new(60, 12).current(Some(0)).next(Some(2));
+        let rows = piano.render_to_strings();
+        assert_eq!(rows[0], "║▓▒░▒ ║ ▒ ▒ ▒ ║");
+        assert_eq!(rows[2], "║▓║░║ ║ ║ ║ ║ ║");
+
+        // Current wins when both mark the same key
+        let piano = Piano::new(60, 12).current(Some(0)).next(Some(0));
+        assert_eq!(piano.render_to_strings()[0], "║▓▒ ▒ ║ ▒ ▒ ▒ ║");
+    }
+
     #[test]
     fn test_render_continuing() {
         let piano = Piano::new(60, 12).continuing(true);
@@ -439,6 +614,53 @@ mod tests {
         assert!(rows[3].ends_with('╩'));
     }
 
+    #[test]
+    fn test_render_ascii_glyphs() {
+        let highlighted: HashSet<usize> = [0, 1].into_iter().collect();
+        let unicode = Piano::new(60, 12).highlighted(highlighted.clone());
+        let ascii = Piano::new(60, 12)
+            .highlighted(highlighted)
+            .with_glyphs(GlyphSet::ASCII);
+        let rows = ascii.render_to_strings();
+
+        assert_eq!(rows[0], "|#@ : | : : : |");
+        assert_eq!(rows[2], "|#| | | | | | |");
+        assert_eq!(rows[3], "\\=+=+=+=+=+=+=/");
+
+        for (a, u) in rows.iter().zip(unicode.render_to_strings()) {
+            assert_eq!(a.chars().count(), u.chars().count());
+        }
+    }
+
+    #[test]
+    fn test_render_truncated_ends_on_border() {
+        let area = Rect::new(0, 0, 40, 4);
+        let mut buf = Buffer::empty(area);
+        Piano::full().render(area, &mut buf);
+
+        let row = |y: u16| -> String { (0..40).map(|x| buf[(x, y)].symbol()).collect() };
+        let (top, border) = (row(0), row(3));
+
+        // Cut after the last whole edge, leaving the rest blank
+        let visible = top.trim_end().chars().count();
+        assert!(visible < 40);
+        assert_eq!(top.chars().nth(visible - 1), Some(chars::EDGE));
+        assert_eq!(border.chars().nth(visible - 1), Some(chars::BORDER_BLACK));
+        assert!(!border.contains(chars::CORNER_RIGHT));
+    }
+
+    #[test]
+    fn test_visible_cells_lands_on_edge() {
+        let cells = Piano::full().build_cells();
+        // A0-B0 is the first whole segment (5 cells)
+        for width in 5..cells.len() {
+            let visible = Piano::visible_cells(&cells, width);
+            assert!(visible <= width);
+            assert_eq!(cells[visible - 1], Cell::Edge, "width {width}");
+        }
+        assert_eq!(Piano::visible_cells(&cells, cells.len()), cells.len());
+    }
+
     #[test]
     fn test_midi_to_index() {
         let piano = Piano::new(60, 12); // C4 to B4
@@ -449,6 +671,62 @@ mod tests {
         assert_eq!(piano.midi_to_index(72), None); // C5 (after range)
     }
 
+    #[test]
+    fn test_auto_center_follows_current_note() {
+        // Walk the current note up the keyboard in a two-octave window
+        let mut previous_start = 21;
+        for midi in (21..=108).step_by(5) {
+            let piano = Piano::full().auto_center(midi, 24);
+            let start = piano.start_midi();
+            let end = start + piano.num_keys() as u8 - 1;
+
+            assert!(piano.midi_to_index(midi).is_some(), "MIDI {midi} in view");
+            assert!((21..=108).contains(&start) && end <= 108);
+            assert!(start >= previous_start, "window only moves up");
+            assert!(!Piano::is_black(start % 12) && !Piano::is_black(end % 12));
+            previous_start = start;
+        }
+
+        // Mid-keyboard, the note sits near the middle of the window
+        let piano = Piano::full().auto_center(60, 24);
+        let index = piano.midi_to_index(60).unwrap();
+        assert!((11..=13).contains(&index), "C4 at index {index}");
+    }
+
+    #[test]
+    fn test_auto_center_clamps_at_the_ends() {
+        assert_eq!(Piano::full().auto_center(21, 24).start_midi(), 21);
+        let top = Piano::full().auto_center(108, 24);
+        assert_eq!(top.start_midi() + top.num_keys() as u8 - 1, 108);
+    }
+
+    #[test]
+    fn test_auto_center_shifts_key_indices() {
+        let highlighted: HashSet<usize> = [0, 39, 40].into_iter().collect();
+        let piano = Piano::full()
+            .highlighted(highlighted)
+            .current(Some(39))
+            .auto_center(60, 12);
+
+        // C4 (index 39 on the full keyboard) keeps its highlight and focus
+        let c4 = piano.midi_to_index(60).unwrap();
+        assert_eq!(piano.current, Some(c4));
+        assert!(piano.highlighted.contains(&c4));
+        assert!(piano.highlighted.contains(&(c4 + 1)));
+        // A0 scrolled out of view
+        assert_eq!(piano.highlighted.len(), 2);
+    }
+
+    #[test]
+    fn test_fit_around_only_narrows_when_needed() {
+        let wide = Piano::full().width();
+        assert_eq!(Piano::full().fit_around(Some(60), wide).num_keys(), 88);
+
+        let narrow = Piano::full().fit_around(Some(60), 40);
+        assert!(narrow.width() <= 40 + 2);
+        assert!(narrow.midi_to_index(60).is_some());
+    }
+
     #[test]
     fn test_is_black() {
         // C=0, C#=1, D=2, D#=3, E=4, F=5, F#=6, G=7, G#=8, A=9, A#=10, B=11
@@ -538,6 +816,37 @@ mod tests {
         assert_eq!(cells[4], Cell::Edge); // Trailing
     }
 
+    #[test]
+    fn test_tolerance_colors_split_in_and_out_of_tolerance() {
+        // C4 off by 4 cents, C#4 by 20, D4 by 10
+        let deviations: HashMap<usize, f32> = [(0, 4.0), (1, -20.0), (2, 10.0)].into();
+        let color = |key_colors| {
+            let piano = Piano::new(60, 3)
+                .with_deviations(deviations.clone())
+                .with_key_colors(key_colors, 5.0);
+            assert_eq!(piano.render_to_strings()[0], "║▓█▓║");
+            (0..3).map(|i| piano.key_style(i).fg).collect::<Vec<_>>()
+        };
+
+        let pass = color(KeyColors::Pass);
+        assert_eq!(pass[0], Some(Theme::IN_TUNE));
+        assert_eq!(pass[1], Some(Theme::OUT_OF_TUNE));
+        assert_eq!(pass[2], Some(Theme::OUT_OF_TUNE));
+
+        let bands = color(KeyColors::Bands);
+        assert_eq!(bands[0], Some(Theme::IN_TUNE));
+        assert_eq!(bands[1], Some(Theme::OUT_OF_TUNE));
+        assert_eq!(bands[2], Some(Theme::WARNING));
+    }
+
+    #[test]
+    fn test_fixed_colors_ignore_tolerance() {
+        let piano = Piano::new(60, 1)
+            .with_deviations([(0, 4.0)].into())
+            .with_key_colors(KeyColors::Fixed, 2.0);
+        assert_eq!(piano.key_style(0).fg, Some(Theme::IN_TUNE));
+    }
+
     #[test]
     fn test_cross_octave() {
         // B to C crossing: B3, C4 = MIDI 59, 60
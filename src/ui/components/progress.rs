@@ -6,7 +6,10 @@ use ratatui::{
     widgets::{Gauge, Widget},
 };
 
-use crate::ui::theme::Theme;
+use crate::ui::theme::{BoxChars, Theme};
+
+/// Bar width per octave segment, in characters.
+const OCTAVE_BAR_WIDTH: usize = 4;
 
 /// Progress indicator showing current note position.
 pub struct Progress {
@@ -80,6 +83,68 @@ impl Widget for Progress {
     }
 }
 
+/// Per-octave progress as a row of small segments (e.g. `3[██▌ ]`).
+pub struct OctaveProgress {
+    /// (octave, completed, total) per octave.
+    octaves: Vec<(i8, usize, usize)>,
+}
+
+impl OctaveProgress {
+    /// Create from (octave, completed, total) entries.
+    pub fn new(octaves: impl Into<Vec<(i8, usize, usize)>>) -> Self {
+        Self {
+            octaves: octaves.into(),
+        }
+    }
+
+    /// Text for one octave segment.
+    pub fn segment(octave: i8, completed: usize, total: usize) -> String {
+        let fill = if total == 0 {
+            0.0
+        } else {
+            completed as f32 / total as f32
+        };
+        let bar: String = (0..OCTAVE_BAR_WIDTH)
+            .map(|i| {
+                let cell = (fill * OCTAVE_BAR_WIDTH as f32 - i as f32).clamp(0.0, 1.0);
+                if cell <= 0.0 {
+                    ' '
+                } else {
+                    BoxChars::block_for_fill(cell)
+                }
+            })
+            .collect();
+        format!("{}[{}]", octave, bar)
+    }
+}
+
+impl Widget for OctaveProgress {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        if area.height < 1 {
+            return;
+        }
+
+        let mut x = area.x;
+        for (octave, completed, total) in self.octaves {
+            let segment = Self::segment(octave, completed, total);
+            let width = segment.chars().count() as u16;
+            if x + width > area.x + area.width {
+                break;
+            }
+
+            let style = if total > 0 && completed >= total {
+                Theme::in_tune()
+            } else if completed > 0 {
+                Theme::accent()
+            } else {
+                Theme::muted()
+            };
+            buf.set_string(x, area.y, &segment, style);
+            x += width + 1;
+        }
+    }
+}
+
 /// Compact progress for header display.
 pub struct CompactProgress {
     note_name: String,
@@ -104,3 +169,15 @@ impl Widget for CompactProgress {
         buf.set_string(area.x, area.y, &text, Theme::muted());
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_octave_segment_fill() {
+        assert_eq!(OctaveProgress::segment(0, 3, 3), "0[████]");
+        assert_eq!(OctaveProgress::segment(4, 0, 12), "4[    ]");
+        assert_eq!(OctaveProgress::segment(2, 6, 12), "2[██  ]");
+    }
+}
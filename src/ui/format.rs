@@ -0,0 +1,52 @@
+//! Number formatting shared by the screens.
+
+/// Decimal places shown on cents readouts by default.
+pub const DEFAULT_CENTS_PRECISION: u8 = 1;
+/// Most decimal places a cents readout shows.
+pub const MAX_CENTS_PRECISION: u8 = 2;
+
+/// Round `cents` half away from zero at `precision` decimal places.
+///
+/// Rounding happens before formatting, so 7.85 shows as 7.9 even though
+/// the nearest `f32` lies just below it.
+fn round_cents(cents: f32, precision: u8) -> f32 {
+    let scale = 10f32.powi(precision.min(MAX_CENTS_PRECISION) as i32);
+    (cents * scale).round() / scale
+}
+
+/// Cents with `precision` decimal places, e.g. "7.9¢".
+pub fn cents(value: f32, precision: u8) -> String {
+    let precision = precision.min(MAX_CENTS_PRECISION) as usize;
+    format!("{:.*}¢", precision, round_cents(value, precision as u8))
+}
+
+/// Signed cents with `precision` decimal places, e.g. "+7.9 cents".
+pub fn signed_cents(value: f32, precision: u8) -> String {
+    let precision = precision.min(MAX_CENTS_PRECISION) as usize;
+    format!(
+        "{:+.*} cents",
+        precision,
+        round_cents(value, precision as u8)
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cents_at_each_precision() {
+        assert_eq!(cents(7.85, 0), "8¢");
+        assert_eq!(cents(7.85, 1), "7.9¢");
+        assert_eq!(cents(7.85, 2), "7.85¢");
+        // Beyond the maximum, two places are shown
+        assert_eq!(cents(7.85, 5), "7.85¢");
+    }
+
+    #[test]
+    fn test_signed_cents() {
+        assert_eq!(signed_cents(7.85, 1), "+7.9 cents");
+        assert_eq!(signed_cents(-7.85, 0), "-8 cents");
+        assert_eq!(signed_cents(0.0, DEFAULT_CENTS_PRECISION), "+0.0 cents");
+    }
+}
@@ -0,0 +1,210 @@
+//! Translated coaching text.
+
+use serde::{Deserialize, Serialize};
+
+use crate::ui::components::instructions::TuningStep;
+
+/// Language for coaching instructions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize, clap::ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum Language {
+    /// English.
+    #[default]
+    English,
+    /// Spanish.
+    Spanish,
+}
+
+/// Title and instruction for one tuning step.
+#[derive(Debug, Clone, Copy)]
+pub struct StepText {
+    /// Short step title.
+    pub title: &'static str,
+    /// Full instruction.
+    pub instruction: &'static str,
+}
+
+/// Text around the step instructions.
+#[derive(Debug, Clone, Copy)]
+pub struct Phrases {
+    /// "Step" in "Step 2 of 4".
+    pub step: &'static str,
+    /// "of" in "Step 2 of 4".
+    pub of: &'static str,
+    /// Instruction for a single-string note.
+    pub simple: &'static str,
+    /// Hint when the note is flat.
+    pub tighten: &'static str,
+    /// Hint when the note is sharp.
+    pub loosen: &'static str,
+    /// Prompt to confirm the step.
+    pub press_space: &'static str,
+}
+
+/// Step text keyed by step and language.
+const STEP_TEXT: &[(TuningStep, Language, StepText)] = &[
+    (
+        TuningStep::MuteBichord,
+        Language::English,
+        StepText {
+            title: "Mute right string",
+            instruction: "Use a felt wedge or rubber mute to mute the right string. Only the left string should sound.",
+        },
+    ),
+    (
+        TuningStep::TuneBichord,
+        Language::English,
+        StepText {
+            title: "Tune left string",
+            instruction: "Tune the left string to pitch. Then remove the mute and tune the right string to match.",
+        },
+    ),
+    (
+        TuningStep::MuteOuter,
+        Language::English,
+        StepText {
+            title: "Mute outer strings",
+            instruction: "Use felt strip or rubber mutes to mute the outer strings. Only the center string should sound.",
+        },
+    ),
+    (
+        TuningStep::TuneCenter,
+        Language::English,
+        StepText {
+            title: "Tune center string",
+            instruction: "Tune the center string to the target pitch using the meter.",
+        },
+    ),
+    (
+        TuningStep::TuneLeft,
+        Language::English,
+        StepText {
+            title: "Tune left string",
+            instruction: "Unmute the left string. Tune it to match the center string until you hear no beats.",
+        },
+    ),
+    (
+        TuningStep::TuneRight,
+        Language::English,
+        StepText {
+            title: "Tune right string",
+            instruction: "Unmute the right string. Tune it to match the center string until you hear no beats.",
+        },
+    ),
+    (
+        TuningStep::MuteBichord,
+        Language::Spanish,
+        StepText {
+            title: "Silenciar cuerda derecha",
+            instruction: "Use una cuña de fieltro o un apagador de goma para silenciar la cuerda derecha. Solo debe sonar la cuerda izquierda.",
+        },
+    ),
+    (
+        TuningStep::TuneBichord,
+        Language::Spanish,
+        StepText {
+            title: "Afinar cuerda izquierda",
+            instruction: "Afine la cuerda izquierda. Luego quite el apagador y afine la cuerda derecha para que coincida.",
+        },
+    ),
+    (
+        TuningStep::MuteOuter,
+        Language::Spanish,
+        StepText {
+            title: "Silenciar cuerdas exteriores",
+            instruction: "Use una tira de fieltro o apagadores de goma para silenciar las cuerdas exteriores. Solo debe sonar la cuerda central.",
+        },
+    ),
+    (
+        TuningStep::TuneCenter,
+        Language::Spanish,
+        StepText {
+            title: "Afinar cuerda central",
+            instruction: "Afine la cuerda central a la altura objetivo usando el medidor.",
+        },
+    ),
+    (
+        TuningStep::TuneLeft,
+        Language::Spanish,
+        StepText {
+            title: "Afinar cuerda izquierda",
+            instruction: "Libere la cuerda izquierda. Afínela con la cuerda central hasta que no oiga batimientos.",
+        },
+    ),
+    (
+        TuningStep::TuneRight,
+        Language::Spanish,
+        StepText {
+            title: "Afinar cuerda derecha",
+            instruction: "Libere la cuerda derecha. Afínela con la cuerda central hasta que no oiga batimientos.",
+        },
+    ),
+];
+
+const ENGLISH: Phrases = Phrases {
+    step: "Step",
+    of: "of",
+    simple: "Tune this string to the target pitch using the meter.",
+    tighten: "Turn tuning pin CLOCKWISE (tighten) slightly",
+    loosen: "Turn tuning pin COUNTER-CLOCKWISE (loosen) slightly",
+    press_space: "Press SPACE to continue",
+};
+
+const SPANISH: Phrases = Phrases {
+    step: "Paso",
+    of: "de",
+    simple: "Afine esta cuerda a la altura objetivo usando el medidor.",
+    tighten: "Gire la clavija en sentido HORARIO (apretar) ligeramente",
+    loosen: "Gire la clavija en sentido ANTIHORARIO (aflojar) ligeramente",
+    press_space: "Pulse ESPACIO para continuar",
+};
+
+impl Language {
+    /// Get the title and instruction for a step.
+    pub fn step_text(self, step: TuningStep) -> StepText {
+        STEP_TEXT
+            .iter()
+            .find(|(s, lang, _)| *s == step && *lang == self)
+            .map(|(_, _, text)| *text)
+            .expect("every step has text in every language")
+    }
+
+    /// Get the surrounding instruction phrases.
+    pub fn phrases(self) -> &'static Phrases {
+        match self {
+            Language::English => &ENGLISH,
+            Language::Spanish => &SPANISH,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const STEPS: [TuningStep; 6] = [
+        TuningStep::MuteBichord,
+        TuningStep::TuneBichord,
+        TuningStep::MuteOuter,
+        TuningStep::TuneCenter,
+        TuningStep::TuneLeft,
+        TuningStep::TuneRight,
+    ];
+
+    #[test]
+    fn test_every_step_translated() {
+        for step in STEPS {
+            for lang in [Language::English, Language::Spanish] {
+                assert!(!lang.step_text(step).title.is_empty());
+            }
+        }
+    }
+
+    #[test]
+    fn test_languages_differ() {
+        let english = Language::English.step_text(TuningStep::TuneCenter);
+        let spanish = Language::Spanish.step_text(TuningStep::TuneCenter);
+        assert_ne!(english.title, spanish.title);
+        assert_ne!(english.instruction, spanish.instruction);
+    }
+}
@@ -1,16 +1,25 @@
 //! Terminal UI screens and components.
 
 use std::io::{self, Stdout};
+use std::sync::OnceLock;
 
 use crossterm::{
-    event::{self, Event, KeyCode, KeyEventKind},
+    event::{
+        self, Event, KeyCode, KeyEventKind, KeyboardEnhancementFlags, PopKeyboardEnhancementFlags,
+        PushKeyboardEnhancementFlags,
+    },
     execute,
-    terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
+    terminal::{
+        disable_raw_mode, enable_raw_mode, supports_keyboard_enhancement, EnterAlternateScreen,
+        LeaveAlternateScreen,
+    },
 };
 use ratatui::{backend::CrosstermBackend, Terminal};
 
 pub mod app;
 pub mod components;
+pub mod format;
+pub mod language;
 pub mod screens;
 pub mod theme;
 
@@ -24,12 +33,21 @@ pub fn init() -> io::Result<Tui> {
     enable_raw_mode()?;
     let mut stdout = io::stdout();
     execute!(stdout, EnterAlternateScreen)?;
+    if reports_key_release() {
+        execute!(
+            stdout,
+            PushKeyboardEnhancementFlags(KeyboardEnhancementFlags::REPORT_EVENT_TYPES)
+        )?;
+    }
     let backend = CrosstermBackend::new(stdout);
     Terminal::new(backend)
 }
 
 /// Restore the terminal to normal mode.
 pub fn restore() -> io::Result<()> {
+    if reports_key_release() {
+        execute!(io::stdout(), PopKeyboardEnhancementFlags)?;
+    }
     disable_raw_mode()?;
     execute!(io::stdout(), LeaveAlternateScreen)?;
     Ok(())
@@ -52,6 +70,23 @@ pub fn poll_event(timeout: std::time::Duration) -> io::Result<Option<Event>> {
     }
 }
 
+/// Check if the terminal can report key releases. The terminal is only
+/// queried once.
+pub fn reports_key_release() -> bool {
+    static SUPPORTED: OnceLock<bool> = OnceLock::new();
+    *SUPPORTED.get_or_init(|| supports_keyboard_enhancement().unwrap_or(false))
+}
+
+/// Check if a key event is a release.
+pub fn is_key_release(event: &Event) -> Option<KeyCode> {
+    if let Event::Key(key) = event {
+        if key.kind == KeyEventKind::Release {
+            return Some(key.code);
+        }
+    }
+    None
+}
+
 /// Check if a key event is a press (not release).
 pub fn is_key_press(event: &Event) -> Option<KeyCode> {
     if let Event::Key(key) = event {
@@ -6,7 +6,10 @@ use ratatui::{
     widgets::{Block, Borders, Paragraph, Widget},
 };
 
-use crate::tuning::session::CompletedNote;
+use std::collections::HashMap;
+
+use crate::tuning::notes::NOTE_COUNT;
+use crate::tuning::session::{CompletedNote, NoteStatus};
 use crate::ui::theme::{Shortcuts, Theme};
 
 /// Session complete screen with summary.
@@ -21,6 +24,14 @@ pub struct CompleteScreen {
     notes_warning: usize,
     /// Notes out of tune (>±15 cents).
     notes_out_of_tune: usize,
+    /// Notes skipped without tuning.
+    notes_skipped: usize,
+    /// Notes judged acceptable and left as-is.
+    notes_left: usize,
+    /// Notes never reached (session finished early).
+    notes_untuned: usize,
+    /// Average absolute starting deviation of tuned notes, if profiled.
+    avg_starting_deviation: Option<f32>,
     /// Total tuning duration.
     duration_secs: u64,
 }
@@ -28,27 +39,36 @@ pub struct CompleteScreen {
 impl CompleteScreen {
     /// Create a new complete screen.
     pub fn new(completed_notes: Vec<CompletedNote>) -> Self {
-        let avg_deviation = if completed_notes.is_empty() {
+        // Skipped and left-as-is notes were never tuned, so only tuned notes
+        // count towards the deviation stats.
+        let tuned: Vec<f32> = completed_notes
+            .iter()
+            .filter(|n| n.status == NoteStatus::Tuned)
+            .map(|n| n.final_cents.abs())
+            .collect();
+
+        let avg_deviation = if tuned.is_empty() {
             0.0
         } else {
-            let sum: f32 = completed_notes.iter().map(|n| n.final_cents.abs()).sum();
-            sum / completed_notes.len() as f32
+            tuned.iter().sum::<f32>() / tuned.len() as f32
         };
 
-        let notes_in_tune = completed_notes
-            .iter()
-            .filter(|n| n.final_cents.abs() <= 5.0)
-            .count();
+        let notes_in_tune = tuned.iter().filter(|&&c| c <= 5.0).count();
+        let notes_warning = tuned.iter().filter(|&&c| c > 5.0 && c <= 15.0).count();
+        let notes_out_of_tune = tuned.iter().filter(|&&c| c > 15.0).count();
 
-        let notes_warning = completed_notes
-            .iter()
-            .filter(|n| n.final_cents.abs() > 5.0 && n.final_cents.abs() <= 15.0)
-            .count();
+        let count_status = |status| {
+            completed_notes
+                .iter()
+                .filter(|n| n.status == status)
+                .count()
+        };
+        let notes_skipped = count_status(NoteStatus::Skipped);
+        let notes_left = count_status(NoteStatus::LeftAsIs);
 
-        let notes_out_of_tune = completed_notes
-            .iter()
-            .filter(|n| n.final_cents.abs() > 15.0)
-            .count();
+        let recorded: std::collections::HashSet<&str> =
+            completed_notes.iter().map(|n| n.note.as_str()).collect();
+        let notes_untuned = NOTE_COUNT.saturating_sub(recorded.len());
 
         Self {
             completed_notes,
@@ -56,6 +76,10 @@ impl CompleteScreen {
             notes_in_tune,
             notes_warning,
             notes_out_of_tune,
+            notes_skipped,
+            notes_left,
+            notes_untuned,
+            avg_starting_deviation: None,
             duration_secs: 0,
         }
     }
@@ -66,15 +90,78 @@ impl CompleteScreen {
         self
     }
 
+    /// Set the deviations notes started at, by note name.
+    pub fn with_starting_cents(mut self, starting_cents: &HashMap<String, f32>) -> Self {
+        let starts: Vec<f32> = self
+            .completed_notes
+            .iter()
+            .filter_map(|n| starting_cents.get(&n.note))
+            .map(|c| c.abs())
+            .collect();
+        self.avg_starting_deviation = if starts.is_empty() {
+            None
+        } else {
+            Some(starts.iter().sum::<f32>() / starts.len() as f32)
+        };
+        self
+    }
+
+    /// Get the average starting deviation, if the session was profiled.
+    pub fn avg_starting_deviation(&self) -> Option<f32> {
+        self.avg_starting_deviation
+    }
+
     /// Get the number of completed notes.
     pub fn note_count(&self) -> usize {
         self.completed_notes.len()
     }
 
+    /// Get the completed notes.
+    pub fn completed_notes(&self) -> &[CompletedNote] {
+        &self.completed_notes
+    }
+
+    /// Get the number of notes tuned to target.
+    pub fn tuned_count(&self) -> usize {
+        self.notes_in_tune + self.notes_warning + self.notes_out_of_tune
+    }
+
+    /// Get the number of skipped notes.
+    pub fn skipped_count(&self) -> usize {
+        self.notes_skipped
+    }
+
+    /// Get the number of notes left as-is.
+    pub fn left_as_is_count(&self) -> usize {
+        self.notes_left
+    }
+
+    /// Get the number of notes left untuned.
+    pub fn untuned_count(&self) -> usize {
+        self.notes_untuned
+    }
+
+    /// Remarks left on notes, as (note, remark), latest per note.
+    pub fn comments(&self) -> Vec<(&str, &str)> {
+        let mut comments: Vec<(&str, &str)> = Vec::new();
+        for note in &self.completed_notes {
+            comments.retain(|(name, _)| *name != note.note);
+            if let Some(comment) = &note.comment {
+                comments.push((&note.note, comment));
+            }
+        }
+        comments
+    }
+
     /// Get average deviation.
     pub fn avg_deviation(&self) -> f32 {
         self.avg_deviation
     }
+
+    /// Whether no note was tuned, e.g. the session was aborted.
+    pub fn nothing_tuned(&self) -> bool {
+        self.tuned_count() == 0
+    }
 }
 
 impl Widget for &CompleteScreen {
@@ -107,7 +194,9 @@ impl Widget for &CompleteScreen {
         .split(inner);
 
         // Congratulations message
-        let quality = if self.avg_deviation <= 3.0 {
+        let quality = if self.nothing_tuned() {
+            ("Nothing tuned", Theme::muted())
+        } else if self.avg_deviation <= 3.0 {
             ("Excellent tuning!", Theme::in_tune())
         } else if self.avg_deviation <= 8.0 {
             ("Good tuning!", Theme::in_tune())
@@ -124,20 +213,34 @@ impl Widget for &CompleteScreen {
 
         // Summary stats
         let stats_area = chunks[2];
-        let stats = [
-            format!("Notes tuned: {}", self.completed_notes.len()),
-            format!("Average deviation: {:.1} cents", self.avg_deviation),
+        let mut stats = vec![
+            format!("Notes tuned: {}", self.tuned_count()),
             format!(
-                "Duration: {}:{:02}",
-                self.duration_secs / 60,
-                self.duration_secs % 60
+                "Left as-is: {}  Skipped: {}",
+                self.notes_left, self.notes_skipped
             ),
+            format!("Notes untuned: {}", self.notes_untuned),
         ];
+        if !self.nothing_tuned() {
+            stats.push(format!(
+                "Average deviation: {:.1} cents",
+                self.avg_deviation
+            ));
+        }
+        if let Some(before) = self.avg_starting_deviation {
+            stats.push(format!("Average before tuning: {:.1} cents", before));
+        }
+        stats.push(format!(
+            "Duration: {}:{:02}",
+            self.duration_secs / 60,
+            self.duration_secs % 60
+        ));
 
         for (i, stat) in stats.iter().enumerate() {
             let y = stats_area.y + i as u16;
             if y < stats_area.y + stats_area.height {
-                let x = stats_area.x + stats_area.width / 2 - stat.len() as u16 / 2;
+                let x = stats_area.x
+                    + (stats_area.width / 2).saturating_sub(stat.chars().count() as u16 / 2);
                 buf.set_string(x, y, stat, Theme::muted());
             }
         }
@@ -180,13 +283,165 @@ impl Widget for &CompleteScreen {
                     Theme::out_of_tune(),
                 );
             }
+            let mut row = 3;
+            if breakdown_inner.height >= 4 && self.notes_untuned > 0 {
+                let untuned_text = format!("○ Untuned: {}", self.notes_untuned);
+                buf.set_string(
+                    breakdown_inner.x + 2,
+                    breakdown_inner.y + 3,
+                    &untuned_text,
+                    Theme::muted(),
+                );
+                row += 1;
+            }
+
+            // Remarks, as many as fit
+            for (note, comment) in self.comments() {
+                if row >= breakdown_inner.height {
+                    break;
+                }
+                buf.set_stringn(
+                    breakdown_inner.x + 2,
+                    breakdown_inner.y + row,
+                    format!("✎ {}: {}", note, comment),
+                    breakdown_inner.width.saturating_sub(4) as usize,
+                    Theme::accent(),
+                );
+                row += 1;
+            }
         }
 
         // Help text
-        let help_text = format!("{} New session  {} Quit", Shortcuts::ENTER, Shortcuts::QUIT);
+        let help_text = format!(
+            "{} Retune next out of tolerance  {} New session  {} Quit",
+            Shortcuts::NEXT,
+            Shortcuts::ENTER,
+            Shortcuts::QUIT
+        );
         let help = Paragraph::new(help_text)
             .style(Theme::muted())
             .alignment(Alignment::Center);
         help.render(chunks[5], buf);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_untuned_count() {
+        let notes = vec![
+            CompletedNote::new("F3", 1.0),
+            CompletedNote::new("F#3", 2.0),
+        ];
+        let screen = CompleteScreen::new(notes);
+        assert_eq!(screen.untuned_count(), 86);
+    }
+
+    #[test]
+    fn test_avg_starting_deviation() {
+        let notes = vec![
+            CompletedNote::new("F3", 1.0),
+            CompletedNote::new("F#3", 2.0),
+        ];
+        let starts: HashMap<String, f32> = [
+            ("F3".to_string(), -10.0),
+            ("F#3".to_string(), 20.0),
+            ("G3".to_string(), 50.0), // not tuned, ignored
+        ]
+        .into_iter()
+        .collect();
+
+        let screen = CompleteScreen::new(notes.clone());
+        assert_eq!(screen.avg_starting_deviation(), None);
+
+        let screen = CompleteScreen::new(notes).with_starting_cents(&starts);
+        assert_eq!(screen.avg_starting_deviation(), Some(15.0));
+    }
+
+    #[test]
+    fn test_status_counts() {
+        let notes = vec![
+            CompletedNote::new("F3", 1.0),
+            CompletedNote::new("F#3", 20.0),
+            CompletedNote::new("G3", 0.0).with_status(NoteStatus::Skipped),
+            CompletedNote::new("G#3", 0.0).with_status(NoteStatus::Skipped),
+            CompletedNote::new("A3", 40.0).with_status(NoteStatus::LeftAsIs),
+        ];
+        let screen = CompleteScreen::new(notes);
+        assert_eq!(screen.tuned_count(), 2);
+        assert_eq!(screen.skipped_count(), 2);
+        assert_eq!(screen.left_as_is_count(), 1);
+        assert_eq!(screen.note_count(), 5);
+        // Only tuned notes count towards the average
+        assert_eq!(screen.avg_deviation(), 10.5);
+    }
+
+    fn render_text(screen: &CompleteScreen) -> String {
+        let area = Rect::new(0, 0, 80, 24);
+        let mut buf = Buffer::empty(area);
+        screen.render(area, &mut buf);
+        (0..area.height)
+            .map(|y| {
+                (0..area.width)
+                    .map(|x| buf[(x, y)].symbol())
+                    .collect::<String>()
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    #[test]
+    fn test_empty_session_renders_nothing_tuned() {
+        let screen = CompleteScreen::new(Vec::new()).with_starting_cents(&HashMap::new());
+        assert!(screen.nothing_tuned());
+        assert_eq!(screen.avg_deviation(), 0.0);
+        assert_eq!(screen.avg_starting_deviation(), None);
+        assert_eq!(screen.untuned_count(), 88);
+
+        let text = render_text(&screen);
+        assert!(text.contains("Nothing tuned"));
+        assert!(text.contains("Notes tuned: 0"));
+        assert!(!text.contains("Excellent"));
+        assert!(!text.contains("Average deviation"));
+    }
+
+    #[test]
+    fn test_only_skipped_notes_count_as_nothing_tuned() {
+        let notes = vec![CompletedNote::new("A4", 0.0).with_status(NoteStatus::Skipped)];
+        let screen = CompleteScreen::new(notes);
+        assert!(screen.nothing_tuned());
+        assert!(render_text(&screen).contains("Nothing tuned"));
+    }
+
+    #[test]
+    fn test_comments_listed_and_rendered() {
+        let notes = vec![
+            CompletedNote::new("F3", 1.0).with_comment("sticky key"),
+            CompletedNote::new("F#3", 2.0),
+            CompletedNote::new("F5", 8.0).with_comment("old remark"),
+            CompletedNote::new("F5", 1.0).with_comment("false beat"),
+        ];
+        let screen = CompleteScreen::new(notes);
+        assert_eq!(
+            screen.comments(),
+            vec![("F3", "sticky key"), ("F5", "false beat")]
+        );
+
+        let text = render_text(&screen);
+        assert!(text.contains("✎ F3: sticky key"));
+        assert!(text.contains("✎ F5: false beat"));
+        assert!(!text.contains("old remark"));
+    }
+
+    #[test]
+    fn test_untuned_count_full_session() {
+        let notes = crate::tuning::notes::NOTES
+            .iter()
+            .map(|n| CompletedNote::new(n.display_name(), 0.0))
+            .collect();
+        let screen = CompleteScreen::new(notes);
+        assert_eq!(screen.untuned_count(), 0);
+    }
+}
@@ -0,0 +1,163 @@
+//! Temperament check screen, shown once the temperament octave is set.
+
+use ratatui::{
+    buffer::Buffer,
+    layout::{Alignment, Constraint, Layout, Rect},
+    widgets::{Block, Borders, Paragraph, Widget},
+};
+
+use crate::tuning::intervals::{temperament_checks, IntervalCheck};
+use crate::tuning::temperament::Temperament;
+use crate::ui::theme::{Shortcuts, Theme};
+
+/// Lists the temperament octave's interval checks and their expected beats.
+pub struct IntervalCheckScreen {
+    /// Checks in listening order.
+    checks: Vec<IntervalCheck>,
+    /// Index of the highlighted check.
+    selected: usize,
+}
+
+impl IntervalCheckScreen {
+    /// Create a check screen for the temperament octave starting at `low_midi`.
+    pub fn new(temperament: &Temperament, low_midi: u8) -> Self {
+        Self {
+            checks: temperament_checks(temperament, low_midi),
+            selected: 0,
+        }
+    }
+
+    /// Get all checks.
+    pub fn checks(&self) -> &[IntervalCheck] {
+        &self.checks
+    }
+
+    /// Get the highlighted check.
+    pub fn selected(&self) -> Option<&IntervalCheck> {
+        self.checks.get(self.selected)
+    }
+
+    /// Highlight the next check.
+    pub fn next(&mut self) {
+        if self.selected + 1 < self.checks.len() {
+            self.selected += 1;
+        }
+    }
+
+    /// Highlight the previous check.
+    pub fn previous(&mut self) {
+        self.selected = self.selected.saturating_sub(1);
+    }
+}
+
+impl Widget for &IntervalCheckScreen {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(Theme::border())
+            .title(" Temperament Check ")
+            .title_style(Theme::title());
+
+        let inner = block.inner(area);
+        block.render(area, buf);
+
+        if inner.height < 8 || inner.width < 40 {
+            let msg = "Terminal too small";
+            buf.set_string(inner.x, inner.y, msg, Theme::warning());
+            return;
+        }
+
+        let chunks = Layout::vertical([
+            Constraint::Length(2), // Instructions
+            Constraint::Length(1), // Spacer
+            Constraint::Min(3),    // Checks
+            Constraint::Length(2), // Help text
+        ])
+        .split(inner);
+
+        Paragraph::new("Play each interval and compare its beats to the expected rate")
+            .style(Theme::title())
+            .alignment(Alignment::Center)
+            .render(chunks[0], buf);
+
+        // Scroll so the selected check stays visible
+        let list = chunks[2];
+        let visible = list.height as usize;
+        let first = (self.selected + 1).saturating_sub(visible);
+        let x = list.x + list.width.saturating_sub(30) / 2;
+
+        for (i, check) in self.checks.iter().enumerate().skip(first).take(visible) {
+            let style = if i == self.selected {
+                Theme::selected()
+            } else {
+                Theme::muted()
+            };
+            let line = format!(
+                "{:<14} {:>6.2} beats/s",
+                check.label(),
+                check.beats_per_second
+            );
+            buf.set_string(x, list.y + (i - first) as u16, line, style);
+        }
+
+        let help_text = format!(
+            "{} Select  {} Continue tuning  {} Skip  {} Quit",
+            Shortcuts::ARROWS,
+            Shortcuts::ENTER,
+            Shortcuts::SKIP,
+            Shortcuts::QUIT
+        );
+        Paragraph::new(help_text)
+            .style(Theme::muted())
+            .alignment(Alignment::Center)
+            .render(chunks[3], buf);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tuning::intervals::TEMPERAMENT_LOW_MIDI;
+
+    fn render_text(screen: &IntervalCheckScreen) -> String {
+        let area = Rect::new(0, 0, 80, 12);
+        let mut buf = Buffer::empty(area);
+        screen.render(area, &mut buf);
+        (0..area.height)
+            .map(|y| {
+                (0..area.width)
+                    .map(|x| buf[(x, y)].symbol())
+                    .collect::<String>()
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    #[test]
+    fn test_selection_stays_in_range() {
+        let mut screen = IntervalCheckScreen::new(&Temperament::new(), TEMPERAMENT_LOW_MIDI);
+        let count = screen.checks().len();
+        assert!(count > 0);
+
+        screen.previous();
+        assert_eq!(screen.selected(), screen.checks().first());
+        for _ in 0..count + 3 {
+            screen.next();
+        }
+        assert_eq!(screen.selected(), screen.checks().last());
+    }
+
+    #[test]
+    fn test_render_scrolls_to_selected_check() {
+        let mut screen = IntervalCheckScreen::new(&Temperament::new(), TEMPERAMENT_LOW_MIDI);
+        assert!(render_text(&screen).contains("M3 F3-A3"));
+
+        for _ in 1..screen.checks().len() {
+            screen.next();
+        }
+        let last = screen.selected().expect("Should have a check").label();
+        let text = render_text(&screen);
+        assert!(text.contains(&last), "{last} not shown");
+        assert!(!text.contains("M3 F3-A3"));
+    }
+}
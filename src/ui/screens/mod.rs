@@ -2,12 +2,16 @@
 
 pub mod calibration;
 pub mod complete;
+pub mod interval_check;
 pub mod mode_select;
 pub mod profiling;
+pub mod training;
 pub mod tuning;
 
 pub use calibration::CalibrationScreen;
 pub use complete::CompleteScreen;
+pub use interval_check::IntervalCheckScreen;
 pub use mode_select::ModeSelectScreen;
 pub use profiling::ProfilingScreen;
+pub use training::TrainingScreen;
 pub use tuning::TuningScreen;
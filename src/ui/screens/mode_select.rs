@@ -7,6 +7,7 @@ use ratatui::{
     widgets::{Block, Borders, Paragraph, Widget},
 };
 
+use crate::tuning::temperament::A4Preset;
 use crate::ui::theme::{Shortcuts, Theme};
 
 /// Selected tuning mode.
@@ -16,6 +17,8 @@ pub enum SelectedMode {
     QuickTune,
     ConcertPitch,
     Profile,
+    Training,
+    RelativeTune,
 }
 
 impl SelectedMode {
@@ -23,8 +26,10 @@ impl SelectedMode {
     pub fn title(&self) -> &'static str {
         match self {
             Self::QuickTune => "Quick Tune",
-            Self::ConcertPitch => "Concert Pitch (A4 = 440 Hz)",
+            Self::ConcertPitch => "Concert Pitch",
             Self::Profile => "Profile Piano",
+            Self::Training => "Ear Training",
+            Self::RelativeTune => "Relative Tune",
         }
     }
 
@@ -32,8 +37,10 @@ impl SelectedMode {
     pub fn description(&self) -> &'static str {
         match self {
             Self::QuickTune => "Calibrate to the piano's current pitch center, then tune all strings relative to that. Best for regular maintenance.",
-            Self::ConcertPitch => "Tune all strings to standard concert pitch (A4 = 440 Hz). Use for pianos that are already close to pitch.",
+            Self::ConcertPitch => "Tune all strings to concert pitch at the chosen A4 (←/→ to change). Use for pianos that are already close to pitch.",
             Self::Profile => "Play all 88 keys (A0→C8) to measure deviations, then tune worst notes first while preserving the temperament octave.",
+            Self::Training => "Practice matching a detuned string to the reference by ear, then see how many cents off you were.",
+            Self::RelativeTune => "Skip calibration: tune each note by interval from notes already tuned, keeping the piano consistent wherever its pitch sits.",
         }
     }
 }
@@ -41,6 +48,8 @@ impl SelectedMode {
 /// Mode selection screen.
 pub struct ModeSelectScreen {
     selected: SelectedMode,
+    /// A4 reference for concert pitch.
+    a4: f32,
 }
 
 impl ModeSelectScreen {
@@ -48,6 +57,66 @@ impl ModeSelectScreen {
     pub fn new() -> Self {
         Self {
             selected: SelectedMode::default(),
+            a4: A4Preset::default().frequency(),
+        }
+    }
+
+    /// Start from the given A4 reference.
+    pub fn with_a4(mut self, a4: f32) -> Self {
+        self.a4 = a4;
+        self
+    }
+
+    /// Change the A4 reference, keeping the selected mode.
+    pub fn set_a4(&mut self, a4: f32) {
+        self.a4 = a4;
+    }
+
+    /// Get the A4 reference for concert pitch.
+    pub fn a4(&self) -> f32 {
+        self.a4
+    }
+
+    /// Switch to the next A4 preset, wrapping around.
+    ///
+    /// A custom A4 moves to the first preset.
+    pub fn next_pitch(&mut self) {
+        let next = match A4Preset::from_frequency(self.a4) {
+            Some(preset) => {
+                let idx = A4Preset::ALL.iter().position(|p| *p == preset).unwrap_or(0);
+                A4Preset::ALL[(idx + 1) % A4Preset::ALL.len()]
+            }
+            None => A4Preset::ALL[0],
+        };
+        self.a4 = next.frequency();
+    }
+
+    /// Switch to the previous A4 preset, wrapping around.
+    ///
+    /// A custom A4 moves to the last preset.
+    pub fn prev_pitch(&mut self) {
+        let count = A4Preset::ALL.len();
+        let prev = match A4Preset::from_frequency(self.a4) {
+            Some(preset) => {
+                let idx = A4Preset::ALL.iter().position(|p| *p == preset).unwrap_or(0);
+                A4Preset::ALL[(idx + count - 1) % count]
+            }
+            None => A4Preset::ALL[count - 1],
+        };
+        self.a4 = prev.frequency();
+    }
+
+    /// Title for a mode, including the chosen pitch for concert pitch.
+    fn mode_title(&self, mode: SelectedMode) -> String {
+        match mode {
+            SelectedMode::ConcertPitch => {
+                let pitch = match A4Preset::from_frequency(self.a4) {
+                    Some(preset) => format!("{} Hz, {}", self.a4, preset.label()),
+                    None => format!("{} Hz", self.a4),
+                };
+                format!("{} (A4 = {})  ◀ ▶", mode.title(), pitch)
+            }
+            _ => mode.title().to_string(),
         }
     }
 
@@ -61,16 +130,20 @@ impl ModeSelectScreen {
         self.selected = match self.selected {
             SelectedMode::QuickTune => SelectedMode::ConcertPitch,
             SelectedMode::ConcertPitch => SelectedMode::Profile,
-            SelectedMode::Profile => SelectedMode::QuickTune,
+            SelectedMode::Profile => SelectedMode::Training,
+            SelectedMode::Training => SelectedMode::RelativeTune,
+            SelectedMode::RelativeTune => SelectedMode::QuickTune,
         };
     }
 
     /// Select the previous mode.
     pub fn prev(&mut self) {
         self.selected = match self.selected {
-            SelectedMode::QuickTune => SelectedMode::Profile,
+            SelectedMode::QuickTune => SelectedMode::RelativeTune,
             SelectedMode::ConcertPitch => SelectedMode::QuickTune,
             SelectedMode::Profile => SelectedMode::ConcertPitch,
+            SelectedMode::Training => SelectedMode::Profile,
+            SelectedMode::RelativeTune => SelectedMode::Training,
         };
     }
 }
@@ -119,13 +192,20 @@ impl Widget for &ModeSelectScreen {
             SelectedMode::QuickTune,
             SelectedMode::ConcertPitch,
             SelectedMode::Profile,
+            SelectedMode::Training,
+            SelectedMode::RelativeTune,
         ];
         let option_height = 4;
         let options_area = chunks[2];
 
-        for (i, mode) in modes.iter().enumerate() {
+        // Scroll so the selected mode stays visible on short terminals
+        let visible = ((options_area.height + 1) / (option_height + 1)).max(1) as usize;
+        let selected_idx = modes.iter().position(|m| *m == self.selected).unwrap_or(0);
+        let first = (selected_idx + 1).saturating_sub(visible);
+
+        for (i, mode) in modes.iter().enumerate().skip(first) {
             let is_selected = *mode == self.selected;
-            let y_offset = i as u16 * (option_height + 1);
+            let y_offset = (i - first) as u16 * (option_height + 1);
 
             if y_offset + option_height > options_area.height {
                 break;
@@ -138,13 +218,20 @@ impl Widget for &ModeSelectScreen {
                 height: option_height,
             };
 
-            render_mode_option(*mode, is_selected, option_area, buf);
+            render_mode_option(
+                *mode,
+                &self.mode_title(*mode),
+                is_selected,
+                option_area,
+                buf,
+            );
         }
 
         // Help text at bottom
         let help_text = format!(
-            "{} Navigate  {} Select  {} Quit",
+            "{} Navigate  {} Concert pitch  {} Select  {} Quit",
             Shortcuts::ARROWS,
+            Shortcuts::LEFT_RIGHT,
             Shortcuts::ENTER,
             Shortcuts::QUIT
         );
@@ -155,7 +242,13 @@ impl Widget for &ModeSelectScreen {
     }
 }
 
-fn render_mode_option(mode: SelectedMode, is_selected: bool, area: Rect, buf: &mut Buffer) {
+fn render_mode_option(
+    mode: SelectedMode,
+    title: &str,
+    is_selected: bool,
+    area: Rect,
+    buf: &mut Buffer,
+) {
     let (border_style, title_style) = if is_selected {
         (Theme::selected(), Theme::selected())
     } else {
@@ -176,7 +269,7 @@ fn render_mode_option(mode: SelectedMode, is_selected: bool, area: Rect, buf: &m
     }
 
     // Title line
-    let title_line = format!("{}{}", prefix, mode.title());
+    let title_line = format!("{}{}", prefix, title);
     buf.set_string(
         inner.x,
         inner.y,
@@ -196,3 +289,44 @@ fn render_mode_option(mode: SelectedMode, is_selected: bool, area: Rect, buf: &m
         buf.set_string(inner.x + 2, inner.y + 1, &truncated, Theme::muted());
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pitch_cycles_through_presets() {
+        let mut screen = ModeSelectScreen::new();
+        assert_eq!(screen.a4(), 440.0);
+
+        let mut seen = vec![screen.a4()];
+        for _ in 1..A4Preset::ALL.len() {
+            screen.next_pitch();
+            seen.push(screen.a4());
+        }
+        assert_eq!(seen, [440.0, 442.0, 443.0, 415.3, 432.0]);
+
+        screen.next_pitch();
+        assert_eq!(screen.a4(), 440.0, "wraps around");
+        screen.prev_pitch();
+        assert_eq!(screen.a4(), 432.0);
+    }
+
+    #[test]
+    fn test_custom_pitch_shown_and_left_for_preset() {
+        let mut screen = ModeSelectScreen::new().with_a4(441.0);
+        assert_eq!(
+            screen.mode_title(SelectedMode::ConcertPitch),
+            "Concert Pitch (A4 = 441 Hz)  ◀ ▶"
+        );
+
+        screen.next_pitch();
+        assert_eq!(screen.a4(), 440.0);
+        screen.prev_pitch();
+        screen.prev_pitch();
+        assert_eq!(
+            screen.mode_title(SelectedMode::ConcertPitch),
+            "Concert Pitch (A4 = 415.3 Hz, Baroque)  ◀ ▶"
+        );
+    }
+}
@@ -1,6 +1,6 @@
 //! Piano profiling screen for measuring deviation of all 88 keys.
 
-use std::collections::HashMap;
+use std::collections::{BTreeSet, HashMap};
 
 use ratatui::{
     buffer::Buffer,
@@ -8,11 +8,42 @@ use ratatui::{
     widgets::{Block, Borders, Paragraph, Widget},
 };
 
-use crate::tuning::notes::{Note, NOTES};
+use crate::tuning::notes::{Note, NoteSpelling, NOTES, NOTE_COUNT};
 use crate::tuning::profile::PianoProfile;
-use crate::ui::components::{Meter, Piano, Progress};
+use crate::tuning::stretch::StretchCurve;
+use crate::tuning::targets::TargetTable;
+use crate::tuning::temperament::Temperament;
+use crate::ui::components::{KeyColors, Meter, Piano, Progress};
 use crate::ui::theme::{Shortcuts, Theme};
 
+/// Consecutive steady readings needed to auto-confirm (~0.5s).
+pub const AUTO_CONFIRM_READINGS: usize = 5;
+/// Furthest a steady reading may wander from the first one, in cents.
+const AUTO_CONFIRM_SPREAD_CENTS: f32 = 3.0;
+/// Readings further than this from the target are likely the wrong key.
+const AUTO_CONFIRM_MAX_CENTS: f32 = 50.0;
+
+/// What confirming a key with no reading does.
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    Default,
+    serde::Serialize,
+    serde::Deserialize,
+    clap::ValueEnum,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum EmptyConfirm {
+    /// Stay on the key and ask for a note to be played.
+    #[default]
+    Reject,
+    /// Move on, flagging the key as left unmeasured.
+    Unmeasured,
+}
+
 /// Profiling screen for measuring all 88 keys sequentially.
 pub struct ProfilingScreen {
     /// Current note index (0-87, chromatic order A0→C8).
@@ -25,6 +56,34 @@ pub struct ProfilingScreen {
     profile: PianoProfile,
     /// Whether to show the piano progress view.
     show_piano: bool,
+    /// Note name spelling for display.
+    spelling: NoteSpelling,
+    /// Whether the microphone buffer is still filling.
+    warming_up: bool,
+    /// Frame of the listening animation; `None` keeps it still.
+    animation_frame: Option<u64>,
+    /// Temperament the targets are computed from.
+    temperament: Temperament,
+    /// Targets built from the temperament and the profile's stretch.
+    targets: TargetTable,
+    /// Record and advance on a steady reading without pressing Space.
+    auto_confirm: bool,
+    /// Cents of the first reading in the current steady run.
+    steady_from: Option<f32>,
+    /// Readings in the current steady run.
+    steady_readings: usize,
+    /// Octave also detected on the last reading.
+    octave_warning: bool,
+    /// Detection confidence of the last reading.
+    confidence: f32,
+    /// What confirming with no reading does.
+    empty_confirm: EmptyConfirm,
+    /// How profiled keys are colored, and the tolerance they are judged by.
+    key_colors: (KeyColors, f32),
+    /// Keys confirmed with no reading, by index.
+    unmeasured: BTreeSet<usize>,
+    /// Message about the last confirm, until the next reading.
+    notice: Option<String>,
 }
 
 impl ProfilingScreen {
@@ -36,12 +95,78 @@ impl ProfilingScreen {
             current_cents: None,
             profile: PianoProfile::new(),
             show_piano: true,
+            spelling: NoteSpelling::default(),
+            warming_up: false,
+            animation_frame: None,
+            temperament: Temperament::new(),
+            targets: TargetTable::default(),
+            auto_confirm: false,
+            steady_from: None,
+            steady_readings: 0,
+            octave_warning: false,
+            confidence: 1.0,
+            empty_confirm: EmptyConfirm::default(),
+            key_colors: (KeyColors::default(), 5.0),
+            unmeasured: BTreeSet::new(),
+            notice: None,
         }
     }
 
-    /// Get the current note to profile.
-    pub fn current_note(&self) -> &'static Note {
-        &NOTES[self.current_note_idx]
+    /// Choose what confirming a key with no reading does.
+    pub fn with_empty_confirm(mut self, empty_confirm: EmptyConfirm) -> Self {
+        self.empty_confirm = empty_confirm;
+        self
+    }
+
+    /// Color profiled keys by `key_colors`, judged against `tolerance` cents.
+    pub fn with_key_colors(mut self, key_colors: KeyColors, tolerance: f32) -> Self {
+        self.key_colors = (key_colors, tolerance);
+        self
+    }
+
+    /// Record and advance automatically once a reading holds steady.
+    pub fn with_auto_confirm(mut self, auto_confirm: bool) -> Self {
+        self.auto_confirm = auto_confirm;
+        self
+    }
+
+    /// Use the given note name spelling for display.
+    pub fn with_spelling(mut self, spelling: NoteSpelling) -> Self {
+        self.spelling = spelling;
+        self
+    }
+
+    /// Compute targets from the given temperament.
+    pub fn with_temperament(mut self, temperament: Temperament) -> Self {
+        self.temperament = temperament;
+        self.rebuild_targets();
+        self
+    }
+
+    /// Apply a stretch curve to the targets, saved with the profile.
+    pub fn with_stretch(mut self, stretch: StretchCurve) -> Self {
+        self.profile.stretch = Some(stretch);
+        self.rebuild_targets();
+        self
+    }
+
+    fn rebuild_targets(&mut self) {
+        self.targets = TargetTable::build(&self.temperament, self.profile.stretch.as_ref(), 0.0);
+    }
+
+    /// Target frequency for a note, including any stretch.
+    pub fn target_for(&self, note: &Note) -> f32 {
+        self.targets.target(note.midi)
+    }
+
+    /// Target frequency for the current note.
+    pub fn target_freq(&self) -> Option<f32> {
+        self.current_note().map(|note| self.target_for(note))
+    }
+
+    /// Get the current note to profile, or `None` once past the last key.
+    pub fn current_note(&self) -> Option<&'static Note> {
+        NOTES.get(self.current_note_idx)
     }
 
     /// Get the current note index.
@@ -53,35 +178,120 @@ impl ProfilingScreen {
     pub fn update(&mut self, freq: f32, cents: f32) {
         self.current_freq = Some(freq);
         self.current_cents = Some(cents);
+        self.notice = None;
+
+        // Only readings near the target, without an octave, count as steady
+        let usable = cents.abs() <= AUTO_CONFIRM_MAX_CENTS && !self.octave_warning;
+        match self.steady_from {
+            Some(from) if usable && (cents - from).abs() <= AUTO_CONFIRM_SPREAD_CENTS => {
+                self.steady_readings += 1;
+            }
+            _ if usable => {
+                self.steady_from = Some(cents);
+                self.steady_readings = 1;
+            }
+            _ => self.reset_steady(),
+        }
     }
 
     /// Clear detected pitch (silence).
     pub fn clear(&mut self) {
         self.current_freq = None;
         self.current_cents = None;
+        self.reset_steady();
+    }
+
+    /// Set the detection confidence of the latest reading.
+    pub fn set_confidence(&mut self, confidence: f32) {
+        self.confidence = confidence;
+    }
+
+    /// Flag that the detected note's octave is sounding too.
+    pub fn set_octave_warning(&mut self, ambiguous: bool) {
+        self.octave_warning = ambiguous;
+        if ambiguous {
+            self.reset_steady();
+        }
+    }
+
+    /// Check if a steady reading should be recorded without a key press.
+    pub fn should_auto_confirm(&self) -> bool {
+        self.auto_confirm
+            && !self.warming_up
+            && self.current_cents.is_some()
+            && self.steady_readings >= AUTO_CONFIRM_READINGS
+    }
+
+    fn reset_steady(&mut self) {
+        self.steady_from = None;
+        self.steady_readings = 0;
+    }
+
+    /// Set whether the microphone buffer is still filling.
+    pub fn set_warming_up(&mut self, warming_up: bool) {
+        self.warming_up = warming_up;
+    }
+
+    /// Set the listening animation frame; `None` keeps it still.
+    pub fn set_animation_frame(&mut self, frame: Option<u64>) {
+        self.animation_frame = frame;
+    }
+
+    /// Check if the microphone buffer is still filling.
+    pub fn is_warming_up(&self) -> bool {
+        self.warming_up
     }
 
     /// Confirm the current note measurement.
     /// Returns true if profiling is now complete.
+    ///
+    /// With no reading, the key is either kept with a hint or passed over
+    /// and flagged as unmeasured, as chosen by `with_empty_confirm`.
     pub fn confirm_note(&mut self) -> bool {
-        if let (Some(freq), Some(cents)) = (self.current_freq, self.current_cents) {
-            let note = self.current_note();
-            self.profile.record_note(note.midi, freq, cents);
+        let Some(note) = self.current_note() else {
+            return self.is_complete();
+        };
+        let name = note.display_name_with(self.spelling);
+        match (self.current_freq, self.current_cents, self.empty_confirm) {
+            (Some(freq), Some(cents), _) => {
+                self.profile.record_note(note.midi, freq, cents);
+                self.unmeasured.remove(&self.current_note_idx);
+                self.notice = None;
+            }
+            (_, _, EmptyConfirm::Reject) => {
+                self.notice = Some(format!(
+                    "No reading for {} yet - play it, or press S to skip",
+                    name
+                ));
+                return false;
+            }
+            (_, _, EmptyConfirm::Unmeasured) => {
+                self.unmeasured.insert(self.current_note_idx);
+                self.notice = Some(format!("{} left unmeasured - nothing was heard", name));
+            }
         }
 
         self.current_note_idx += 1;
-        self.current_freq = None;
-        self.current_cents = None;
+        self.clear();
 
         self.is_complete()
     }
 
+    /// Keys confirmed with no reading, by index (0 = A0).
+    pub fn unmeasured(&self) -> &BTreeSet<usize> {
+        &self.unmeasured
+    }
+
+    /// Message about the last confirm, if any.
+    pub fn notice(&self) -> Option<&str> {
+        self.notice.as_deref()
+    }
+
     /// Skip the current note without recording.
     /// Returns true if profiling is now complete.
     pub fn skip_note(&mut self) -> bool {
         self.current_note_idx += 1;
-        self.current_freq = None;
-        self.current_cents = None;
+        self.clear();
 
         self.is_complete()
     }
@@ -90,11 +300,30 @@ impl ProfilingScreen {
     pub fn go_back(&mut self) {
         if self.current_note_idx > 0 {
             self.current_note_idx -= 1;
-            self.current_freq = None;
-            self.current_cents = None;
+            self.clear();
         }
     }
 
+    /// Jump to a key by index, clamped to the keyboard.
+    pub fn goto(&mut self, idx: usize) {
+        let idx = idx.min(NOTE_COUNT - 1);
+        if idx != self.current_note_idx {
+            self.current_note_idx = idx;
+            self.clear();
+        }
+    }
+
+    /// Move by `keys` semitones, stopping at either end of the keyboard.
+    pub fn move_by(&mut self, keys: i32) {
+        let idx = (self.current_note_idx as i32 + keys).clamp(0, NOTE_COUNT as i32 - 1);
+        self.goto(idx as usize);
+    }
+
+    /// Move by whole octaves, stopping at either end of the keyboard.
+    pub fn move_octaves(&mut self, octaves: i32) {
+        self.move_by(octaves * 12);
+    }
+
     /// Check if profiling is complete (all 88 notes visited).
     pub fn is_complete(&self) -> bool {
         self.current_note_idx >= 88
@@ -130,7 +359,13 @@ impl Default for ProfilingScreen {
 impl Widget for &ProfilingScreen {
     fn render(self, area: Rect, buf: &mut Buffer) {
         let note = self.current_note();
-        let title = format!(" Profile: {} ", note.display_name());
+        let note_name = note
+            .map(|n| n.display_name_with(self.spelling))
+            .unwrap_or_default();
+        let title = match note {
+            Some(_) => format!(" Profile: {} ", note_name),
+            None => " Profile ".to_string(),
+        };
 
         let block = Block::default()
             .borders(Borders::ALL)
@@ -162,7 +397,8 @@ impl Widget for &ProfilingScreen {
 
         // Progress indicator
         let (completed, total) = self.progress();
-        let progress = Progress::new(completed, total, note.display_name(), "Profiling");
+        let completed = completed.min(total);
+        let progress = Progress::new(completed, total, note_name.as_str(), "Profiling");
         progress.render(chunks[0], buf);
 
         // Piano visualization with profiled notes colored by deviation
@@ -176,27 +412,70 @@ impl Widget for &ProfilingScreen {
 
         let piano = Piano::full()
             .with_deviations(deviations)
-            .current(Some(self.current_note_idx));
-        piano.render(chunks[2], buf);
+            .with_key_colors(self.key_colors.0, self.key_colors.1)
+            .current(note.map(|_| self.current_note_idx));
+        let current_midi = note.map(|n| n.midi);
+        piano
+            .fit_around(current_midi, chunks[2].width as usize)
+            .render(chunks[2], buf);
+
+        let Some(note) = note else {
+            Paragraph::new("All keys visited")
+                .style(Theme::accent())
+                .alignment(Alignment::Center)
+                .render(chunks[4], buf);
+            return;
+        };
 
         // Note info panel
-        render_note_info(note, &self.profile, chunks[4], buf);
+        render_note_info(
+            &note_name,
+            self.target_for(note),
+            note.strings,
+            &self.profile,
+            chunks[4],
+            buf,
+        );
+
+        // Why the last confirm didn't record, between the info lines
+        if let (Some(notice), true) = (&self.notice, chunks[4].height >= 3) {
+            let notice_area = Rect {
+                y: chunks[4].y + 1,
+                height: 1,
+                ..chunks[4]
+            };
+            Paragraph::new(notice.as_str())
+                .style(Theme::warning())
+                .alignment(Alignment::Center)
+                .render(notice_area, buf);
+        }
 
         // Cents meter
-        if let Some(cents) = self.current_cents {
-            let meter = Meter::new(cents);
+        if self.warming_up {
+            Meter::warming_up()
+                .with_frame(self.animation_frame)
+                .render(chunks[6], buf);
+        } else if let Some(cents) = self.current_cents {
+            let meter = Meter::new(cents).with_confidence(self.confidence);
             meter.render(chunks[6], buf);
         } else {
-            let meter = Meter::listening();
+            let meter = Meter::listening().with_frame(self.animation_frame);
             meter.render(chunks[6], buf);
         }
 
         // Help text
         let help_text = format!(
-            "{} Confirm  {} Back  {} Skip  {} Quit",
+            "{}{} Confirm  {} Back  {} Skip  {} Key  {} Octave  {} Quit",
+            if self.auto_confirm {
+                "Auto-confirm on  "
+            } else {
+                ""
+            },
             Shortcuts::SPACE,
             Shortcuts::BACK,
             Shortcuts::SKIP,
+            Shortcuts::LEFT_RIGHT,
+            Shortcuts::ARROWS,
             Shortcuts::QUIT
         );
         let help = Paragraph::new(help_text)
@@ -207,18 +486,22 @@ impl Widget for &ProfilingScreen {
 }
 
 /// Render note info panel.
-fn render_note_info(note: &Note, profile: &PianoProfile, area: Rect, buf: &mut Buffer) {
+fn render_note_info(
+    note_name: &str,
+    target_freq: f32,
+    strings: u8,
+    profile: &PianoProfile,
+    area: Rect,
+    buf: &mut Buffer,
+) {
     if area.height < 3 {
         return;
     }
 
     // Note name and target frequency
-    let target_freq = 440.0 * 2_f32.powf((note.midi as f32 - 69.0) / 12.0);
     let info_line = format!(
         "{}  Target: {:.1} Hz  Strings: {}",
-        note.display_name(),
-        target_freq,
-        note.strings
+        note_name, target_freq, strings
     );
 
     let info = Paragraph::new(info_line)
@@ -253,3 +536,215 @@ fn render_note_info(note: &Note, profile: &PianoProfile, area: Rect, buf: &mut B
     };
     summary_para.render(summary_area, buf);
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn screen_at(idx: usize) -> ProfilingScreen {
+        let mut screen = ProfilingScreen::new();
+        for _ in 0..idx {
+            screen.skip_note();
+        }
+        screen
+    }
+
+    #[test]
+    fn test_confirm_without_reading_is_rejected() {
+        let mut screen = screen_at(48);
+        assert!(!screen.confirm_note());
+
+        assert_eq!(screen.current_note_idx(), 48, "stays on the key");
+        assert!(screen.profile().notes[48].is_none());
+        assert!(screen.notice().unwrap().contains("No reading for A4"));
+
+        // Playing the note clears the hint and lets the confirm through
+        screen.update(440.5, 2.0);
+        assert_eq!(screen.notice(), None);
+        screen.confirm_note();
+        assert_eq!(screen.current_note_idx(), 49);
+        assert!(screen.profile().notes[48].is_some());
+    }
+
+    #[test]
+    fn test_confirm_without_reading_can_mark_unmeasured() {
+        let mut screen = screen_at(48).with_empty_confirm(EmptyConfirm::Unmeasured);
+        screen.confirm_note();
+
+        assert_eq!(screen.current_note_idx(), 49, "moves on");
+        assert!(screen.profile().notes[48].is_none());
+        assert!(screen.unmeasured().contains(&48));
+        assert!(screen.notice().unwrap().contains("A4 left unmeasured"));
+
+        // Measuring it later clears the flag
+        screen.go_back();
+        screen.update(440.5, 2.0);
+        screen.confirm_note();
+        assert!(screen.unmeasured().is_empty());
+    }
+
+    #[test]
+    fn test_octave_up_from_bottom() {
+        let mut screen = ProfilingScreen::new();
+        screen.move_octaves(1);
+        assert_eq!(screen.current_note_idx(), 12);
+        screen.move_by(1);
+        assert_eq!(screen.current_note_idx(), 13);
+        screen.move_octaves(-1);
+        screen.move_by(-1);
+        assert_eq!(screen.current_note_idx(), 0);
+    }
+
+    #[test]
+    fn test_navigation_clamps_at_ends() {
+        let mut screen = ProfilingScreen::new();
+        screen.move_by(-1);
+        assert_eq!(screen.current_note_idx(), 0);
+        screen.move_octaves(-1);
+        assert_eq!(screen.current_note_idx(), 0);
+
+        screen.goto(80);
+        screen.move_octaves(1);
+        assert_eq!(screen.current_note_idx(), 87);
+        screen.move_by(1);
+        assert_eq!(screen.current_note_idx(), 87);
+
+        screen.goto(5);
+        screen.move_octaves(-1);
+        assert_eq!(screen.current_note_idx(), 0);
+
+        screen.goto(200);
+        assert_eq!(screen.current_note_idx(), 87);
+    }
+
+    #[test]
+    fn test_navigation_clears_reading() {
+        let mut screen = ProfilingScreen::new();
+        screen.update(27.5, 2.0);
+        screen.move_by(1);
+        screen.confirm_note();
+        assert!(screen.profile().notes[1].is_none(), "stale reading dropped");
+    }
+
+    /// Feed `count` readings at `cents` from the target.
+    fn hear(screen: &mut ProfilingScreen, cents: f32, count: usize) {
+        for _ in 0..count {
+            screen.update(27.5, cents);
+        }
+    }
+
+    #[test]
+    fn test_steady_reading_auto_confirms() {
+        let mut screen = ProfilingScreen::new().with_auto_confirm(true);
+        hear(&mut screen, 4.0, AUTO_CONFIRM_READINGS - 1);
+        assert!(!screen.should_auto_confirm());
+
+        screen.update(27.5, 5.5);
+        assert!(screen.should_auto_confirm());
+
+        screen.confirm_note();
+        assert!(!screen.should_auto_confirm(), "next note starts fresh");
+        assert!(screen.profile().notes[0].is_some());
+    }
+
+    #[test]
+    fn test_silence_and_wandering_do_not_auto_confirm() {
+        let mut screen = ProfilingScreen::new().with_auto_confirm(true);
+
+        hear(&mut screen, 2.0, AUTO_CONFIRM_READINGS - 1);
+        screen.clear();
+        hear(&mut screen, 2.0, 1);
+        assert!(!screen.should_auto_confirm(), "silence restarts the run");
+
+        for i in 0..AUTO_CONFIRM_READINGS * 2 {
+            screen.update(27.5, if i % 2 == 0 { -10.0 } else { 10.0 });
+        }
+        assert!(!screen.should_auto_confirm(), "wandering pitch");
+
+        hear(&mut screen, 80.0, AUTO_CONFIRM_READINGS * 2);
+        assert!(!screen.should_auto_confirm(), "likely the wrong key");
+    }
+
+    #[test]
+    fn test_octave_ambiguity_blocks_auto_confirm() {
+        let mut screen = ProfilingScreen::new().with_auto_confirm(true);
+        screen.set_octave_warning(true);
+        hear(&mut screen, 1.0, AUTO_CONFIRM_READINGS * 2);
+        assert!(!screen.should_auto_confirm());
+
+        screen.set_octave_warning(false);
+        hear(&mut screen, 1.0, AUTO_CONFIRM_READINGS);
+        assert!(screen.should_auto_confirm());
+    }
+
+    #[test]
+    fn test_auto_confirm_off_by_default() {
+        let mut screen = ProfilingScreen::new();
+        hear(&mut screen, 1.0, AUTO_CONFIRM_READINGS * 2);
+        assert!(!screen.should_auto_confirm());
+    }
+
+    fn render(screen: &ProfilingScreen) -> Buffer {
+        let area = Rect::new(0, 0, 100, 30);
+        let mut buf = Buffer::empty(area);
+        screen.render(area, &mut buf);
+        buf
+    }
+
+    fn render_text(screen: &ProfilingScreen) -> String {
+        let buf = render(screen);
+        (0..buf.area.height)
+            .map(|y| {
+                (0..buf.area.width)
+                    .map(|x| buf[(x, y)].symbol())
+                    .collect::<String>()
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    #[test]
+    fn test_target_uses_a4_setting() {
+        let a4 = Note::from_name("A4").unwrap();
+        let screen =
+            screen_at((a4.midi - 21) as usize).with_temperament(Temperament::with_a4(442.0));
+
+        assert_eq!(screen.target_freq(), Some(442.0));
+        assert!(render_text(&screen).contains("Target: 442.0 Hz"));
+    }
+
+    #[test]
+    fn test_target_includes_stretch() {
+        let c7 = Note::from_name("C7").unwrap();
+        let plain = screen_at((c7.midi - 21) as usize);
+        let stretched = screen_at((c7.midi - 21) as usize).with_stretch(StretchCurve::new());
+
+        let cents = Temperament::new().cents_from_target(
+            stretched.target_freq().unwrap(),
+            plain.target_freq().unwrap(),
+        );
+        let expected = StretchCurve::new().offset_cents(c7.midi);
+        assert!((cents - expected).abs() < 0.01);
+        assert_eq!(stretched.profile().stretch, Some(StretchCurve::new()));
+    }
+
+    #[test]
+    fn test_current_note_at_last_key() {
+        let screen = screen_at(NOTES.len() - 1);
+        assert_eq!(screen.current_note().map(|n| n.midi), Some(108));
+        render(&screen);
+    }
+
+    #[test]
+    fn test_current_note_past_end() {
+        let mut screen = screen_at(NOTES.len());
+        assert!(screen.is_complete());
+        assert!(screen.current_note().is_none());
+
+        // Confirming and rendering past the end must not panic
+        screen.update(440.0, 0.0);
+        screen.confirm_note();
+        assert!(screen.current_note().is_none());
+        render(&screen);
+    }
+}
@@ -0,0 +1,171 @@
+//! Ear training screen.
+
+use ratatui::{
+    buffer::Buffer,
+    layout::{Alignment, Constraint, Layout, Rect},
+    widgets::{Block, Borders, Paragraph, Widget},
+};
+
+use crate::tuning::training::{RoundGenerator, TrainingResult, TrainingRound, TrainingStats};
+use crate::ui::theme::{Shortcuts, Theme};
+
+/// Cents moved per arrow key press.
+pub const ADJUST_STEP_CENTS: f32 = 1.0;
+
+/// Ear training screen: match the simulated string to the reference by ear.
+pub struct TrainingScreen {
+    /// Current round.
+    round: TrainingRound,
+    /// Running stats across rounds.
+    stats: TrainingStats,
+    /// Source of new rounds.
+    generator: RoundGenerator,
+}
+
+impl TrainingScreen {
+    /// Create a training screen with a time-seeded generator.
+    pub fn new() -> Self {
+        Self::with_generator(RoundGenerator::from_time())
+    }
+
+    /// Create a training screen with a specific generator.
+    pub fn with_generator(mut generator: RoundGenerator) -> Self {
+        let round = generator.next_round();
+        Self {
+            round,
+            stats: TrainingStats::new(),
+            generator,
+        }
+    }
+
+    /// Get the current round.
+    pub fn round(&self) -> &TrainingRound {
+        &self.round
+    }
+
+    /// Get the running stats.
+    pub fn stats(&self) -> &TrainingStats {
+        &self.stats
+    }
+
+    /// Raise the simulated string by one step.
+    pub fn raise(&mut self) {
+        self.round.adjust(ADJUST_STEP_CENTS);
+    }
+
+    /// Lower the simulated string by one step.
+    pub fn lower(&mut self) {
+        self.round.adjust(-ADJUST_STEP_CENTS);
+    }
+
+    /// Reveal the current round, recording it in the stats the first time.
+    pub fn reveal(&mut self) -> TrainingResult {
+        let already_revealed = self.round.is_revealed();
+        let result = self.round.reveal();
+        if !already_revealed {
+            self.stats.record(&result);
+        }
+        result
+    }
+
+    /// Start the next round. Only allowed once the current one is revealed.
+    pub fn next_round(&mut self) -> bool {
+        if !self.round.is_revealed() {
+            return false;
+        }
+        self.round = self.generator.next_round();
+        true
+    }
+}
+
+impl Default for TrainingScreen {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Widget for &TrainingScreen {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let note_name = self
+            .round
+            .note()
+            .map(|n| n.display_name())
+            .unwrap_or_default();
+
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(Theme::border())
+            .title(format!(" Ear Training: {} ", note_name))
+            .title_style(Theme::title());
+
+        let inner = block.inner(area);
+        block.render(area, buf);
+
+        if inner.height < 10 || inner.width < 40 {
+            let msg = "Terminal too small";
+            buf.set_string(inner.x, inner.y, msg, Theme::warning());
+            return;
+        }
+
+        let chunks = Layout::vertical([
+            Constraint::Length(2), // Instructions
+            Constraint::Length(1), // Spacer
+            Constraint::Length(3), // Result
+            Constraint::Length(1), // Spacer
+            Constraint::Length(2), // Stats
+            Constraint::Min(1),    // Spacer
+            Constraint::Length(2), // Help text
+        ])
+        .split(inner);
+
+        let instruction = if self.round.is_revealed() {
+            "Press ENTER for the next round"
+        } else {
+            "Listen, then move the string until it matches the reference"
+        };
+        Paragraph::new(instruction)
+            .style(Theme::title())
+            .alignment(Alignment::Center)
+            .render(chunks[0], buf);
+
+        let result_lines = match self.round.result() {
+            Some(result) => vec![
+                format!("You were {:+.1} cents off", result.error_cents),
+                format!("Score: {}/100", result.score),
+            ],
+            None => vec!["Hidden until you reveal".to_string()],
+        };
+        let result_style = match self.round.result() {
+            Some(result) => Theme::style_for_cents(result.error_cents),
+            None => Theme::muted(),
+        };
+        Paragraph::new(result_lines.join("\n"))
+            .style(result_style)
+            .alignment(Alignment::Center)
+            .render(chunks[2], buf);
+
+        let stats_text = format!(
+            "Rounds: {}  Accuracy: {:.0}%  Avg error: {:.1} cents",
+            self.stats.rounds(),
+            self.stats.accuracy(),
+            self.stats.average_error()
+        );
+        Paragraph::new(stats_text)
+            .style(Theme::muted())
+            .alignment(Alignment::Center)
+            .render(chunks[4], buf);
+
+        let help_text = format!(
+            "{} Adjust  {} Listen  {} Reveal  {} Next  {} Quit",
+            Shortcuts::ARROWS,
+            Shortcuts::REFERENCE,
+            Shortcuts::SPACE,
+            Shortcuts::ENTER,
+            Shortcuts::QUIT
+        );
+        Paragraph::new(help_text)
+            .style(Theme::muted())
+            .alignment(Alignment::Center)
+            .render(chunks[6], buf);
+    }
+}
@@ -1,17 +1,73 @@
 //! Main tuning screen.
 
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 
 use ratatui::{
     buffer::Buffer,
     layout::{Alignment, Constraint, Layout, Rect},
+    text::{Line, Span},
     widgets::{Block, Borders, Paragraph, Widget},
 };
 
+use crate::tuning::intervals::{beat_rate, Interval};
 use crate::ui::components::instructions::TuningStep;
-use crate::ui::components::{Instructions, Meter, Piano, Progress};
+use crate::ui::components::{
+    Instructions, KeyColors, Meter, MeterCurve, OctaveProgress, Piano, Progress,
+};
+use crate::ui::format::{self, DEFAULT_CENTS_PRECISION};
+use crate::ui::language::Language;
 use crate::ui::theme::{Shortcuts, Theme};
 
+/// Default display and confirmation tolerance in cents.
+pub const DEFAULT_TOLERANCE_CENTS: f32 = 5.0;
+
+/// Furthest a reading can be from the target and still be coached as this note.
+///
+/// Wider than half a semitone so a badly slipped string still gets
+/// tighten/loosen hints instead of a wrong-note message.
+pub const ON_NOTE_CENTS: f32 = 75.0;
+
+/// Largest deviation a real reading can have: eight octaves, more than the
+/// keyboard spans. Anything beyond is a detection glitch.
+pub const MAX_READING_CENTS: f32 = 9600.0;
+
+/// How the keyboard shows progress while tuning.
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    Default,
+    serde::Serialize,
+    serde::Deserialize,
+    clap::ValueEnum,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum KeyboardView {
+    /// Plain keyboard with the current key.
+    #[default]
+    Chromatic,
+    /// Completed keys plus a marker on the next key in tuning order.
+    Order,
+}
+
+/// What the microphone is hearing relative to the target note.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Detection {
+    /// Not enough audio buffered yet for a reading.
+    WarmingUp,
+    /// No pitch detected.
+    Listening,
+    /// A pitch too far from the target to be this note.
+    WrongNote {
+        /// Name of the nearest note to what was heard.
+        heard: String,
+    },
+    /// Close enough to the target to coach.
+    OnNote,
+}
+
 /// Main tuning screen state.
 pub struct TuningScreen {
     /// Current note name.
@@ -38,6 +94,56 @@ pub struct TuningScreen {
     show_piano_progress: bool,
     /// Set of completed chromatic indices.
     completed_notes: HashSet<usize>,
+    /// How the keyboard shows progress.
+    keyboard_view: KeyboardView,
+    /// Chromatic index of the next note in tuning order.
+    next_key: Option<usize>,
+    /// How the meter maps cents to positions.
+    meter_curve: MeterCurve,
+    /// How keys on the deviation keyboard are colored.
+    key_colors: KeyColors,
+    /// Decimal places on cents readouts.
+    cents_precision: u8,
+    /// Octave also detected (neighboring strings ringing).
+    octave_warning: bool,
+    /// Detection confidence of the last reading.
+    confidence: f32,
+    /// Whether the note seems to beat against itself.
+    false_beats: bool,
+    /// Deviations measured before tuning, by chromatic index.
+    starting_deviations: HashMap<usize, f32>,
+    /// Cents within which the meter shows in tune.
+    display_tolerance: f32,
+    /// Cents within which the note counts as complete.
+    confirm_tolerance: f32,
+    /// Showing reference and detected pitch side by side.
+    comparing: bool,
+    /// Name of the nearest note to the detected pitch.
+    heard_note: Option<String>,
+    /// Whether the microphone buffer is still filling.
+    warming_up: bool,
+    /// Frame of the listening animation; `None` keeps it still.
+    animation_frame: Option<u64>,
+    /// Whether to keep the last reading visible through silence.
+    hold: bool,
+    /// Last reading (frequency, cents) for the current note.
+    last_reading: Option<(f32, f32)>,
+    /// Language for coaching instructions.
+    language: Language,
+    /// Completed and total notes per octave, as (octave, completed, total).
+    octave_progress: Vec<(i8, usize, usize)>,
+    /// Tuner's remark on this note.
+    comment: Option<String>,
+    /// Remark being typed, while editing.
+    comment_input: Option<String>,
+    /// Best-guess frequency when sound is heard but no reading is trusted.
+    raw_estimate: Option<f32>,
+    /// Microphone input is hitting full scale.
+    clipping: bool,
+    /// Microphone has delivered only dead silence for a long stretch.
+    no_audio: bool,
+    /// Tuned note an octave below and its actual pitch, when tuning by octave beats.
+    octave_reference: Option<(String, f32)>,
 }
 
 impl TuningScreen {
@@ -50,34 +156,79 @@ impl TuningScreen {
         string_count: u8,
         midi: u8,
     ) -> Self {
-        // Use first_for_strings to get the starting step for bi/trichord notes
-        let tuning_step = TuningStep::first_for_strings(string_count);
-
-        let phase_name = if string_count == 3 {
-            "Trichord".to_string()
-        } else if string_count == 2 {
-            "Bichord".to_string()
-        } else {
-            "Single".to_string()
-        };
-
-        // Chromatic index: 0=A0 (MIDI 21), 87=C8 (MIDI 108)
-        let chromatic_index = (midi - 21) as usize;
-
-        Self {
-            note_name: note_name.into(),
-            note_index,
-            chromatic_index,
+        let mut screen = Self {
+            note_name: String::new(),
+            note_index: 0,
+            chromatic_index: 0,
             total_notes,
-            target_freq,
+            target_freq: 0.0,
             detected_freq: None,
             cents_deviation: 0.0,
-            string_count,
-            tuning_step,
-            phase_name,
+            string_count: 1,
+            tuning_step: None,
+            phase_name: String::new(),
             show_piano_progress: false,
             completed_notes: HashSet::new(),
+            keyboard_view: KeyboardView::default(),
+            next_key: None,
+            meter_curve: MeterCurve::default(),
+            key_colors: KeyColors::default(),
+            cents_precision: DEFAULT_CENTS_PRECISION,
+            octave_warning: false,
+            confidence: 1.0,
+            false_beats: false,
+            starting_deviations: HashMap::new(),
+            display_tolerance: DEFAULT_TOLERANCE_CENTS,
+            confirm_tolerance: DEFAULT_TOLERANCE_CENTS,
+            comparing: false,
+            heard_note: None,
+            warming_up: false,
+            animation_frame: None,
+            hold: false,
+            last_reading: None,
+            language: Language::default(),
+            octave_progress: Vec::new(),
+            comment: None,
+            comment_input: None,
+            raw_estimate: None,
+            clipping: false,
+            no_audio: false,
+            octave_reference: None,
+        };
+        screen.load_note(note_name, note_index, target_freq, string_count, midi);
+        screen
+    }
+
+    /// Switch to a new note in place.
+    ///
+    /// Detection and step state reset; display settings, completed notes
+    /// and starting deviations are kept.
+    pub fn load_note(
+        &mut self,
+        note_name: impl Into<String>,
+        note_index: usize,
+        target_freq: f32,
+        string_count: u8,
+        midi: u8,
+    ) {
+        self.note_name = note_name.into();
+        self.note_index = note_index;
+        // Chromatic index: 0=A0 (MIDI 21), 87=C8 (MIDI 108)
+        self.chromatic_index = (midi - 21) as usize;
+        self.target_freq = target_freq;
+        self.string_count = string_count;
+        // Use first_for_strings to get the starting step for bi/trichord notes
+        self.tuning_step = TuningStep::first_for_strings(string_count);
+        self.phase_name = match string_count {
+            3 => "Trichord",
+            2 => "Bichord",
+            _ => "Single",
         }
+        .to_string();
+        self.last_reading = None;
+        self.comment = None;
+        self.comment_input = None;
+        self.clear();
     }
 
     /// Toggle piano progress display.
@@ -85,26 +236,293 @@ impl TuningScreen {
         self.show_piano_progress = !self.show_piano_progress;
     }
 
+    /// Toggle the reference comparison view.
+    pub fn toggle_compare(&mut self) {
+        self.comparing = !self.comparing;
+    }
+
+    /// Check if the reference comparison view is showing.
+    pub fn is_comparing(&self) -> bool {
+        self.comparing
+    }
+
+    /// Detected minus target frequency in Hz, if a pitch is detected.
+    pub fn difference_hz(&self) -> Option<f32> {
+        self.detected_freq.map(|f| f - self.target_freq)
+    }
+
     /// Set the completed notes for progress display.
     pub fn set_completed_notes(&mut self, completed: HashSet<usize>) {
         self.completed_notes = completed;
     }
 
+    /// Set how the keyboard shows progress.
+    pub fn set_keyboard_view(&mut self, view: KeyboardView) {
+        self.keyboard_view = view;
+    }
+
+    /// Set how the meter maps cents to positions.
+    pub fn set_meter_curve(&mut self, curve: MeterCurve) {
+        self.meter_curve = curve;
+    }
+
+    /// Set how keys on the deviation keyboard are colored.
+    pub fn set_key_colors(&mut self, key_colors: KeyColors) {
+        self.key_colors = key_colors;
+    }
+
+    /// Set the decimal places on cents readouts (0 to 2).
+    pub fn set_cents_precision(&mut self, precision: u8) {
+        self.cents_precision = precision;
+    }
+
+    /// Set the chromatic index of the next note in tuning order.
+    pub fn set_next_key(&mut self, key: Option<usize>) {
+        self.next_key = key;
+    }
+
+    /// Set per-octave progress, shown with the piano progress view.
+    pub fn set_octave_progress(&mut self, progress: impl Into<Vec<(i8, usize, usize)>>) {
+        self.octave_progress = progress.into();
+    }
+
+    /// Set the deviations each note started at (chromatic index -> cents).
+    pub fn set_starting_deviations(&mut self, deviations: HashMap<usize, f32>) {
+        self.starting_deviations = deviations;
+    }
+
+    /// Set the meter's in-tune zone and the completion gate, in cents.
+    pub fn set_tolerances(&mut self, display: f32, confirm: f32) {
+        self.display_tolerance = display;
+        self.confirm_tolerance = confirm;
+    }
+
+    /// Get the meter's in-tune tolerance in cents.
+    pub fn display_tolerance(&self) -> f32 {
+        self.display_tolerance
+    }
+
+    /// Get the completion tolerance in cents.
+    pub fn confirm_tolerance(&self) -> f32 {
+        self.confirm_tolerance
+    }
+
+    /// Check if the meter shows the current reading as in tune.
+    pub fn shows_in_tune(&self) -> bool {
+        self.detected_freq.is_some() && self.cents_deviation.abs() <= self.display_tolerance
+    }
+
+    /// Get the deviation the current note started at, if known.
+    pub fn starting_cents(&self) -> Option<f32> {
+        self.starting_deviations.get(&self.chromatic_index).copied()
+    }
+
+    /// Get the detected frequency, if a pitch is being heard.
+    pub fn detected_freq(&self) -> Option<f32> {
+        self.detected_freq
+    }
+
     /// Get note index.
     pub fn note_index(&self) -> usize {
         self.note_index
     }
 
     /// Update with detected pitch.
+    ///
+    /// Non-finite or implausible readings are ignored, keeping the prior one.
     pub fn update(&mut self, freq: f32, cents: f32) {
+        let valid =
+            freq.is_finite() && freq > 0.0 && cents.is_finite() && cents.abs() <= MAX_READING_CENTS;
+        if !valid {
+            return;
+        }
+
         self.detected_freq = Some(freq);
         self.cents_deviation = cents;
+        self.last_reading = Some((freq, cents));
+        self.raw_estimate = None;
+    }
+
+    /// Set the best guess for sound too unclear to read, or `None`.
+    pub fn set_raw_estimate(&mut self, freq: Option<f32>) {
+        self.raw_estimate = freq.filter(|f| f.is_finite() && *f > 0.0);
+    }
+
+    /// Best-guess frequency shown while no reading is trusted.
+    pub fn raw_estimate(&self) -> Option<f32> {
+        match self.detection() {
+            Detection::Listening => self.raw_estimate,
+            _ => None,
+        }
+    }
+
+    /// Set the language for coaching instructions.
+    pub fn set_language(&mut self, language: Language) {
+        self.language = language;
+    }
+
+    /// Get the remark on this note.
+    pub fn comment(&self) -> Option<&str> {
+        self.comment.as_deref()
+    }
+
+    /// Start typing a remark, starting from the current one.
+    pub fn start_comment(&mut self) {
+        self.comment_input = Some(self.comment.clone().unwrap_or_default());
+    }
+
+    /// Check if a remark is being typed.
+    pub fn is_editing_comment(&self) -> bool {
+        self.comment_input.is_some()
+    }
+
+    /// Type a character into the remark being edited.
+    pub fn type_comment(&mut self, c: char) {
+        if let Some(input) = &mut self.comment_input {
+            input.push(c);
+        }
+    }
+
+    /// Delete the last character of the remark being edited.
+    pub fn backspace_comment(&mut self) {
+        if let Some(input) = &mut self.comment_input {
+            input.pop();
+        }
+    }
+
+    /// Keep the typed remark; an empty one removes it.
+    pub fn commit_comment(&mut self) {
+        if let Some(input) = self.comment_input.take() {
+            let trimmed = input.trim();
+            self.comment = (!trimmed.is_empty()).then(|| trimmed.to_string());
+        }
+    }
+
+    /// Discard the typed remark.
+    pub fn cancel_comment(&mut self) {
+        self.comment_input = None;
+    }
+
+    /// Toggle keeping the last reading visible after the note decays.
+    pub fn toggle_hold(&mut self) {
+        self.hold = !self.hold;
+    }
+
+    /// Check if the last reading is held through silence.
+    pub fn is_holding(&self) -> bool {
+        self.hold
+    }
+
+    /// Get the reading (frequency, cents) held through silence, if any.
+    pub fn held_reading(&self) -> Option<(f32, f32)> {
+        match self.detection() {
+            Detection::Listening if self.hold => self.last_reading,
+            _ => None,
+        }
+    }
+
+    /// Set the nearest note to the detected pitch.
+    pub fn set_heard_note(&mut self, name: impl Into<String>) {
+        self.heard_note = Some(name.into());
+    }
+
+    /// Set whether the microphone buffer is still filling.
+    pub fn set_warming_up(&mut self, warming_up: bool) {
+        self.warming_up = warming_up;
+    }
+
+    /// Set the listening animation frame; `None` keeps it still.
+    pub fn set_animation_frame(&mut self, frame: Option<u64>) {
+        self.animation_frame = frame;
+    }
+
+    /// Classify the current reading against the target.
+    pub fn detection(&self) -> Detection {
+        if self.warming_up {
+            Detection::WarmingUp
+        } else if self.detected_freq.is_none() {
+            Detection::Listening
+        } else if self.cents_deviation.abs() > ON_NOTE_CENTS {
+            Detection::WrongNote {
+                heard: self.heard_note.clone().unwrap_or_default(),
+            }
+        } else {
+            Detection::OnNote
+        }
     }
 
     /// Clear detected pitch (silence/no detection).
     pub fn clear(&mut self) {
+        self.heard_note = None;
         self.detected_freq = None;
         self.cents_deviation = 0.0;
+        self.octave_warning = false;
+        self.false_beats = false;
+        self.raw_estimate = None;
+    }
+
+    /// Set the detection confidence of the latest reading.
+    pub fn set_confidence(&mut self, confidence: f32) {
+        self.confidence = confidence;
+    }
+
+    /// Set whether the octave is sounding along with the note.
+    pub fn set_octave_warning(&mut self, ambiguous: bool) {
+        self.octave_warning = ambiguous;
+    }
+
+    /// Check if the octave warning is showing.
+    pub fn octave_warning(&self) -> bool {
+        self.octave_warning
+    }
+
+    /// Set whether the microphone input is clipping.
+    pub fn set_clipping(&mut self, clipping: bool) {
+        self.clipping = clipping;
+    }
+
+    /// Check if the clipping warning is showing.
+    pub fn clipping(&self) -> bool {
+        self.clipping
+    }
+
+    /// Tune by beats against the note an octave below, left at `pitch` Hz.
+    pub fn set_octave_reference(&mut self, reference: Option<(String, f32)>) {
+        self.octave_reference = reference;
+    }
+
+    /// Beat rate of the target against the octave below, if tuning by octave beats.
+    pub fn target_octave_beats(&self) -> Option<f32> {
+        let (_, lower) = self.octave_reference.as_ref()?;
+        Some(beat_rate(Interval::Octave, *lower, self.target_freq))
+    }
+
+    /// Beat rate of the detected pitch against the octave below.
+    pub fn octave_beats(&self) -> Option<f32> {
+        let (_, lower) = self.octave_reference.as_ref()?;
+        Some(beat_rate(Interval::Octave, *lower, self.detected_freq?))
+    }
+
+    /// Set whether the microphone seems to deliver no audio at all.
+    pub fn set_no_audio(&mut self, no_audio: bool) {
+        self.no_audio = no_audio;
+    }
+
+    /// Check if the no-audio hint is showing.
+    pub fn no_audio(&self) -> bool {
+        self.no_audio
+    }
+
+    /// Set whether the note seems to beat against itself.
+    pub fn set_false_beats(&mut self, false_beats: bool) {
+        self.false_beats = false_beats;
+    }
+
+    /// Check if the false beat warning is showing.
+    ///
+    /// Only meaningful while a single string sounds, since unisons beat anyway.
+    pub fn false_beat_warning(&self) -> bool {
+        self.false_beats && matches!(self.tuning_step, None | Some(TuningStep::TuneCenter))
     }
 
     /// Get current cents deviation.
@@ -156,18 +574,19 @@ impl TuningScreen {
 
     /// Check if note tuning is complete.
     pub fn is_complete(&self) -> bool {
+        let within = self.cents_deviation.abs() <= self.confirm_tolerance;
         match self.string_count {
             3 => {
                 self.tuning_step == Some(TuningStep::TuneRight)
-                    && self.cents_deviation.abs() <= 5.0
+                    && within
                     && self.detected_freq.is_some()
             }
             2 => {
                 self.tuning_step == Some(TuningStep::TuneBichord)
-                    && self.cents_deviation.abs() <= 5.0
+                    && within
                     && self.detected_freq.is_some()
             }
-            _ => self.cents_deviation.abs() <= 5.0 && self.detected_freq.is_some(),
+            _ => within && self.detected_freq.is_some(),
         }
     }
 
@@ -180,6 +599,65 @@ impl TuningScreen {
     pub fn target_freq(&self) -> f32 {
         self.target_freq
     }
+
+    /// Move the target, keeping the current note and step.
+    pub fn set_target_freq(&mut self, target_freq: f32) {
+        self.target_freq = target_freq;
+    }
+}
+
+impl TuningScreen {
+    /// Render reference and detected pitch side by side.
+    fn render_compare(&self, area: Rect, buf: &mut Buffer) {
+        let rows = Layout::vertical([
+            Constraint::Length(1), // Labels
+            Constraint::Length(1), // Frequencies
+            Constraint::Length(1), // Spacer
+            Constraint::Length(1), // Difference
+        ])
+        .split(area);
+        let columns = |row: Rect| Layout::horizontal([Constraint::Ratio(1, 2); 2]).split(row);
+        let (labels, values) = (columns(rows[0]), columns(rows[1]));
+
+        Paragraph::new("Reference (playing)")
+            .style(Theme::muted())
+            .alignment(Alignment::Center)
+            .render(labels[0], buf);
+        Paragraph::new("Detected")
+            .style(Theme::muted())
+            .alignment(Alignment::Center)
+            .render(labels[1], buf);
+
+        Paragraph::new(format!("{:.2} Hz", self.target_freq))
+            .style(Theme::accent())
+            .alignment(Alignment::Center)
+            .render(values[0], buf);
+
+        let (detected, difference, style) = match self.difference_hz() {
+            Some(diff) => (
+                format!("{:.2} Hz", self.target_freq + diff),
+                format!(
+                    "Difference: {:+.2} Hz ({:+.1} cents)",
+                    diff, self.cents_deviation
+                ),
+                Theme::style_for_cents(self.cents_deviation),
+            ),
+            None if self.warming_up => (
+                "--".to_string(),
+                "Warming up...".to_string(),
+                Theme::muted(),
+            ),
+            None => ("--".to_string(), "Listening...".to_string(), Theme::muted()),
+        };
+        Paragraph::new(detected)
+            .style(style)
+            .alignment(Alignment::Center)
+            .render(values[1], buf);
+        Paragraph::new(difference)
+            .style(style)
+            .alignment(Alignment::Center)
+            .render(rows[3], buf);
+    }
 }
 
 impl Widget for &TuningScreen {
@@ -225,19 +703,102 @@ impl Widget for &TuningScreen {
         );
         progress.render(chunks[0], buf);
 
-        // Piano visualization (full 88-key piano, A0=MIDI 21)
-        let piano = if self.show_piano_progress {
+        // The remark being typed, or where this note started and its remark
+        if let Some(input) = &self.comment_input {
+            Paragraph::new(format!("Comment: {}_", input))
+                .style(Theme::accent())
+                .alignment(Alignment::Center)
+                .render(chunks[1], buf);
+        } else {
+            let mut spans = Vec::new();
+            if let Some(start) = self.starting_cents() {
+                spans.push(Span::styled(
+                    format!(
+                        "Started at {}",
+                        format::signed_cents(start, self.cents_precision)
+                    ),
+                    Theme::style_for_cents(start),
+                ));
+            }
+            if let Some(comment) = &self.comment {
+                if !spans.is_empty() {
+                    spans.push(Span::styled("  ", Theme::muted()));
+                }
+                spans.push(Span::styled(format!("✎ {}", comment), Theme::muted()));
+            }
+            Paragraph::new(Line::from(spans))
+                .alignment(Alignment::Center)
+                .render(chunks[1], buf);
+        }
+
+        // Piano visualization (88 keys from A0=MIDI 21)
+        let piano = if self.keyboard_view == KeyboardView::Order {
+            Piano::full()
+                .highlighted(self.completed_notes.clone())
+                .next(self.next_key)
+                .current(Some(self.chromatic_index))
+        } else if self.show_piano_progress {
             Piano::full()
                 .highlighted(self.completed_notes.clone())
                 .current(Some(self.chromatic_index))
         } else {
-            Piano::full().current(Some(self.chromatic_index))
+            Piano::full()
+                .with_deviations(self.starting_deviations.clone())
+                .with_key_colors(self.key_colors, self.display_tolerance)
+                .current(Some(self.chromatic_index))
         };
-        piano.render(chunks[2], buf);
+        // On narrow terminals, scroll to keep the current note centered
+        let current_midi = Some(self.chromatic_index as u8 + 21);
+        piano
+            .fit_around(current_midi, chunks[2].width as usize)
+            .render(chunks[2], buf);
+
+        // Per-octave breakdown under the progress view
+        if self.show_piano_progress && !self.octave_progress.is_empty() {
+            OctaveProgress::new(self.octave_progress.clone()).render(chunks[3], buf);
+        }
 
-        // Instructions panel
-        let instructions_area = chunks[4];
-        if let Some(step) = self.tuning_step {
+        // Instructions panel, or the reference comparison, with the
+        // octave beat coaching on its last line
+        let mut instructions_area = chunks[4];
+        if let Some((lower_name, _)) = &self.octave_reference {
+            let [top, last] =
+                Layout::vertical([Constraint::Min(1), Constraint::Length(1)]).areas(chunks[4]);
+            instructions_area = top;
+            let (text, style) = match (self.octave_beats(), self.target_octave_beats()) {
+                (Some(beats), _) => (
+                    format!(
+                        "Beats with {}: {:.1}/s - tune until they stop",
+                        lower_name, beats
+                    ),
+                    Theme::accent(),
+                ),
+                (None, Some(target)) => (
+                    format!(
+                        "Play with {} and tune the octave beatless (target {:.1}/s)",
+                        lower_name, target
+                    ),
+                    Theme::muted(),
+                ),
+                (None, None) => (String::new(), Theme::muted()),
+            };
+            Paragraph::new(text)
+                .style(style)
+                .alignment(Alignment::Center)
+                .render(last, buf);
+        }
+        if self.comparing {
+            self.render_compare(instructions_area, buf);
+        } else if let Detection::WrongNote { heard } = self.detection() {
+            let message = format!(
+                "I hear {} but we're tuning {} - play {}",
+                heard, self.note_name, self.note_name
+            );
+            Paragraph::new(message)
+                .style(Theme::warning())
+                .alignment(Alignment::Center)
+                .render(instructions_area, buf);
+        } else if let Some(step) = self.tuning_step {
             // Multi-string note (bichord or trichord)
             let instructions = if is_muting_step {
                 // Don't show direction hints during muting
@@ -245,31 +806,85 @@ impl Widget for &TuningScreen {
             } else {
                 Instructions::for_step(step, self.string_count)
                     .with_direction_hint(self.cents_deviation)
-            };
+            }
+            .with_language(self.language);
             instructions.render(instructions_area, buf);
         } else {
             // Monochord note - simple instruction
-            let instructions = Instructions::simple().with_direction_hint(self.cents_deviation);
+            let instructions = Instructions::simple()
+                .with_direction_hint(self.cents_deviation)
+                .with_language(self.language);
             instructions.render(instructions_area, buf);
         }
 
+        // Input level problems, octave ambiguity or false beat warning,
+        // or the reading held through silence
+        if self.clipping {
+            Paragraph::new("Input clipping - turn down the microphone gain")
+                .style(Theme::warning())
+                .alignment(Alignment::Center)
+                .render(chunks[5], buf);
+        } else if self.no_audio {
+            Paragraph::new("No audio detected - check your microphone")
+                .style(Theme::warning())
+                .alignment(Alignment::Center)
+                .render(chunks[5], buf);
+        } else if self.octave_warning {
+            let warning = Paragraph::new("Octave also ringing - mute neighboring strings")
+                .style(Theme::warning())
+                .alignment(Alignment::Center);
+            warning.render(chunks[5], buf);
+        } else if self.false_beat_warning() {
+            Paragraph::new("String beats on its own - check for a twisted string or bridge pin")
+                .style(Theme::warning())
+                .alignment(Alignment::Center)
+                .render(chunks[5], buf);
+        } else if let Some((freq, cents)) = self.held_reading() {
+            Paragraph::new(format!("Held: {:.2} Hz ({:+.1} cents)", freq, cents))
+                .style(Theme::muted())
+                .alignment(Alignment::Center)
+                .render(chunks[5], buf);
+        } else if let Some(freq) = self.raw_estimate() {
+            Paragraph::new(format!("≈ {:.1} Hz (low confidence)", freq))
+                .style(Theme::muted())
+                .alignment(Alignment::Center)
+                .render(chunks[5], buf);
+        }
+
         // Cents meter (hidden during muting step)
         if !is_muting_step {
-            let meter = if self.detected_freq.is_some() {
-                Meter::new(self.cents_deviation)
-            } else {
-                Meter::listening()
+            let meter = match (self.detection(), self.held_reading()) {
+                (Detection::WarmingUp, _) => Meter::warming_up(),
+                (Detection::Listening, Some((_, cents))) => Meter::new(cents),
+                (Detection::Listening, None) => Meter::listening(),
+                _ => Meter::new(self.cents_deviation),
+            }
+            .tolerance(self.display_tolerance)
+            .with_curve(self.meter_curve)
+            .with_precision(self.cents_precision)
+            .with_confidence(self.confidence)
+            .with_frame(self.animation_frame);
+            // Ghost marker where the note sat in the baseline profile
+            let meter = match self.starting_cents() {
+                Some(start) => meter.with_previous(start),
+                None => meter,
             };
             meter.render(chunks[6], buf);
         }
 
         // Help text
         let help_text = format!(
-            "{} Confirm  {} Back  {} Progress  {} Skip  {} Quit",
+            "{} Confirm  {} Back  {} Progress  {} Compare  {} A/B  {} Hold  {} Comment  {} Leave  {} Skip  {} Finish  {} Quit",
             Shortcuts::SPACE,
             Shortcuts::BACK,
             Shortcuts::PIANO,
+            Shortcuts::REFERENCE,
+            Shortcuts::AB,
+            Shortcuts::HOLD,
+            Shortcuts::COMMENT,
+            Shortcuts::LEAVE,
             Shortcuts::SKIP,
+            Shortcuts::FINISH,
             Shortcuts::QUIT
         );
         let help = Paragraph::new(help_text)
@@ -278,3 +893,315 @@ impl Widget for &TuningScreen {
         help.render(chunks[7], buf);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_load_note_resets_detection() {
+        let mut screen = TuningScreen::new("F3", 0, 88, 174.61, 3, 53);
+        screen.update(175.0, 3.8);
+        screen.set_octave_warning(true);
+        screen.next_step();
+
+        screen.load_note("F#3", 1, 185.0, 3, 54);
+
+        assert_eq!(screen.note_name(), "F#3");
+        assert_eq!(screen.note_index(), 1);
+        assert_eq!(screen.target_freq(), 185.0);
+        assert_eq!(screen.cents(), 0.0);
+        assert!(!screen.octave_warning());
+        assert!(!screen.is_complete());
+        assert_eq!(
+            screen.tuning_step(),
+            TuningStep::first_for_strings(3),
+            "Should restart at the first step"
+        );
+    }
+
+    #[test]
+    fn test_display_and_confirm_tolerance_are_separate() {
+        let mut screen = TuningScreen::new("A1", 0, 88, 55.0, 1, 33);
+        screen.set_tolerances(5.0, 2.0);
+
+        screen.update(55.1, 3.0);
+        assert!(screen.shows_in_tune());
+        assert!(!screen.is_complete());
+
+        screen.update(55.05, 1.5);
+        assert!(screen.shows_in_tune());
+        assert!(screen.is_complete());
+    }
+
+    #[test]
+    fn test_tolerances_survive_load_note() {
+        let mut screen = TuningScreen::new("A1", 0, 88, 55.0, 1, 33);
+        screen.set_tolerances(5.0, 2.0);
+        screen.load_note("A#1", 1, 58.27, 1, 34);
+
+        assert_eq!(screen.display_tolerance(), 5.0);
+        assert_eq!(screen.confirm_tolerance(), 2.0);
+    }
+
+    fn render_text(screen: &TuningScreen) -> String {
+        let area = Rect::new(0, 0, 120, 30);
+        let mut buf = Buffer::empty(area);
+        screen.render(area, &mut buf);
+        (0..area.height)
+            .map(|y| {
+                (0..area.width)
+                    .map(|x| buf[(x, y)].symbol())
+                    .collect::<String>()
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    #[test]
+    fn test_raw_estimate_tagged_low_confidence() {
+        let mut screen = TuningScreen::new("A4", 0, 88, 440.0, 1, 69);
+        screen.set_raw_estimate(Some(437.26));
+        assert_eq!(screen.detection(), Detection::Listening);
+        assert!(render_text(&screen).contains("≈ 437.3 Hz (low confidence)"));
+
+        // Hidden once a trusted reading arrives
+        screen.update(440.0, 0.0);
+        assert_eq!(screen.raw_estimate(), None);
+        assert!(!render_text(&screen).contains("low confidence"));
+    }
+
+    #[test]
+    fn test_clipping_warning_outranks_others() {
+        let mut screen = TuningScreen::new("A4", 0, 88, 440.0, 1, 69);
+        screen.update(440.0, 0.0);
+        screen.set_octave_warning(true);
+        screen.set_clipping(true);
+        let text = render_text(&screen);
+        assert!(text.contains("Input clipping"));
+        assert!(!text.contains("Octave also ringing"));
+
+        // Survives losing the reading, since it comes from the raw input
+        screen.clear();
+        assert!(screen.clipping());
+        screen.set_clipping(false);
+        assert!(!render_text(&screen).contains("Input clipping"));
+    }
+
+    #[test]
+    fn test_no_audio_hint() {
+        let mut screen = TuningScreen::new("A4", 0, 88, 440.0, 1, 69);
+        assert!(!render_text(&screen).contains("No audio detected"));
+        screen.set_no_audio(true);
+        assert!(render_text(&screen).contains("No audio detected - check your microphone"));
+    }
+
+    #[test]
+    fn test_octave_beats_line() {
+        let mut screen = TuningScreen::new("A6", 0, 88, 1760.0, 3, 93);
+        assert!(!render_text(&screen).contains("Beats with"));
+
+        screen.set_octave_reference(Some(("A5".to_string(), 881.0)));
+        assert!(render_text(&screen).contains("tune the octave beatless (target 2.0/s)"));
+
+        screen.update(1761.0, 1.0);
+        assert!(render_text(&screen).contains("Beats with A5: 1.0/s - tune until they stop"));
+    }
+
+    #[test]
+    fn test_compare_view_shows_both_readouts() {
+        let mut screen = TuningScreen::new("A4", 0, 88, 440.0, 3, 69);
+        screen.toggle_compare();
+        assert!(screen.is_comparing());
+
+        screen.update(441.5, 5.9);
+        let diff = screen.difference_hz().expect("Should have a difference");
+        assert!((diff - 1.5).abs() < 1e-4);
+
+        let text = render_text(&screen);
+        assert!(text.contains("440.00 Hz"));
+        assert!(text.contains("441.50 Hz"));
+        assert!(text.contains("Difference: +1.50 Hz (+5.9 cents)"));
+    }
+
+    #[test]
+    fn test_compare_view_without_pitch() {
+        let mut screen = TuningScreen::new("A4", 0, 88, 440.0, 3, 69);
+        screen.toggle_compare();
+
+        assert_eq!(screen.difference_hz(), None);
+        let text = render_text(&screen);
+        assert!(text.contains("440.00 Hz"));
+        assert!(!text.contains("Difference"));
+    }
+
+    /// Feed a frequency the way the app does, with the nearest note.
+    fn hear(screen: &mut TuningScreen, freq: f32) {
+        let temperament = crate::tuning::Temperament::new();
+        let (midi, _) = temperament.nearest_note(freq);
+        let cents = temperament.cents_from_target(freq, screen.target_freq());
+        screen.update(freq, cents);
+        screen.set_heard_note(crate::tuning::Note::from_midi(midi).unwrap().display_name());
+    }
+
+    #[test]
+    fn test_detection_states() {
+        let mut screen = TuningScreen::new("A4", 0, 88, 440.0, 3, 69);
+        assert_eq!(screen.detection(), Detection::Listening);
+
+        hear(&mut screen, 261.63);
+        assert_eq!(
+            screen.detection(),
+            Detection::WrongNote {
+                heard: "C4".to_string()
+            }
+        );
+        assert!(render_text(&screen).contains("I hear C4 but we're tuning A4 - play A4"));
+
+        hear(&mut screen, 443.0);
+        assert_eq!(screen.detection(), Detection::OnNote);
+
+        screen.clear();
+        assert_eq!(screen.detection(), Detection::Listening);
+    }
+
+    #[test]
+    fn test_warming_up_hides_reading() {
+        let mut screen = TuningScreen::new("A1", 0, 88, 55.0, 1, 33);
+        screen.set_warming_up(true);
+        assert_eq!(screen.detection(), Detection::WarmingUp);
+        assert!(render_text(&screen).contains("Warming up..."));
+
+        screen.set_warming_up(false);
+        assert_eq!(screen.detection(), Detection::Listening);
+        assert!(render_text(&screen).contains("Listening..."));
+    }
+
+    #[test]
+    fn test_hold_keeps_last_reading_through_silence() {
+        let mut screen = TuningScreen::new("A1", 0, 88, 55.0, 1, 33);
+        screen.toggle_hold();
+        assert!(screen.is_holding());
+
+        screen.update(55.2, 6.3);
+        assert_eq!(screen.held_reading(), None);
+
+        // The note decays
+        screen.clear();
+        assert_eq!(screen.detection(), Detection::Listening);
+        assert_eq!(screen.held_reading(), Some((55.2, 6.3)));
+        assert!(render_text(&screen).contains("Held: 55.20 Hz (+6.3 cents)"));
+
+        // The next strike replaces it
+        screen.update(55.0, 0.1);
+        screen.clear();
+        assert_eq!(screen.held_reading(), Some((55.0, 0.1)));
+    }
+
+    #[test]
+    fn test_hold_off_clears_to_listening() {
+        let mut screen = TuningScreen::new("A1", 0, 88, 55.0, 1, 33);
+        screen.update(55.2, 6.3);
+        screen.clear();
+        assert_eq!(screen.held_reading(), None);
+        assert!(render_text(&screen).contains("Listening..."));
+    }
+
+    #[test]
+    fn test_new_note_drops_held_reading() {
+        let mut screen = TuningScreen::new("A1", 0, 88, 55.0, 1, 33);
+        screen.toggle_hold();
+        screen.update(55.2, 6.3);
+        screen.load_note("A#1", 1, 58.27, 1, 34);
+
+        assert!(screen.is_holding());
+        assert_eq!(screen.held_reading(), None);
+    }
+
+    #[test]
+    fn test_false_beat_warning_on_single_string_only() {
+        let mut mono = TuningScreen::new("A1", 0, 88, 55.0, 1, 33);
+        mono.update(55.0, 0.0);
+        mono.set_false_beats(true);
+        assert!(mono.false_beat_warning());
+        assert!(render_text(&mono).contains("String beats on its own"));
+
+        mono.clear();
+        assert!(!mono.false_beat_warning());
+
+        // Trichord: only while the center string sounds alone
+        let mut tri = TuningScreen::new("A4", 0, 88, 440.0, 3, 69);
+        tri.next_step();
+        assert_eq!(tri.tuning_step(), Some(TuningStep::TuneCenter));
+        tri.set_false_beats(true);
+        assert!(tri.false_beat_warning());
+
+        tri.next_step();
+        assert!(!tri.false_beat_warning());
+    }
+
+    #[test]
+    fn test_slipped_string_still_on_note() {
+        // 60 cents flat is nearer G#4, but still coached as A4
+        let mut screen = TuningScreen::new("A4", 0, 88, 440.0, 3, 69);
+        hear(&mut screen, 425.0);
+        assert_eq!(screen.detection(), Detection::OnNote);
+    }
+
+    #[test]
+    fn test_update_ignores_glitches() {
+        let mut screen = TuningScreen::new("A4", 0, 88, 440.0, 3, 69);
+        screen.update(441.0, 3.9);
+
+        screen.update(440.0, f32::NAN);
+        screen.update(440.0, f32::INFINITY);
+        screen.update(0.001, -50000.0);
+        screen.update(f32::NAN, 0.0);
+
+        assert_eq!(screen.cents(), 3.9);
+        assert_eq!(screen.detected_freq(), Some(441.0));
+    }
+
+    #[test]
+    fn test_update_ignores_glitch_before_any_reading() {
+        let mut screen = TuningScreen::new("A4", 0, 88, 440.0, 3, 69);
+        screen.update(440.0, f32::NEG_INFINITY);
+        assert_eq!(screen.detected_freq(), None);
+        assert_eq!(screen.detection(), Detection::Listening);
+    }
+
+    #[test]
+    fn test_order_view_marks_next_key() {
+        use crate::ui::components::piano::chars;
+
+        let mut screen = TuningScreen::new("F3", 0, 88, 174.61, 3, 53);
+        screen.set_next_key(Some(33));
+        assert!(!render_text(&screen).contains(chars::NEXT));
+
+        screen.set_keyboard_view(KeyboardView::Order);
+        assert!(render_text(&screen).contains(chars::NEXT));
+    }
+
+    #[test]
+    fn test_load_note_changes_string_count() {
+        let mut screen = TuningScreen::new("C4", 7, 88, 261.63, 3, 60);
+        screen.load_note("A1", 8, 55.0, 1, 33);
+
+        assert!(!screen.is_multi_string());
+        assert_eq!(screen.tuning_step(), None);
+    }
+
+    #[test]
+    fn test_load_note_keeps_display_state() {
+        let mut screen = TuningScreen::new("F3", 0, 88, 174.61, 3, 53);
+        screen.toggle_piano_progress();
+        screen.set_completed_notes([32].into_iter().collect());
+        screen.set_starting_deviations([(33, 12.0)].into_iter().collect());
+
+        screen.load_note("F#3", 1, 185.0, 3, 54);
+
+        assert!(screen.show_piano_progress);
+        assert!(screen.completed_notes.contains(&32));
+        assert_eq!(screen.starting_cents(), Some(12.0));
+    }
+}
@@ -91,6 +91,8 @@ pub struct BoxChars;
 impl BoxChars {
     /// Vertical bar characters for different fill levels (1/8 to 8/8).
     pub const BLOCKS: [char; 8] = ['▏', '▎', '▍', '▌', '▋', '▊', '▉', '█'];
+    /// Block shades from faint to solid.
+    pub const SHADES: [char; 3] = ['▒', '▓', '█'];
     /// Thin vertical line.
     pub const THIN_VERTICAL: char = '┊';
     /// Thick vertical line (center).
@@ -103,6 +105,14 @@ impl BoxChars {
     pub const LEFT_ARROW: char = '◀';
     /// Right arrow.
     pub const RIGHT_ARROW: char = '▶';
+    /// Step reached.
+    pub const DOT_FILLED: char = '●';
+    /// Step still to come.
+    pub const DOT_EMPTY: char = '○';
+    /// Faint marker where a note sat before.
+    pub const GHOST: char = '░';
+    /// Rotating glyphs for the listening animation.
+    pub const SPINNER: [char; 4] = ['◐', '◓', '◑', '◒'];
 
     /// Get block character for fill level (0.0 to 1.0).
     pub fn block_for_fill(fill: f32) -> char {
@@ -126,8 +136,24 @@ impl Shortcuts {
     pub const BACK: &'static str = "[B]";
     /// P key hint.
     pub const PIANO: &'static str = "[P]";
+    /// R key hint.
+    pub const REFERENCE: &'static str = "[R]";
+    /// L key hint.
+    pub const LEAVE: &'static str = "[L]";
+    /// A key hint.
+    pub const AB: &'static str = "[A]";
+    /// H key hint.
+    pub const HOLD: &'static str = "[H]";
+    /// N key hint.
+    pub const NEXT: &'static str = "[N]";
+    /// F key hint.
+    pub const FINISH: &'static str = "[F]";
+    /// C key hint.
+    pub const COMMENT: &'static str = "[C]";
     /// Enter key hint.
     pub const ENTER: &'static str = "[Enter]";
+    /// Left/Right arrows hint.
+    pub const LEFT_RIGHT: &'static str = "[←/→]";
     /// Up/Down arrows hint.
     pub const ARROWS: &'static str = "[↑/↓]";
 
@@ -0,0 +1,1352 @@
+//! End-to-end tuning loop: drives `App` through a session with synthesized audio.
+
+use std::sync::OnceLock;
+
+use crossterm::event::KeyCode;
+use tempfile::TempDir;
+
+use pianito::audio::gate::rms;
+use pianito::audio::smoother::DEFAULT_WINDOW as SMOOTHING_WINDOW;
+use pianito::audio::{AudioSource, PitchDetector, ResponsePreset, TestAudioSource};
+use pianito::tuning::session::NoteStatus;
+use pianito::tuning::storage::DATA_DIR_ENV;
+use pianito::tuning::{
+    Note, NoteSpelling, Session, SessionEventKind, StretchCurve, Temperament, TuningMode,
+    NOTE_COUNT,
+};
+use pianito::ui::app::{AppState, ConfirmAction};
+use pianito::ui::App;
+
+const SAMPLE_RATE: u32 = 44100;
+
+/// Number of notes tuned by ear before skipping the rest.
+const TUNED_NOTES: usize = 5;
+
+/// Keep sessions and profiles saved by tests out of the real data directory.
+fn isolate_data_dir() {
+    static DIR: OnceLock<TempDir> = OnceLock::new();
+    DIR.get_or_init(|| {
+        let dir = TempDir::new().expect("Should create temp dir");
+        std::env::set_var(DATA_DIR_ENV, dir.path());
+        dir
+    });
+}
+
+/// Run one 100ms buffer of a sine at `freq` through detection into the app.
+fn feed_tone(app: &mut App, detector: &PitchDetector, freq: f32) {
+    let mut source = TestAudioSource::sine(freq, 0.1, SAMPLE_RATE);
+    let mut buffer = vec![0.0f32; SAMPLE_RATE as usize / 10];
+    let read = source.read_samples(&mut buffer);
+
+    match detector.detect(&buffer[..read]) {
+        Some(result) => app.update_pitch(result.frequency, result.confidence),
+        None => app.clear_pitch(),
+    }
+}
+
+/// Converge the simulated string onto the target, then confirm every step.
+fn tune_current_note(app: &mut App, detector: &PitchDetector, start_cents: f32) {
+    let target = app.current_target_freq().expect("Should have a target");
+    let temperament = Temperament::new();
+
+    for cents in [start_cents, start_cents / 2.0, start_cents / 4.0] {
+        feed_tone(app, detector, temperament.cents_to_frequency(target, cents));
+    }
+
+    // Let the string settle long enough for smoothing to catch up
+    for _ in 0..SMOOTHING_WINDOW {
+        feed_tone(app, detector, temperament.cents_to_frequency(target, 0.5));
+    }
+
+    let cents = app.tuning().expect("Should be tuning").cents();
+    assert!(
+        cents.abs() < 1.5,
+        "Should converge near target, got {cents}"
+    );
+
+    let steps = app
+        .tuning()
+        .and_then(|t| t.tuning_step())
+        .map(|s| s.total_steps())
+        .unwrap_or(1);
+    for _ in 0..steps {
+        app.handle_key(KeyCode::Char(' '));
+    }
+}
+
+#[test]
+fn test_concert_session_runs_to_complete() {
+    isolate_data_dir();
+    let detector = PitchDetector::new(SAMPLE_RATE);
+    let mut app = App::new();
+    assert_eq!(app.state(), AppState::ModeSelect);
+
+    // Quick Tune is selected first; move to Concert Pitch
+    app.handle_key(KeyCode::Down);
+    app.handle_key(KeyCode::Enter);
+    assert_eq!(app.state(), AppState::Tuning);
+
+    for i in 0..TUNED_NOTES {
+        let start_cents = if i % 2 == 0 { 30.0 } else { -30.0 };
+        tune_current_note(&mut app, &detector, start_cents);
+        assert_eq!(app.session().unwrap().completed_notes.len(), i + 1);
+    }
+
+    // Skip the rest of the piano, and the temperament check on the way
+    while matches!(app.state(), AppState::Tuning | AppState::IntervalCheck) {
+        app.handle_key(KeyCode::Char('s'));
+    }
+
+    assert_eq!(app.state(), AppState::Complete);
+    let complete = app.complete().expect("Should have a complete screen");
+    assert_eq!(complete.note_count(), NOTE_COUNT);
+
+    let names: Vec<&str> = complete.completed_notes()[..TUNED_NOTES]
+        .iter()
+        .map(|n| n.note.as_str())
+        .collect();
+    assert_eq!(names, ["F3", "F#3", "G3", "G#3", "A3"]);
+
+    for note in &complete.completed_notes()[..TUNED_NOTES] {
+        assert!(
+            note.final_cents.abs() < 1.5,
+            "{} should be tuned, got {} cents",
+            note.note,
+            note.final_cents
+        );
+    }
+}
+
+#[test]
+fn test_finish_early_leaves_remaining_untuned() {
+    isolate_data_dir();
+    let detector = PitchDetector::new(SAMPLE_RATE);
+    let mut app = App::new();
+    app.handle_key(KeyCode::Down);
+    app.handle_key(KeyCode::Enter);
+
+    tune_current_note(&mut app, &detector, 20.0);
+    tune_current_note(&mut app, &detector, -20.0);
+    app.handle_key(KeyCode::Char('f'));
+
+    assert_eq!(app.state(), AppState::Complete);
+    let complete = app.complete().expect("Should have a complete screen");
+    assert_eq!(complete.note_count(), 2);
+    assert_eq!(complete.untuned_count(), NOTE_COUNT - 2);
+}
+
+#[test]
+fn test_temperament_start_orders_and_resumes_from_a3() {
+    isolate_data_dir();
+    let detector = PitchDetector::new(SAMPLE_RATE);
+    let a3 = Note::from_name("A3").unwrap();
+    let mut app = App::new().with_temperament_start(a3.midi);
+    app.handle_key(KeyCode::Down);
+    app.handle_key(KeyCode::Enter);
+
+    tune_current_note(&mut app, &detector, 20.0);
+    tune_current_note(&mut app, &detector, -20.0);
+    let session = app.session().expect("Should have a session").clone();
+    let names: Vec<&str> = session
+        .completed_notes
+        .iter()
+        .map(|n| n.note.as_str())
+        .collect();
+    assert_eq!(names, ["A3", "A#3"]);
+    assert_eq!(session.temperament_start, Some(a3.midi));
+
+    // A resumed session keeps its start, even against another setting
+    let b3 = Note::from_name("B3").unwrap();
+    let resumed = App::with_session(session).with_temperament_start(53);
+    let target = resumed.current_target_freq().expect("Should have a target");
+    assert!((target - Temperament::new().frequency(b3.midi)).abs() < 0.01);
+}
+
+#[test]
+fn test_leave_octave_marks_rest_of_octave() {
+    isolate_data_dir();
+    let detector = PitchDetector::new(SAMPLE_RATE);
+    let mut app = App::new();
+    app.handle_key(KeyCode::Down);
+    app.handle_key(KeyCode::Enter);
+
+    tune_current_note(&mut app, &detector, 20.0);
+    app.handle_key(KeyCode::Char('o'));
+
+    // The rest of octave 3 is left as is, bass included; tuning moves on to C4
+    let session = app.session().expect("Should have a session");
+    assert_eq!(session.completed_notes.len(), 12);
+    assert_eq!(session.current_note_index, 7);
+    assert_eq!(session.completed_notes[0].status, NoteStatus::Tuned);
+    let left: Vec<&str> = session.completed_notes[1..]
+        .iter()
+        .filter(|n| n.status == NoteStatus::LeftAsIs)
+        .map(|n| n.note.as_str())
+        .collect();
+    assert_eq!(
+        left,
+        ["F#3", "G3", "G#3", "A3", "A#3", "B3", "E3", "D#3", "D3", "C#3", "C3"]
+    );
+
+    let c4 = Note::from_name("C4").unwrap();
+    let target = app.current_target_freq().expect("Should have a target");
+    assert!((target - Temperament::new().frequency(c4.midi)).abs() < 0.01);
+
+    // Skipping on down to B2 never brings octave 3 back up
+    while app.tuning().is_some_and(|t| t.note_name() != "B2") {
+        app.handle_key(KeyCode::Char('s'));
+    }
+    let session = app.session().unwrap();
+    assert_eq!(
+        session
+            .completed_notes
+            .iter()
+            .filter(|n| n.note == "E3")
+            .count(),
+        1
+    );
+}
+
+#[test]
+fn test_leave_octave_covers_scattered_profile_order() {
+    isolate_data_dir();
+    let detector = PitchDetector::new(SAMPLE_RATE);
+    let temperament = Temperament::new();
+    let mut app = App::new();
+
+    // Quick Tune -> Concert Pitch -> Profile
+    app.handle_key(KeyCode::Down);
+    app.handle_key(KeyCode::Down);
+    app.handle_key(KeyCode::Enter);
+
+    // Profile A4 +20, B4 +10 and C5 +15 so octave 4 is split up in the order
+    for _ in 0..48 {
+        app.handle_key(KeyCode::Char('s'));
+    }
+    for (hz, cents, skip_after) in [(440.0, 20.0, 1), (493.88, 10.0, 0), (523.25, 15.0, 0)] {
+        feed_tone(
+            &mut app,
+            &detector,
+            temperament.cents_to_frequency(hz, cents),
+        );
+        app.handle_key(KeyCode::Char(' '));
+        for _ in 0..skip_after {
+            app.handle_key(KeyCode::Char('s'));
+        }
+    }
+    while app.state() == AppState::Profiling {
+        app.handle_key(KeyCode::Char('s'));
+    }
+
+    // Skip the temperament octave; A4, C5 and B4 come next
+    for _ in 0..13 {
+        app.handle_key(KeyCode::Char('s'));
+    }
+    assert_eq!(app.tuning().unwrap().note_name(), "A4");
+    app.handle_key(KeyCode::Char('o'));
+
+    let session = app.session().unwrap();
+    assert_eq!(session.status_for("B4"), Some(NoteStatus::LeftAsIs));
+    assert_eq!(
+        session.final_cents_for("B4"),
+        session.starting_cents_for("B4")
+    );
+    // Notes already skipped keep their result
+    assert_eq!(session.status_for("F4"), Some(NoteStatus::Skipped));
+    assert_eq!(app.tuning().unwrap().note_name(), "C5");
+
+    // B4 comes up next in the order but is passed over
+    let recorded = session.completed_notes.len();
+    app.handle_key(KeyCode::Char('s'));
+    assert_eq!(app.tuning().unwrap().note_name(), "A0");
+    assert_eq!(app.session().unwrap().completed_notes.len(), recorded + 1);
+}
+
+#[test]
+fn test_profile_seeds_starting_deviation() {
+    isolate_data_dir();
+    let detector = PitchDetector::new(SAMPLE_RATE);
+    let temperament = Temperament::new();
+    let mut app = App::new();
+
+    // Quick Tune -> Concert Pitch -> Profile
+    app.handle_key(KeyCode::Down);
+    app.handle_key(KeyCode::Down);
+    app.handle_key(KeyCode::Enter);
+    assert_eq!(app.state(), AppState::Profiling);
+
+    // Skip A0..G#4, measure A4 sharp, skip the rest
+    for _ in 0..48 {
+        app.handle_key(KeyCode::Char('s'));
+    }
+    feed_tone(
+        &mut app,
+        &detector,
+        temperament.cents_to_frequency(440.0, 20.0),
+    );
+    app.handle_key(KeyCode::Char(' '));
+    while app.state() == AppState::Profiling {
+        app.handle_key(KeyCode::Char('s'));
+    }
+    assert_eq!(app.state(), AppState::Tuning);
+
+    let start = app
+        .session()
+        .and_then(|s| s.starting_cents_for("A4"))
+        .expect("A4 should carry its profiled deviation");
+    assert!((start - 20.0).abs() < 1.5, "got {start}");
+
+    // Unprofiled notes show no starting point
+    assert_eq!(app.tuning().and_then(|t| t.starting_cents()), None);
+
+    // A4 is the worst note outside the temperament octave, so it comes right after
+    for _ in 0..13 {
+        app.handle_key(KeyCode::Char('s'));
+    }
+    assert_eq!(app.current_target_freq(), Some(440.0));
+    let shown = app.tuning().and_then(|t| t.starting_cents());
+    assert_eq!(shown, Some(start));
+}
+
+#[test]
+fn test_autosave_every_n_notes() {
+    isolate_data_dir();
+    let mut app = App::new().with_autosave_every(3);
+    app.handle_key(KeyCode::Down);
+    app.handle_key(KeyCode::Enter);
+
+    for _ in 0..7 {
+        app.handle_key(KeyCode::Char('s'));
+    }
+    assert_eq!(app.save_count(), 2);
+
+    // Quitting saves whatever is pending
+    app.handle_key(KeyCode::Char('q'));
+    assert_eq!(app.save_count(), 3);
+    assert!(app.should_quit());
+}
+
+#[test]
+fn test_single_note_tunes_then_quits() {
+    isolate_data_dir();
+    let detector = PitchDetector::new(SAMPLE_RATE);
+    let note = Note::from_name("A4").expect("A4 exists");
+    let mut app = App::single_note(note, 442.0);
+
+    assert_eq!(app.state(), AppState::Tuning);
+    assert_eq!(app.current_target_freq(), Some(442.0));
+    assert!(app.session().is_none());
+
+    tune_current_note(&mut app, &detector, 15.0);
+    assert!(app.should_quit());
+}
+
+/// Hold a steady, in-tune A4 long enough for the reading to lock.
+fn lock_on_a4(app: &mut App) {
+    let detector = PitchDetector::new(SAMPLE_RATE);
+    for _ in 0..SMOOTHING_WINDOW {
+        feed_tone(app, &detector, 440.0);
+    }
+}
+
+#[test]
+fn test_beep_on_lock_queues_one_tone() {
+    isolate_data_dir();
+    let note = Note::from_name("A4").expect("A4 exists");
+    let mut app = App::single_note(note, 440.0).with_beep(true);
+
+    lock_on_a4(&mut app);
+    assert_eq!(app.take_tone_requests().len(), 1);
+
+    // Staying locked doesn't beep again
+    lock_on_a4(&mut app);
+    assert!(app.take_tone_requests().is_empty());
+}
+
+#[test]
+fn test_quiet_mode_queues_no_tones() {
+    isolate_data_dir();
+    let note = Note::from_name("A4").expect("A4 exists");
+    let mut app = App::single_note(note, 440.0)
+        .with_beep(true)
+        .with_quiet(true);
+
+    lock_on_a4(&mut app);
+    assert!(app.take_tone_requests().is_empty());
+
+    app.handle_key(KeyCode::Char('r'));
+    assert_eq!(app.looping_tone(), None);
+}
+
+#[test]
+fn test_fanfare_on_complete_unless_quiet() {
+    isolate_data_dir();
+    for quiet in [false, true] {
+        let mut app = App::new().with_quiet(quiet);
+        app.handle_key(KeyCode::Down);
+        app.handle_key(KeyCode::Enter);
+        app.handle_key(KeyCode::Char('f'));
+
+        assert_eq!(app.state(), AppState::Complete);
+        assert_eq!(app.take_tone_requests().is_empty(), quiet);
+    }
+}
+
+/// Hold the current note at `cents` from target, then confirm every step.
+fn record_current_note(app: &mut App, detector: &PitchDetector, cents: f32) -> String {
+    let target = app.current_target_freq().expect("Should have a target");
+    let freq = Temperament::new().cents_to_frequency(target, cents);
+    for _ in 0..SMOOTHING_WINDOW {
+        feed_tone(app, detector, freq);
+    }
+
+    let tuning = app.tuning().expect("Should be tuning");
+    let name = tuning.note_name().to_string();
+    let steps = tuning.tuning_step().map(|s| s.total_steps()).unwrap_or(1);
+    for _ in 0..steps {
+        app.handle_key(KeyCode::Char(' '));
+    }
+    name
+}
+
+#[test]
+fn test_jump_to_out_of_tolerance_notes() {
+    isolate_data_dir();
+    let detector = PitchDetector::new(SAMPLE_RATE);
+    let mut app = App::new();
+    app.handle_key(KeyCode::Down);
+    app.handle_key(KeyCode::Enter);
+
+    let sharp = record_current_note(&mut app, &detector, 12.0);
+    record_current_note(&mut app, &detector, 0.0);
+    let flat = record_current_note(&mut app, &detector, -10.0);
+    app.handle_key(KeyCode::Char('f'));
+    assert_eq!(app.state(), AppState::Complete);
+
+    // Visits both in order, then wraps
+    app.handle_key(KeyCode::Char('n'));
+    assert_eq!(app.state(), AppState::Tuning);
+    assert_eq!(app.tuning().unwrap().note_name(), sharp);
+    app.handle_key(KeyCode::Char('n'));
+    assert_eq!(app.tuning().unwrap().note_name(), flat);
+    app.handle_key(KeyCode::Char('n'));
+    assert_eq!(app.tuning().unwrap().note_name(), sharp);
+
+    // Fixing a revisited note goes back to the complete screen, replacing
+    // its result rather than adding another
+    record_current_note(&mut app, &detector, 0.5);
+    assert_eq!(app.state(), AppState::Complete);
+    let session = app.session().unwrap();
+    assert_eq!(session.completed_notes.len(), 3);
+    assert!(session.is_complete());
+
+    assert!(app.jump_to_out_of_tolerance());
+    assert_eq!(app.tuning().unwrap().note_name(), flat);
+    record_current_note(&mut app, &detector, -0.5);
+    assert_eq!(app.state(), AppState::Complete);
+    assert_eq!(app.session().unwrap().completed_notes.len(), 3);
+    assert!(!app.jump_to_out_of_tolerance());
+}
+
+#[test]
+fn test_revisit_mid_session_returns_to_current_note() {
+    isolate_data_dir();
+    let detector = PitchDetector::new(SAMPLE_RATE);
+    let mut app = App::new();
+    app.handle_key(KeyCode::Down);
+    app.handle_key(KeyCode::Enter);
+
+    let sharp = record_current_note(&mut app, &detector, 12.0);
+    record_current_note(&mut app, &detector, 0.0);
+    let here = app.tuning().unwrap().note_name().to_string();
+
+    app.handle_key(KeyCode::Char('n'));
+    assert_eq!(app.tuning().unwrap().note_name(), sharp);
+    record_current_note(&mut app, &detector, 0.5);
+
+    assert_eq!(app.tuning().unwrap().note_name(), here);
+    let session = app.session().unwrap();
+    assert_eq!(session.completed_notes.len(), 2);
+    assert_eq!(session.current_note_index, 2);
+}
+
+#[test]
+fn test_response_preset_holds_through_dropouts() {
+    isolate_data_dir();
+    let note = Note::from_name("A4").expect("A4 exists");
+
+    let mut stable = App::single_note(note, 440.0).with_response(ResponsePreset::Stable);
+    assert_eq!(stable.smoothing_window(), 9);
+    lock_on_a4(&mut stable);
+    for _ in 0..2 {
+        stable.update_input_level(0.0, 0.2);
+        stable.clear_pitch();
+    }
+    assert!(stable.tuning().unwrap().detected_freq().is_some());
+    stable.update_input_level(0.0, 0.2);
+    stable.clear_pitch();
+    assert!(stable.tuning().unwrap().detected_freq().is_none());
+
+    // The hold is time, not a count: one long gap clears at once
+    lock_on_a4(&mut stable);
+    stable.update_input_level(0.0, 0.6);
+    stable.clear_pitch();
+    assert!(stable.tuning().unwrap().detected_freq().is_none());
+
+    let mut fast = App::single_note(note, 440.0).with_response(ResponsePreset::Fast);
+    lock_on_a4(&mut fast);
+    fast.update_input_level(0.0, 0.1);
+    fast.clear_pitch();
+    assert!(fast.tuning().unwrap().detected_freq().is_none());
+}
+
+#[test]
+fn test_low_confidence_below_preset_is_ignored() {
+    isolate_data_dir();
+    let note = Note::from_name("A4").expect("A4 exists");
+    let mut app = App::single_note(note, 440.0).with_response(ResponsePreset::Fast);
+
+    app.update_pitch(440.0, 0.55);
+    assert!(app.tuning().unwrap().detected_freq().is_some());
+
+    let mut app = App::single_note(note, 440.0).with_response(ResponsePreset::Stable);
+    app.update_pitch(440.0, 0.55);
+    assert!(app.tuning().unwrap().detected_freq().is_none());
+}
+
+#[test]
+fn test_unsure_reading_shows_as_raw_estimate() {
+    isolate_data_dir();
+    let note = Note::from_name("A4").expect("A4 exists");
+    let mut app = App::single_note(note, 440.0);
+
+    // Too unsure to coach on, but shown as a tagged guess
+    app.update_pitch(452.0, 0.3);
+    let tuning = app.tuning().unwrap();
+    assert!(tuning.detected_freq().is_none());
+    assert_eq!(tuning.raw_estimate(), Some(452.0));
+
+    // A trusted reading replaces it, and silence clears it
+    app.update_pitch(440.5, 0.95);
+    assert_eq!(app.tuning().unwrap().raw_estimate(), None);
+    app.update_pitch(452.0, 0.3);
+    app.clear_pitch();
+    assert_eq!(app.tuning().unwrap().raw_estimate(), None);
+}
+
+#[test]
+fn test_temperament_practice_loops_and_scores() {
+    isolate_data_dir();
+    let detector = PitchDetector::new(SAMPLE_RATE);
+    let mut app = App::temperament_practice(440.0);
+    assert_eq!(app.state(), AppState::Tuning);
+
+    for _ in 0..13 {
+        tune_current_note(&mut app, &detector, 20.0);
+    }
+
+    // Back at F3 for another pass rather than the complete screen
+    assert_eq!(app.state(), AppState::Tuning);
+    assert_eq!(app.tuning().unwrap().note_name(), "F3");
+    assert!(app.session().is_none());
+
+    let practice = app.practice().expect("Should be practising");
+    assert_eq!(practice.passes(), 1);
+    let report = practice.report();
+    assert_eq!(report.len(), 3);
+    for accuracy in report {
+        assert!(accuracy.average_error_bps < 1.0, "{accuracy:?}");
+    }
+
+    app.handle_key(KeyCode::Char('f'));
+    assert!(app.should_quit());
+}
+
+/// Start a concert session with the given confirm behaviour.
+fn concert_app(action: ConfirmAction) -> App {
+    let mut app = App::new().with_confirm_action(action);
+    app.handle_key(KeyCode::Down);
+    app.handle_key(KeyCode::Enter);
+    app
+}
+
+#[test]
+fn test_confirm_advances_by_default() {
+    isolate_data_dir();
+    let detector = PitchDetector::new(SAMPLE_RATE);
+    let mut app = concert_app(ConfirmAction::Advance);
+
+    tune_current_note(&mut app, &detector, 10.0);
+
+    let session = app.session().unwrap();
+    assert_eq!(session.completed_notes.len(), 1);
+    assert_eq!(session.current_note_index, 1);
+    assert_eq!(app.tuning().unwrap().note_name(), "F#3");
+}
+
+#[test]
+fn test_confirm_can_stay_on_note() {
+    isolate_data_dir();
+    let detector = PitchDetector::new(SAMPLE_RATE);
+    let mut app = concert_app(ConfirmAction::Stay);
+
+    tune_current_note(&mut app, &detector, 10.0);
+
+    let session = app.session().unwrap();
+    assert_eq!(session.completed_notes.len(), 1);
+    assert_eq!(session.current_note_index, 0);
+    assert_eq!(app.tuning().unwrap().note_name(), "F3");
+
+    // Confirming again replaces the result rather than adding another
+    app.handle_key(KeyCode::Char(' '));
+    let session = app.session().unwrap();
+    assert_eq!(session.completed_notes.len(), 1);
+    let confirms = session
+        .events
+        .iter()
+        .filter(|e| matches!(e.kind, SessionEventKind::NoteConfirmed { .. }))
+        .count();
+    assert_eq!(confirms, 1);
+
+    app.handle_key(KeyCode::Enter);
+    assert_eq!(app.session().unwrap().current_note_index, 1);
+    assert_eq!(app.tuning().unwrap().note_name(), "F#3");
+}
+
+#[test]
+fn test_skip_after_staying_keeps_the_confirm() {
+    isolate_data_dir();
+    let detector = PitchDetector::new(SAMPLE_RATE);
+    let mut app = concert_app(ConfirmAction::Stay);
+
+    tune_current_note(&mut app, &detector, 10.0);
+    app.handle_key(KeyCode::Char('s'));
+
+    let session = app.session().unwrap();
+    assert_eq!(session.completed_notes.len(), 1);
+    assert_eq!(session.completed_notes[0].status, NoteStatus::Tuned);
+    assert_eq!(session.current_note_index, 1);
+    assert_eq!(app.tuning().unwrap().note_name(), "F#3");
+}
+
+/// A single string swelling and fading twice a second.
+fn beating_tone(freq: f32, secs: f32) -> Vec<f32> {
+    let source = TestAudioSource::sine(freq, secs, SAMPLE_RATE);
+    source
+        .samples()
+        .iter()
+        .enumerate()
+        .map(|(i, s)| {
+            let t = i as f32 / SAMPLE_RATE as f32;
+            s * (0.7 + 0.3 * (std::f32::consts::TAU * 2.0 * t).cos())
+        })
+        .collect()
+}
+
+#[test]
+fn test_false_beats_show_after_two_seconds_of_buffers() {
+    isolate_data_dir();
+    // A single-string bass note, where the warning shows
+    let note = Note::from_name("A1").expect("A1 exists");
+    let mut app = App::single_note(note, 440.0);
+    assert_eq!(app.tuning().unwrap().tuning_step(), None);
+    let detector = PitchDetector::new(SAMPLE_RATE);
+
+    // One string swelling and fading twice a second, read 100ms at a time
+    let beating = beating_tone(55.0, 3.0);
+    let mut warnings = Vec::new();
+    for buffer in beating.chunks(SAMPLE_RATE as usize / 10) {
+        app.update_input_level(rms(buffer), 0.1);
+        let result = detector.detect(buffer).expect("Should detect pitch");
+        app.update_pitch(result.frequency, result.confidence);
+        app.update_false_beats(buffer, buffer.len(), SAMPLE_RATE, true);
+        warnings.push(app.tuning().unwrap().false_beat_warning());
+    }
+    assert!(
+        !warnings[..10].iter().any(|&w| w),
+        "judged before two seconds"
+    );
+    assert!(
+        warnings.last().copied().unwrap(),
+        "2 Hz beat should be flagged"
+    );
+
+    // A fresh strike starts a fresh window
+    let steady = TestAudioSource::sine(55.0, 0.1, SAMPLE_RATE);
+    app.update_input_level(0.0, 0.1);
+    app.update_input_level(rms(steady.samples()), 0.1);
+    app.update_false_beats(steady.samples(), steady.samples().len(), SAMPLE_RATE, true);
+    assert!(!app.tuning().unwrap().false_beat_warning());
+}
+
+#[test]
+fn test_false_beats_wait_for_two_seconds_of_sliding_reads() {
+    isolate_data_dir();
+    let note = Note::from_name("A1").expect("A1 exists");
+    let mut app = App::single_note(note, 440.0);
+    let beating = beating_tone(55.0, 3.0);
+
+    // Like the microphone loop: a 100ms window read every 50ms, so each
+    // read repeats half of the one before
+    let window = SAMPLE_RATE as usize / 10;
+    let step = SAMPLE_RATE as usize / 20;
+    let mut first_warning = None;
+    for end in (window..=beating.len()).step_by(step) {
+        let read = &beating[end - window..end];
+        let fresh = if end == window { window } else { step };
+        app.update_input_level(rms(read), 0.05);
+        app.update_pitch(55.0, 0.9);
+        app.update_false_beats(read, fresh, SAMPLE_RATE, true);
+        if first_warning.is_none() && app.tuning().unwrap().false_beat_warning() {
+            first_warning = Some(end as f32 / SAMPLE_RATE as f32);
+        }
+    }
+
+    let heard = first_warning.expect("2 Hz beat should be flagged");
+    assert!(heard >= 2.0, "flagged after only {heard:.2}s of audio");
+}
+
+#[test]
+fn test_attack_confirm_uses_pitch_after_strike() {
+    isolate_data_dir();
+    let detector = PitchDetector::new(SAMPLE_RATE);
+    let mut app = concert_app(ConfirmAction::Advance).with_attack_confirm(true);
+    let target = app.current_target_freq().unwrap();
+    let temperament = Temperament::new();
+
+    // A loud strike reading +2 cents, then a decaying tail drifting sharp
+    for _ in 0..4 {
+        app.update_input_level(0.5, 0.1);
+        feed_tone(
+            &mut app,
+            &detector,
+            temperament.cents_to_frequency(target, 2.0),
+        );
+    }
+    for _ in 0..SMOOTHING_WINDOW + 3 {
+        app.update_input_level(0.2, 0.1);
+        feed_tone(
+            &mut app,
+            &detector,
+            temperament.cents_to_frequency(target, 12.0),
+        );
+    }
+    assert!(app.tuning().unwrap().cents() > 10.0);
+
+    let steps = app
+        .tuning()
+        .unwrap()
+        .tuning_step()
+        .map_or(1, |s| s.total_steps());
+    for _ in 0..steps {
+        app.handle_key(KeyCode::Char(' '));
+    }
+
+    let recorded = app.session().unwrap().completed_notes[0].final_cents;
+    assert!((recorded - 2.0).abs() < 1.0, "recorded {recorded:.2} cents");
+}
+
+#[test]
+fn test_attack_confirm_sets_relative_neighbor_from_attack() {
+    isolate_data_dir();
+    let detector = PitchDetector::new(SAMPLE_RATE);
+    let temperament = Temperament::new();
+    let mut app = App::new().with_attack_confirm(true);
+    for _ in 0..4 {
+        app.handle_key(KeyCode::Down);
+    }
+    app.handle_key(KeyCode::Enter);
+    let f3 = temperament.frequency(53);
+
+    // Struck at +2 cents, decaying sharp to +12
+    for _ in 0..4 {
+        app.update_input_level(0.5, 0.1);
+        feed_tone(&mut app, &detector, temperament.cents_to_frequency(f3, 2.0));
+    }
+    for _ in 0..SMOOTHING_WINDOW + 3 {
+        app.update_input_level(0.2, 0.1);
+        feed_tone(
+            &mut app,
+            &detector,
+            temperament.cents_to_frequency(f3, 12.0),
+        );
+    }
+    while app.session().unwrap().completed_notes.is_empty() {
+        app.handle_key(KeyCode::Char(' '));
+    }
+
+    // F#3 is a semitone above where the strike read, not the tail
+    let fs3 = app.current_target_freq().expect("F#3 target");
+    let cents = temperament.cents_from_target(fs3, temperament.frequency(54));
+    assert!((cents - 2.0).abs() < 1.0, "F#3 target at {cents:.2} cents");
+}
+
+#[test]
+fn test_changing_a4_retargets_remaining_notes() {
+    isolate_data_dir();
+    let detector = PitchDetector::new(SAMPLE_RATE);
+    let mut app = concert_app(ConfirmAction::Advance);
+    tune_current_note(&mut app, &detector, 10.0);
+    let before = app.current_target_freq().unwrap();
+
+    app.set_a4(442.0);
+
+    let after = app.current_target_freq().unwrap();
+    assert!((after / before - 442.0 / 440.0).abs() < 1e-4);
+    assert_eq!(app.tuning().unwrap().note_name(), "F#3");
+    let session = app.session().unwrap();
+    assert_eq!(session.a4_reference, 442.0);
+    assert_eq!(session.notes_for_review(), ["F3"]);
+}
+
+#[test]
+fn test_changing_a4_keeps_the_selected_mode() {
+    isolate_data_dir();
+    let mut app = App::new();
+    app.handle_key(KeyCode::Down);
+
+    app.set_a4(442.0);
+    app.handle_key(KeyCode::Enter);
+
+    let session = app.session().expect("Should have a session");
+    assert_eq!(session.mode, TuningMode::Concert);
+    assert_eq!(session.a4_reference, 442.0);
+}
+
+#[test]
+fn test_keyboard_order_view_marks_next_note() {
+    isolate_data_dir();
+    use pianito::ui::components::piano::chars;
+    use pianito::ui::screens::tuning::KeyboardView;
+    use ratatui::{buffer::Buffer, layout::Rect, widgets::Widget};
+
+    let mut app = App::new().with_keyboard_view(KeyboardView::Order);
+    app.handle_key(KeyCode::Down);
+    app.handle_key(KeyCode::Enter);
+
+    let area = Rect::new(0, 0, 120, 30);
+    let mut buf = Buffer::empty(area);
+    app.tuning().unwrap().render(area, &mut buf);
+    let text: String = buf.content().iter().map(|c| c.symbol()).collect();
+    assert!(text.contains(chars::NEXT));
+}
+
+#[test]
+fn test_auto_profile_advances_on_steady_reading() {
+    isolate_data_dir();
+    let temperament = Temperament::new();
+    let mut app = App::new().with_auto_profile(true);
+    app.handle_key(KeyCode::Down);
+    app.handle_key(KeyCode::Down);
+    app.handle_key(KeyCode::Enter);
+    assert_eq!(app.state(), AppState::Profiling);
+
+    // Silence never advances
+    for _ in 0..20 {
+        app.clear_pitch();
+    }
+    assert_eq!(app.profiling().unwrap().current_note_idx(), 0);
+
+    // A steady A0 a little sharp is recorded, then A#0 is up
+    let a0 = temperament.cents_to_frequency(27.5, 3.0);
+    for _ in 0..10 {
+        app.update_pitch(a0, 0.95);
+    }
+    let profiling = app.profiling().unwrap();
+    assert_eq!(profiling.current_note_idx(), 1);
+    let recorded = profiling.profile().notes[0].as_ref().expect("A0 recorded");
+    assert!((recorded.cents - 3.0).abs() < 0.1);
+}
+
+#[test]
+fn test_hold_to_compare_plays_while_held() {
+    isolate_data_dir();
+    let note = Note::from_name("A4").expect("A4 exists");
+    let mut app = App::single_note(note, 440.0);
+    assert_eq!(app.looping_tone(), None);
+
+    app.handle_key(KeyCode::Char('a'));
+    assert_eq!(app.looping_tone(), Some(440.0));
+
+    // Another press while held keeps it playing
+    app.handle_key(KeyCode::Char('a'));
+    assert_eq!(app.looping_tone(), Some(440.0));
+
+    app.handle_key_release(KeyCode::Char('a'));
+    assert_eq!(app.looping_tone(), None);
+}
+
+#[test]
+fn test_hold_to_compare_toggles_without_key_release() {
+    isolate_data_dir();
+    let note = Note::from_name("A4").expect("A4 exists");
+    let mut app = App::single_note(note, 440.0).with_key_release(false);
+
+    app.handle_key(KeyCode::Char('a'));
+    app.handle_key_release(KeyCode::Char('a'));
+    assert_eq!(app.looping_tone(), Some(440.0));
+
+    app.handle_key(KeyCode::Char('a'));
+    assert_eq!(app.looping_tone(), None);
+}
+
+#[test]
+fn test_held_reference_stops_on_note_change() {
+    isolate_data_dir();
+    let mut app = App::new().with_key_release(false);
+    app.handle_key(KeyCode::Down);
+    app.handle_key(KeyCode::Enter);
+
+    // Toggled on, then the tuner moves on without toggling it off
+    app.handle_key(KeyCode::Char('a'));
+    assert!(app.looping_tone().is_some());
+    app.handle_key(KeyCode::Char('s'));
+    assert_eq!(app.looping_tone(), None);
+
+    app.handle_key(KeyCode::Char('a'));
+    app.handle_key(KeyCode::Char('b'));
+    assert_eq!(app.looping_tone(), None);
+
+    // Leaving tuning drops it too, so it doesn't resume on return
+    let detector = PitchDetector::new(SAMPLE_RATE);
+    let mut app = App::new().with_key_release(false);
+    app.handle_key(KeyCode::Down);
+    app.handle_key(KeyCode::Enter);
+    let target = app.current_target_freq().expect("Should have a target");
+    for _ in 0..SMOOTHING_WINDOW {
+        feed_tone(
+            &mut app,
+            &detector,
+            Temperament::new().cents_to_frequency(target, 20.0),
+        );
+    }
+    while app.session().unwrap().completed_notes.is_empty() {
+        app.handle_key(KeyCode::Char(' '));
+    }
+    app.handle_key(KeyCode::Char('a'));
+    app.handle_key(KeyCode::Char('f'));
+    assert_eq!(app.state(), AppState::Complete);
+
+    // Revisit the out-of-tolerance F3
+    app.handle_key(KeyCode::Char('n'));
+    assert_eq!(app.state(), AppState::Tuning);
+    assert_eq!(app.looping_tone(), None);
+}
+
+#[test]
+fn test_string_and_note_cues_on_trichord() {
+    isolate_data_dir();
+    let note = Note::from_name("A4").expect("A4 exists");
+    let mut app = App::single_note(note, 440.0).with_beep(true);
+    let steps = app
+        .tuning()
+        .and_then(|t| t.tuning_step())
+        .map(|s| s.total_steps())
+        .expect("A4 is a trichord");
+    assert!(steps > 1);
+
+    // Each intermediate step gets the short string cue
+    for _ in 1..steps {
+        app.handle_key(KeyCode::Char(' '));
+        let tones = app.take_tone_requests();
+        assert_eq!(tones.len(), 1);
+        assert_eq!(tones[0].frequency, 1318.51);
+    }
+
+    // The last step finishes the note
+    app.handle_key(KeyCode::Char(' '));
+    let tones = app.take_tone_requests();
+    let frequencies: Vec<f32> = tones.iter().take(2).map(|t| t.frequency).collect();
+    assert_eq!(frequencies, vec![1046.5, 1567.98]);
+}
+
+#[test]
+fn test_no_cues_without_beep() {
+    isolate_data_dir();
+    let note = Note::from_name("A4").expect("A4 exists");
+    let mut app = App::single_note(note, 440.0);
+    app.handle_key(KeyCode::Char(' '));
+    assert!(app.take_tone_requests().is_empty());
+}
+
+#[test]
+fn test_relative_tune_targets_follow_tuned_neighbors() {
+    isolate_data_dir();
+    let temperament = Temperament::new();
+    let mut app = App::new();
+    for _ in 0..4 {
+        app.handle_key(KeyCode::Down);
+    }
+    app.handle_key(KeyCode::Enter);
+    assert_eq!(app.state(), AppState::Tuning, "no calibration step");
+
+    // F3 has nothing tuned yet, so its first reading becomes its target
+    let flat_f3 = temperament.cents_to_frequency(temperament.frequency(53), -30.0);
+    app.update_pitch(flat_f3, 1.0);
+    let f3 = app.current_target_freq().expect("F3 target");
+    assert!((f3 - flat_f3).abs() < 0.01, "got {f3}");
+
+    // Leave F3 where the piano has it, recorded 30 cents flat of concert
+    app.handle_key(KeyCode::Char('l'));
+    let recorded = app.session().unwrap().completed_notes[0].final_cents;
+    assert!((recorded + 30.0).abs() < 0.1, "got {recorded}");
+
+    // F#3 is now a semitone above the flat F3, not above concert F3
+    let fs3 = app.current_target_freq().expect("F#3 target");
+    let expected = temperament.cents_to_frequency(temperament.frequency(54), -30.0);
+    assert!(
+        (fs3 - expected).abs() < 0.05,
+        "expected {expected}, got {fs3}"
+    );
+}
+
+#[test]
+fn test_quick_tune_offset_survives_resume() {
+    isolate_data_dir();
+    let temperament = Temperament::new();
+    let mut app = App::new();
+    app.handle_key(KeyCode::Enter);
+    assert_eq!(app.state(), AppState::Calibration);
+
+    // The piano's A4 sits at 430 Hz
+    for _ in 0..100 {
+        if app.state() != AppState::Calibration {
+            break;
+        }
+        app.update_pitch(430.0, 0.95);
+    }
+    assert_eq!(app.state(), AppState::Tuning);
+
+    let session = app.session().expect("Should have a session").clone();
+    let offset = temperament.cents_from_target(430.0, 440.0);
+    assert!((session.piano_offset_cents - offset).abs() < 0.01);
+    let f3 = app.current_target_freq().expect("F3 target");
+    let expected = temperament.cents_to_frequency(temperament.frequency(53), offset);
+    assert!(
+        (f3 - expected).abs() < 0.01,
+        "expected {expected}, got {f3}"
+    );
+
+    // Resuming tunes to the same offset targets
+    let resumed = App::with_session(session);
+    let target = resumed.current_target_freq().expect("F3 target");
+    assert!((target - f3).abs() < 0.01, "expected {f3}, got {target}");
+}
+
+#[test]
+fn test_session_stretch_moves_targets() {
+    isolate_data_dir();
+    let temperament = Temperament::new();
+    let mut curve = StretchCurve::new();
+    curve.set_point(53, 10.0);
+    let session = Session::concert_pitch(440.0).with_stretch(curve);
+
+    let app = App::with_session(session);
+    let f3 = app.current_target_freq().expect("F3 target");
+    let expected = temperament.cents_to_frequency(temperament.frequency(53), 10.0);
+    assert!(
+        (f3 - expected).abs() < 0.01,
+        "expected {expected}, got {f3}"
+    );
+}
+
+#[test]
+fn test_resumed_relative_session_keeps_neighbor_targets() {
+    isolate_data_dir();
+    let temperament = Temperament::new();
+    let mut app = App::new();
+    for _ in 0..4 {
+        app.handle_key(KeyCode::Down);
+    }
+    app.handle_key(KeyCode::Enter);
+
+    // F3 left 30 cents flat, F#3 left 5 cents sharp of its relative target
+    app.update_pitch(
+        temperament.cents_to_frequency(temperament.frequency(53), -30.0),
+        1.0,
+    );
+    app.handle_key(KeyCode::Char('l'));
+    let fs3 = app.current_target_freq().expect("F#3 target");
+    app.update_pitch(temperament.cents_to_frequency(fs3, 5.0), 1.0);
+    app.handle_key(KeyCode::Char('l'));
+    let g3 = app.current_target_freq().expect("G3 target");
+
+    // Resuming targets G3 from the same actual pitches, not from concert
+    let session = app.session().unwrap().clone();
+    assert_eq!(session.mode, TuningMode::Relative);
+    let resumed = App::with_session(session);
+    let target = resumed.current_target_freq().expect("G3 target");
+    assert!((target - g3).abs() < 0.05, "expected {g3}, got {target}");
+    let concert = temperament.frequency(55);
+    assert!((target - concert).abs() > 1.0);
+}
+
+#[test]
+fn test_temperament_check_covers_octave_from_a3() {
+    isolate_data_dir();
+    let detector = PitchDetector::new(SAMPLE_RATE);
+    let a3 = Note::from_name("A3").unwrap();
+    let mut app = App::new().with_temperament_start(a3.midi);
+    app.handle_key(KeyCode::Down);
+    app.handle_key(KeyCode::Enter);
+
+    // Tune A3, then move through the rest of the octave up to A4
+    tune_current_note(&mut app, &detector, 20.0);
+    while app.state() == AppState::Tuning {
+        app.handle_key(KeyCode::Char('s'));
+    }
+    assert_eq!(app.state(), AppState::IntervalCheck);
+
+    let checks = app
+        .interval_check()
+        .expect("Should have a check screen")
+        .checks();
+    assert_eq!(
+        checks.first().map(|c| c.label()).as_deref(),
+        Some("M3 A3-C#4")
+    );
+    assert!(checks
+        .iter()
+        .all(|c| c.lower_midi >= a3.midi && c.upper_midi <= a3.midi + 12));
+}
+
+#[test]
+fn test_temperament_check_follows_the_octave() {
+    isolate_data_dir();
+    let detector = PitchDetector::new(SAMPLE_RATE);
+    let mut app = App::new();
+    app.handle_key(KeyCode::Down);
+    app.handle_key(KeyCode::Enter);
+
+    // Tune F3, then move through the rest of the octave up to F4
+    tune_current_note(&mut app, &detector, 20.0);
+    while app.tuning().is_some_and(|t| t.note_name() != "F4") {
+        app.handle_key(KeyCode::Char('s'));
+    }
+    assert_eq!(app.state(), AppState::Tuning);
+
+    // Completing the last temperament note offers the check
+    app.handle_key(KeyCode::Char('s'));
+    assert_eq!(app.state(), AppState::IntervalCheck);
+    let check = app.interval_check().expect("Should have a check screen");
+    assert_eq!(
+        check.selected().map(|c| c.label()).as_deref(),
+        Some("M3 F3-A3")
+    );
+
+    // Continuing resumes the order above the octave
+    app.handle_key(KeyCode::Enter);
+    assert_eq!(app.state(), AppState::Tuning);
+    assert_eq!(app.tuning().map(|t| t.note_name()), Some("F#4"));
+
+    // Revisiting F4 later doesn't offer it again
+    app.handle_key(KeyCode::Char('b'));
+    assert_eq!(app.tuning().map(|t| t.note_name()), Some("F4"));
+    app.handle_key(KeyCode::Char('s'));
+    assert_eq!(app.state(), AppState::Tuning);
+}
+
+#[test]
+fn test_no_temperament_check_when_octave_skipped() {
+    isolate_data_dir();
+    let mut app = App::new();
+    app.handle_key(KeyCode::Down);
+    app.handle_key(KeyCode::Enter);
+
+    for _ in 0..13 {
+        app.handle_key(KeyCode::Char('s'));
+    }
+    assert_eq!(app.state(), AppState::Tuning);
+    assert_eq!(app.tuning().map(|t| t.note_name()), Some("F#4"));
+}
+
+#[test]
+fn test_comment_attaches_to_recorded_note() {
+    isolate_data_dir();
+    let mut app = App::new();
+    app.handle_key(KeyCode::Down);
+    app.handle_key(KeyCode::Enter);
+
+    // Letters that are shortcuts elsewhere are typed, not acted on
+    app.handle_key(KeyCode::Char('c'));
+    for c in "sticky keyx".chars() {
+        app.handle_key(KeyCode::Char(c));
+    }
+    app.handle_key(KeyCode::Backspace);
+    app.handle_key(KeyCode::Enter);
+    assert_eq!(app.state(), AppState::Tuning);
+    assert_eq!(app.tuning().and_then(|t| t.comment()), Some("sticky key"));
+
+    app.handle_key(KeyCode::Char('s'));
+    let session = app.session().expect("Should have a session");
+    assert_eq!(session.completed_notes.len(), 1);
+    assert_eq!(
+        session.completed_notes[0].comment.as_deref(),
+        Some("sticky key")
+    );
+
+    // The next note starts without a remark, and Esc discards one
+    assert_eq!(app.tuning().and_then(|t| t.comment()), None);
+    app.handle_key(KeyCode::Char('c'));
+    app.handle_key(KeyCode::Char('x'));
+    app.handle_key(KeyCode::Esc);
+    app.handle_key(KeyCode::Char('s'));
+    let session = app.session().expect("Should have a session");
+    assert_eq!(session.completed_notes[1].comment, None);
+}
+
+#[test]
+fn test_mode_select_picks_concert_pitch_preset() {
+    isolate_data_dir();
+    let mut app = App::new().with_a4(440.0);
+    app.handle_key(KeyCode::Down);
+    for _ in 0..3 {
+        app.handle_key(KeyCode::Right);
+    }
+    app.handle_key(KeyCode::Enter);
+
+    let session = app.session().expect("Should have a session");
+    assert_eq!(session.a4_reference, 415.3);
+    let f3 = Temperament::with_a4(415.3).frequency(53);
+    let target = app.current_target_freq().expect("F3 target");
+    assert!((target - f3).abs() < 0.01, "expected {f3}, got {target}");
+}
+
+#[test]
+fn test_profiling_uses_configured_a4() {
+    isolate_data_dir();
+    let mut app = App::new().with_a4(442.0);
+
+    // Quick Tune -> Concert Pitch -> Profile
+    app.handle_key(KeyCode::Down);
+    app.handle_key(KeyCode::Down);
+    app.handle_key(KeyCode::Enter);
+    assert_eq!(app.state(), AppState::Profiling);
+
+    for _ in 0..48 {
+        app.handle_key(KeyCode::Char('s'));
+    }
+    let profiling = app.profiling().expect("Should be profiling");
+    assert_eq!(profiling.current_note().map(|n| n.midi), Some(69));
+    assert_eq!(profiling.target_freq(), Some(442.0));
+}
+
+#[test]
+fn test_octave_beats_coach_the_high_treble() {
+    isolate_data_dir();
+    let temperament = Temperament::new();
+    let mut app = App::new().with_octave_beats(true);
+    app.handle_key(KeyCode::Down);
+    app.handle_key(KeyCode::Enter);
+
+    let skip_to = |app: &mut App, midi: u8| {
+        let target = temperament.frequency(midi);
+        while app
+            .current_target_freq()
+            .is_none_or(|freq| (freq - target).abs() > 0.01)
+        {
+            assert!(
+                matches!(app.state(), AppState::Tuning | AppState::IntervalCheck),
+                "never reached MIDI {midi}"
+            );
+            app.handle_key(KeyCode::Char('s'));
+        }
+    };
+
+    // Leave A5 two cents sharp; it is below the octave beat range
+    skip_to(&mut app, 81);
+    let tuning = app.tuning().expect("Should be tuning");
+    assert_eq!(tuning.target_octave_beats(), None);
+    app.update_pitch(temperament.cents_to_frequency(880.0, 2.0), 1.0);
+    app.handle_key(KeyCode::Char('l'));
+
+    // A6's target now beats against the sharp A5
+    skip_to(&mut app, 93);
+    let beats = app
+        .tuning()
+        .and_then(|t| t.target_octave_beats())
+        .expect("A6 should be coached by octave beats");
+    assert!((beats - 2.03).abs() < 0.05, "got {beats}");
+
+    // Playing exactly twice the A5 pitch is beatless
+    app.update_pitch(2.0 * temperament.cents_to_frequency(880.0, 2.0), 1.0);
+    let live = app.tuning().and_then(|t| t.octave_beats()).unwrap();
+    assert!(live < 0.05, "got {live}");
+}
+
+#[test]
+fn test_long_silence_suggests_checking_the_microphone() {
+    isolate_data_dir();
+    let mut app = App::new();
+
+    // Silence on the menu doesn't count
+    app.update_input_level(0.0, 30.0);
+    app.handle_key(KeyCode::Down);
+    app.handle_key(KeyCode::Enter);
+    assert!(!app.tuning().expect("Should be tuning").no_audio());
+
+    for _ in 0..200 {
+        app.update_input_level(0.0, 0.05);
+    }
+    assert!(app.tuning().unwrap().no_audio());
+
+    // Any audio clears the hint
+    app.update_input_level(0.002, 0.05);
+    assert!(!app.tuning().unwrap().no_audio());
+}
+
+#[test]
+fn test_rebuilding_the_screen_logs_one_note_entry() {
+    isolate_data_dir();
+    let mut app = App::new();
+    app.handle_key(KeyCode::Down);
+    app.handle_key(KeyCode::Enter);
+
+    // Changing the spelling redraws F3 but is still the same visit
+    let mut app = app.with_spelling(NoteSpelling::Flats);
+    app.set_a4(441.0);
+
+    let entered = app
+        .session()
+        .expect("Should have a session")
+        .events
+        .iter()
+        .filter(|e| matches!(e.kind, SessionEventKind::NoteEntered { .. }))
+        .count();
+    assert_eq!(entered, 1);
+}
+
+#[test]
+fn test_session_logs_note_events_in_order() {
+    isolate_data_dir();
+    let detector = PitchDetector::new(SAMPLE_RATE);
+    let mut app = App::new();
+    app.handle_key(KeyCode::Down);
+    app.handle_key(KeyCode::Enter);
+
+    tune_current_note(&mut app, &detector, 20.0);
+    app.handle_key(KeyCode::Char('s'));
+
+    let events: Vec<String> = app
+        .session()
+        .expect("Should have a session")
+        .events
+        .iter()
+        .map(|e| match &e.kind {
+            SessionEventKind::NoteEntered { note } => format!("enter {note}"),
+            SessionEventKind::NoteConfirmed { note, .. } => format!("confirm {note}"),
+            SessionEventKind::NoteSkipped { note } => format!("skip {note}"),
+            other => format!("{other:?}"),
+        })
+        .collect();
+    assert_eq!(
+        events,
+        [
+            "enter F3",
+            "confirm F3",
+            "enter F#3",
+            "skip F#3",
+            "enter G3"
+        ]
+    );
+}